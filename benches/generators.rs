@@ -13,6 +13,7 @@ extern crate rand;
 extern crate rand_isaac;
 extern crate rand_chacha;
 extern crate rand_hc;
+extern crate rand_aes;
 extern crate rand_pcg;
 extern crate rand_xorshift;
 extern crate rand_xoshiro;
@@ -27,8 +28,9 @@ use rand::prelude::*;
 use rand::rngs::adapter::ReseedingRng;
 use rand::rngs::{OsRng, JitterRng, EntropyRng};
 use rand_isaac::{IsaacRng, Isaac64Rng};
-use rand_chacha::ChaChaRng;
+use rand_chacha::{ChaChaRng, ChaCha8Rng, ChaCha12Rng};
 use rand_hc::{Hc128Rng, Hc128Core};
+use rand_aes::{Aes128CtrRng, Aes256CtrRng};
 use rand_pcg::{Lcg64Xsh32, Mcg128Xsl64};
 use rand_xorshift::XorShiftRng;
 use rand_xoshiro::{Xoshiro256StarStar, Xoshiro256Plus, Xoshiro128StarStar,
@@ -64,8 +66,12 @@ gen_bytes!(gen_bytes_xoroshiro64star, Xoroshiro64Star::from_entropy());
 gen_bytes!(gen_bytes_splitmix64, SplitMix64::from_entropy());
 gen_bytes!(gen_bytes_lcg64_xsh32, Lcg64Xsh32::from_entropy());
 gen_bytes!(gen_bytes_mcg128_xsh64, Mcg128Xsl64::from_entropy());
+gen_bytes!(gen_bytes_chacha8, ChaCha8Rng::from_entropy());
+gen_bytes!(gen_bytes_chacha12, ChaCha12Rng::from_entropy());
 gen_bytes!(gen_bytes_chacha20, ChaChaRng::from_entropy());
 gen_bytes!(gen_bytes_hc128, Hc128Rng::from_entropy());
+gen_bytes!(gen_bytes_aes128ctr, Aes128CtrRng::from_entropy());
+gen_bytes!(gen_bytes_aes256ctr, Aes256CtrRng::from_entropy());
 gen_bytes!(gen_bytes_isaac, IsaacRng::from_entropy());
 gen_bytes!(gen_bytes_isaac64, Isaac64Rng::from_entropy());
 gen_bytes!(gen_bytes_std, StdRng::from_entropy());
@@ -101,8 +107,12 @@ gen_uint!(gen_u32_xoroshiro64star, u32, Xoroshiro64Star::from_entropy());
 gen_uint!(gen_u32_splitmix64, u32, SplitMix64::from_entropy());
 gen_uint!(gen_u32_lcg64_xsh32, u32, Lcg64Xsh32::from_entropy());
 gen_uint!(gen_u32_mcg128_xsh64, u32, Mcg128Xsl64::from_entropy());
+gen_uint!(gen_u32_chacha8, u32, ChaCha8Rng::from_entropy());
+gen_uint!(gen_u32_chacha12, u32, ChaCha12Rng::from_entropy());
 gen_uint!(gen_u32_chacha20, u32, ChaChaRng::from_entropy());
 gen_uint!(gen_u32_hc128, u32, Hc128Rng::from_entropy());
+gen_uint!(gen_u32_aes128ctr, u32, Aes128CtrRng::from_entropy());
+gen_uint!(gen_u32_aes256ctr, u32, Aes256CtrRng::from_entropy());
 gen_uint!(gen_u32_isaac, u32, IsaacRng::from_entropy());
 gen_uint!(gen_u32_isaac64, u32, Isaac64Rng::from_entropy());
 gen_uint!(gen_u32_std, u32, StdRng::from_entropy());
@@ -121,8 +131,12 @@ gen_uint!(gen_u64_xoroshiro64star, u64, Xoroshiro64Star::from_entropy());
 gen_uint!(gen_u64_splitmix64, u64, SplitMix64::from_entropy());
 gen_uint!(gen_u64_lcg64_xsh32, u64, Lcg64Xsh32::from_entropy());
 gen_uint!(gen_u64_mcg128_xsh64, u64, Mcg128Xsl64::from_entropy());
+gen_uint!(gen_u64_chacha8, u64, ChaCha8Rng::from_entropy());
+gen_uint!(gen_u64_chacha12, u64, ChaCha12Rng::from_entropy());
 gen_uint!(gen_u64_chacha20, u64, ChaChaRng::from_entropy());
 gen_uint!(gen_u64_hc128, u64, Hc128Rng::from_entropy());
+gen_uint!(gen_u64_aes128ctr, u64, Aes128CtrRng::from_entropy());
+gen_uint!(gen_u64_aes256ctr, u64, Aes256CtrRng::from_entropy());
 gen_uint!(gen_u64_isaac, u64, IsaacRng::from_entropy());
 gen_uint!(gen_u64_isaac64, u64, Isaac64Rng::from_entropy());
 gen_uint!(gen_u64_std, u64, StdRng::from_entropy());
@@ -169,6 +183,7 @@ init_gen!(init_hc128, Hc128Rng);
 init_gen!(init_isaac, IsaacRng);
 init_gen!(init_isaac64, Isaac64Rng);
 init_gen!(init_chacha, ChaChaRng);
+init_gen!(init_aes128ctr, Aes128CtrRng);
 
 #[bench]
 fn init_jitter(b: &mut Bencher) {
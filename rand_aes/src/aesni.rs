@@ -0,0 +1,113 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! AES encryption with the AES-NI instructions of x86 processors.
+//!
+//! The round keys are expanded by the software implementation; the
+//! instructions are only used to encrypt up to eight blocks in parallel,
+//! which hides their latency.
+
+use cipher::BLOCK_BYTES;
+
+/// Encrypt the blocks of `blocks` in place with AES-NI, if supported by the
+/// CPU.
+///
+/// Returns `false`, leaving `blocks` untouched, if AES-NI is not available.
+#[allow(unused_variables, unreachable_code)]
+pub(crate) fn encrypt_blocks(round_keys: &[[u8; BLOCK_BYTES]], blocks: &mut [u8]) -> bool {
+    #[cfg(all(rustc_1_27, any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if x86::has_aes() {
+            unsafe { x86::encrypt_blocks(round_keys, blocks) };
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(all(rustc_1_27, any(target_arch = "x86", target_arch = "x86_64")))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+    use cipher::BLOCK_BYTES;
+
+    const PARALLEL_BLOCKS: usize = 8;
+
+    /// Whether AES-NI is available.
+    #[inline]
+    pub fn has_aes() -> bool {
+        #[cfg(feature="std")] {
+            is_x86_feature_detected!("aes")
+        }
+        #[cfg(not(feature="std"))] {
+            cfg!(all(target_feature = "aes", target_feature = "sse2"))
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn load(bytes: &[u8]) -> __m128i {
+        _mm_loadu_si128(bytes.as_ptr() as *const __m128i)
+    }
+
+    #[target_feature(enable = "aes")]
+    pub unsafe fn encrypt_blocks(round_keys: &[[u8; BLOCK_BYTES]], blocks: &mut [u8]) {
+        let rounds = round_keys.len() - 1;
+        for chunk in blocks.chunks_mut(PARALLEL_BLOCKS * BLOCK_BYTES) {
+            let n = chunk.len() / BLOCK_BYTES;
+            let key = load(&round_keys[0]);
+            let mut x = [_mm_setzero_si128(); PARALLEL_BLOCKS];
+            for (i, x) in x[..n].iter_mut().enumerate() {
+                *x = _mm_xor_si128(load(&chunk[i * BLOCK_BYTES..]), key);
+            }
+            for round_key in &round_keys[1..rounds] {
+                let key = load(round_key);
+                for x in x[..n].iter_mut() {
+                    *x = _mm_aesenc_si128(*x, key);
+                }
+            }
+            let key = load(&round_keys[rounds]);
+            for (i, x) in x[..n].iter().enumerate() {
+                let ptr = chunk[i * BLOCK_BYTES..].as_mut_ptr() as *mut __m128i;
+                _mm_storeu_si128(ptr, _mm_aesenclast_si128(*x, key));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cipher::{BLOCK_BYTES, MAX_ROUND_KEYS};
+    use soft;
+
+    #[test]
+    fn test_aesni_matches_soft() {
+        let mut key = [0u8; 32];
+        for (i, k) in key.iter_mut().enumerate() {
+            *k = (i * 37 + 11) as u8;
+        }
+        // More blocks than are encrypted in parallel
+        let mut blocks = [0u8; 12 * BLOCK_BYTES];
+        for (i, b) in blocks.iter_mut().enumerate() {
+            *b = (i * 101 + 3) as u8;
+        }
+        for &key_bytes in &[16, 32] {
+            let rounds = key_bytes / 4 + 6;
+            let mut round_keys = [[0u8; BLOCK_BYTES]; MAX_ROUND_KEYS];
+            soft::expand_key(&key[..key_bytes], &mut round_keys);
+
+            let mut expected = blocks;
+            soft::encrypt_blocks(&round_keys[..rounds + 1], &mut expected[..]);
+            let mut results = blocks;
+            if super::encrypt_blocks(&round_keys[..rounds + 1], &mut results[..]) {
+                assert_eq!(&results[..], &expected[..]);
+            }
+        }
+    }
+}
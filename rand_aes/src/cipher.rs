@@ -0,0 +1,108 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The AES block cipher.
+
+use core::fmt;
+use {aesni, soft};
+
+pub(crate) const BLOCK_BYTES: usize = 16;
+pub(crate) const MAX_ROUND_KEYS: usize = 15; // 14 rounds of AES-256
+
+macro_rules! aes_impl {
+    ($Aes:ident, $key_bytes:expr, $rounds:expr, $doc:expr) => {
+        #[doc=$doc]
+        ///
+        /// This encrypts independent 16-byte blocks, i.e. in electronic
+        /// codebook (ECB) mode, with AES-NI if available and with the
+        /// constant-time software implementation otherwise. It is the block
+        /// cipher of the counter mode generators, and is provided to build
+        /// other constructions on AES.
+        #[derive(Clone)]
+        pub struct $Aes {
+            round_keys: [[u8; BLOCK_BYTES]; MAX_ROUND_KEYS],
+        }
+
+        // Custom Debug implementation that does not expose the round keys
+        impl fmt::Debug for $Aes {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, concat!(stringify!($Aes), " {{}}"))
+            }
+        }
+
+        impl $Aes {
+            /// Construct a new instance, expanding the `key` into the round
+            /// keys.
+            pub fn new(key: [u8; $key_bytes]) -> Self {
+                let mut round_keys = [[0; BLOCK_BYTES]; MAX_ROUND_KEYS];
+                soft::expand_key(&key, &mut round_keys);
+                $Aes { round_keys }
+            }
+
+            /// Encrypt a single block in place.
+            pub fn encrypt_block(&self, block: &mut [u8; BLOCK_BYTES]) {
+                self.encrypt_blocks(block);
+            }
+
+            /// Encrypt consecutive blocks in place.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the length of `blocks` is not a multiple of 16.
+            pub fn encrypt_blocks(&self, blocks: &mut [u8]) {
+                assert!(blocks.len() % BLOCK_BYTES == 0,
+                        "length is not a multiple of the AES block size");
+                let round_keys = &self.round_keys[..$rounds + 1];
+                if !aesni::encrypt_blocks(round_keys, blocks) {
+                    soft::encrypt_blocks(round_keys, blocks);
+                }
+            }
+        }
+    }
+}
+
+aes_impl!(Aes128, 16, 10, "The AES block cipher with a 128-bit key.");
+aes_impl!(Aes256, 32, 14, "The AES block cipher with a 256-bit key.");
+
+#[cfg(test)]
+mod test {
+    use super::{Aes128, Aes256};
+
+    #[test]
+    fn test_aes_cipher_true_values() {
+        // Examples from FIPS 197, appendix C
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+                         0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+        let mut key = [0u8; 32];
+        for (i, k) in key.iter_mut().enumerate() {
+            *k = i as u8;
+        }
+
+        let mut key128 = [0u8; 16];
+        key128.copy_from_slice(&key[..16]);
+        let mut block = plaintext;
+        Aes128::new(key128).encrypt_block(&mut block);
+        assert_eq!(block, [0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30,
+                           0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a]);
+
+        let mut block = plaintext;
+        Aes256::new(key).encrypt_block(&mut block);
+        assert_eq!(block, [0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf,
+                           0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60, 0x89]);
+
+        // Several blocks at once, not filling a whole batch
+        let mut blocks = [0u8; 48];
+        for chunk in blocks.chunks_mut(16) {
+            chunk.copy_from_slice(&plaintext);
+        }
+        Aes256::new(key).encrypt_blocks(&mut blocks);
+        for chunk in blocks.chunks(16) {
+            assert_eq!(chunk, &block[..]);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The AES-CTR random number generators.
+
+use core::fmt;
+use rand_core::{CryptoRng, RngCore, SeedableRng, Error, le};
+use rand_core::block::{BlockRngCore, BlockRng};
+use cipher::{Aes128, Aes256, BLOCK_BYTES};
+
+const BUF_BLOCKS: usize = 8; // blocks encrypted at once
+const BUF_BYTES: usize = BUF_BLOCKS * BLOCK_BYTES;
+
+// The 128-bit counter block is kept as its big-endian high and low halves.
+fn read_counter(block: &[u8; BLOCK_BYTES]) -> [u64; 2] {
+    let mut counter = [0u64; 2];
+    for (half, bytes) in counter.iter_mut().zip(block.chunks(8)) {
+        for &b in bytes {
+            *half = (*half << 8) | b as u64;
+        }
+    }
+    counter
+}
+
+fn write_counter(block: &mut [u8], counter: &[u64; 2]) {
+    for (half, bytes) in counter.iter().zip(block.chunks_mut(8)) {
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (half >> (56 - 8 * i)) as u8;
+        }
+    }
+}
+
+macro_rules! aes_ctr_impl {
+    ($AesCtrRng:ident, $AesCtrCore:ident, $Aes:ident, $key_bytes:expr,
+     $doc:expr, $core_doc:expr) => {
+        #[doc=$doc]
+        ///
+        /// AES (Rijndael) is the block cipher standardized in FIPS 197[^1],
+        /// which we use in counter (CTR) mode as specified in NIST SP
+        /// 800-38A[^2]: the output is the encryption of successive values of a
+        /// 128-bit counter block, incremented as a big-endian integer. Its
+        /// state is small: the expanded key and the counter, besides the output
+        /// buffer.
+        ///
+        /// On x86 and x86-64 processors with the AES-NI instruction set, we
+        /// encrypt eight blocks at once with these instructions, which makes
+        /// AES-CTR one of the fastest cryptographically secure generators.
+        /// With the `std` feature, AES-NI support is detected at runtime;
+        /// otherwise it is only used when enabled at compile time. Elsewhere
+        /// we fall back to a portable implementation which is constant-time,
+        /// using no lookup tables indexed by secret data, but considerably
+        /// slower.
+        ///
+        /// The seed is the AES key, and the counter starts at zero. To use a
+        /// given initial counter block, for example to reproduce an AES-CTR
+        /// keystream, use [`from_key_and_counter`]. The counter cycles after
+        /// 2<sup>128</sup> blocks.
+        ///
+        /// This implementation uses an output buffer of eight blocks (32 `u32`
+        /// words), in which the keystream bytes are read as little-endian
+        /// words, and uses [`BlockRng`] to implement the [`RngCore`] methods.
+        ///
+        /// [^1]: NIST, [*FIPS 197: Advanced Encryption Standard (AES)*](
+        ///       https://doi.org/10.6028/NIST.FIPS.197)
+        ///
+        /// [^2]: M. Dworkin, [*NIST SP 800-38A: Recommendation for Block Cipher
+        ///       Modes of Operation*](https://doi.org/10.6028/NIST.SP.800-38A)
+        ///
+        /// [`from_key_and_counter`]: #method.from_key_and_counter
+        /// [`BlockRng`]: rand_core::block::BlockRng
+        /// [`RngCore`]: rand_core::RngCore
+        #[derive(Clone, Debug)]
+        pub struct $AesCtrRng(BlockRng<$AesCtrCore>);
+
+        impl RngCore for $AesCtrRng {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                self.0.next_u32()
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                self.0.next_u64()
+            }
+
+            #[inline]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.0.fill_bytes(dest)
+            }
+
+            #[inline]
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.0.try_fill_bytes(dest)
+            }
+        }
+
+        impl SeedableRng for $AesCtrRng {
+            type Seed = <$AesCtrCore as SeedableRng>::Seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                $AesCtrRng(BlockRng::<$AesCtrCore>::from_seed(seed))
+            }
+
+            fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
+                BlockRng::<$AesCtrCore>::from_rng(rng).map($AesCtrRng)
+            }
+        }
+
+        impl CryptoRng for $AesCtrRng {}
+
+        impl $AesCtrRng {
+            /// Construct a generator from a `key` and the initial `counter`
+            /// block, producing the AES-CTR keystream of NIST SP 800-38A.
+            pub fn from_key_and_counter(key: [u8; $key_bytes],
+                                        counter: [u8; BLOCK_BYTES]) -> Self
+            {
+                $AesCtrRng(BlockRng::new($AesCtrCore::from_key_and_counter(key, counter)))
+            }
+        }
+
+        #[doc=$core_doc]
+        #[derive(Clone)]
+        pub struct $AesCtrCore {
+            cipher: $Aes,
+            counter: [u64; 2],
+        }
+
+        // Custom Debug implementation that does not expose the internal state
+        impl fmt::Debug for $AesCtrCore {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, concat!(stringify!($AesCtrCore), " {{}}"))
+            }
+        }
+
+        impl $AesCtrCore {
+            /// Construct a core from a `key` and the initial `counter` block.
+            pub fn from_key_and_counter(key: [u8; $key_bytes],
+                                        counter: [u8; BLOCK_BYTES]) -> Self
+            {
+                $AesCtrCore { cipher: $Aes::new(key), counter: read_counter(&counter) }
+            }
+        }
+
+        impl BlockRngCore for $AesCtrCore {
+            type Item = u32;
+            type Results = [u32; BUF_BYTES / 4];
+
+            fn generate(&mut self, results: &mut Self::Results) {
+                let mut blocks = [0u8; BUF_BYTES];
+                for block in blocks.chunks_mut(BLOCK_BYTES) {
+                    write_counter(block, &self.counter);
+                    self.counter[1] = self.counter[1].wrapping_add(1);
+                    if self.counter[1] == 0 {
+                        self.counter[0] = self.counter[0].wrapping_add(1);
+                    }
+                }
+                self.cipher.encrypt_blocks(&mut blocks);
+                le::read_u32_into(&blocks, results);
+            }
+        }
+
+        impl SeedableRng for $AesCtrCore {
+            type Seed = [u8; $key_bytes];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                $AesCtrCore::from_key_and_counter(seed, [0; BLOCK_BYTES])
+            }
+        }
+
+        impl CryptoRng for $AesCtrCore {}
+
+        impl From<$AesCtrCore> for $AesCtrRng {
+            fn from(core: $AesCtrCore) -> Self {
+                $AesCtrRng(BlockRng::new(core))
+            }
+        }
+    }
+}
+
+aes_ctr_impl!(Aes128CtrRng, Aes128CtrCore, Aes128, 16,
+              "A cryptographically secure random number generator that uses \
+               AES-128 in counter mode.",
+              "The core of `Aes128CtrRng`, used with `BlockRng`.");
+aes_ctr_impl!(Aes256CtrRng, Aes256CtrCore, Aes256, 32,
+              "A cryptographically secure random number generator that uses \
+               AES-256 in counter mode.",
+              "The core of `Aes256CtrRng`, used with `BlockRng`.");
+
+#[cfg(test)]
+mod test {
+    use ::rand_core::{RngCore, SeedableRng};
+    use super::{Aes128CtrRng, Aes256CtrRng};
+
+    // Plaintext and initial counter block of the CTR examples of
+    // NIST SP 800-38A, appendix F.5
+    const PLAINTEXT: [u8; 64] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96,
+        0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+        0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c,
+        0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+        0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11,
+        0xe5, 0xfb, 0xc1, 0x19, 0x1a, 0x0a, 0x52, 0xef,
+        0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17,
+        0xad, 0x2b, 0x41, 0x7b, 0xe6, 0x6c, 0x37, 0x10];
+    const COUNTER: [u8; 16] = [
+        0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+        0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff];
+
+    fn check_ciphertext<R: RngCore>(rng: &mut R, expected: &[u8; 64]) {
+        let mut keystream = [0u8; 64];
+        rng.fill_bytes(&mut keystream);
+        for i in 0..64 {
+            assert_eq!(PLAINTEXT[i] ^ keystream[i], expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_aes128_ctr_true_values() {
+        // CTR-AES128.Encrypt, NIST SP 800-38A, F.5.1
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6,
+                   0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let expected = [
+            0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26,
+            0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce,
+            0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff,
+            0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff, 0xfd, 0xff,
+            0x5a, 0xe4, 0xdf, 0x3e, 0xdb, 0xd5, 0xd3, 0x5e,
+            0x5b, 0x4f, 0x09, 0x02, 0x0d, 0xb0, 0x3e, 0xab,
+            0x1e, 0x03, 0x1d, 0xda, 0x2f, 0xbe, 0x03, 0xd1,
+            0x79, 0x21, 0x70, 0xa0, 0xf3, 0x00, 0x9c, 0xee];
+        let mut rng = Aes128CtrRng::from_key_and_counter(key, COUNTER);
+        check_ciphertext(&mut rng, &expected);
+    }
+
+    #[test]
+    fn test_aes256_ctr_true_values() {
+        // CTR-AES256.Encrypt, NIST SP 800-38A, F.5.5
+        let key = [0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe,
+                   0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81,
+                   0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7,
+                   0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4];
+        let expected = [
+            0x60, 0x1e, 0xc3, 0x13, 0x77, 0x57, 0x89, 0xa5,
+            0xb7, 0xa7, 0xf5, 0x04, 0xbb, 0xf3, 0xd2, 0x28,
+            0xf4, 0x43, 0xe3, 0xca, 0x4d, 0x62, 0xb5, 0x9a,
+            0xca, 0x84, 0xe9, 0x90, 0xca, 0xca, 0xf5, 0xc5,
+            0x2b, 0x09, 0x30, 0xda, 0xa2, 0x3d, 0xe9, 0x4c,
+            0xe8, 0x70, 0x17, 0xba, 0x2d, 0x84, 0x98, 0x8d,
+            0xdf, 0xc9, 0xc5, 0x8d, 0xb6, 0x7a, 0xad, 0xa6,
+            0x13, 0xc2, 0xdd, 0x08, 0x45, 0x79, 0x41, 0xa6];
+        let mut rng = Aes256CtrRng::from_key_and_counter(key, COUNTER);
+        check_ciphertext(&mut rng, &expected);
+    }
+
+    #[test]
+    fn test_aes_ctr_counter_carry() {
+        // The low half of the counter block overflows after four blocks,
+        // in the middle of the output buffer.
+        let key = [7u8; 32];
+        let mut rng1 = Aes256CtrRng::from_key_and_counter(key,
+            [0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfc]);
+        let mut rng2 = Aes256CtrRng::from_key_and_counter(key,
+            [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        for _ in 0..16 { rng1.next_u32(); }
+        for _ in 0..64 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_aes_ctr_construction() {
+        let seed = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let mut rng1 = Aes128CtrRng::from_seed(seed);
+        let mut rng2 = Aes128CtrRng::from_key_and_counter(seed, [0; 16]);
+        for _ in 0..40 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+
+        let mut rng3 = Aes256CtrRng::from_rng(&mut rng1).unwrap();
+        let mut clone = rng3.clone();
+        for _ in 0..40 {
+            assert_eq!(rng3.next_u32(), clone.next_u32());
+        }
+    }
+}
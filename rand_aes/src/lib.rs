@@ -0,0 +1,31 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The AES-CTR random number generators, and the AES block cipher.
+
+#![doc(html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk.png",
+       html_favicon_url = "https://www.rust-lang.org/favicon.ico",
+       html_root_url = "https://rust-random.github.io/rand/")]
+
+#![deny(missing_docs)]
+#![deny(missing_debug_implementations)]
+#![doc(test(attr(allow(unused_variables), deny(warnings))))]
+
+#![no_std]
+
+#[cfg(feature="std")] #[macro_use] extern crate std;
+
+pub extern crate rand_core;
+
+mod aesni;
+mod cipher;
+mod ctr;
+mod soft;
+
+pub use cipher::{Aes128, Aes256};
+pub use ctr::{Aes128CtrRng, Aes128CtrCore, Aes256CtrRng, Aes256CtrCore};
@@ -0,0 +1,225 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Portable, constant-time implementation of AES encryption.
+//!
+//! Table lookups indexed by secret data leak through the cache, so instead
+//! of the usual S-box table we evaluate the S-box as a boolean circuit on
+//! bitsliced bytes: each of eight `u64` words holds one bit of up to 64
+//! bytes. The other steps use no secret-dependent branches or indices.
+
+use cipher::{BLOCK_BYTES, MAX_ROUND_KEYS};
+
+/// Expand `key`, of 16 or 32 bytes, into the `rounds + 1` round keys of
+/// AES-128 or AES-256, as specified in FIPS 197 section 5.2.
+pub(crate) fn expand_key(key: &[u8], round_keys: &mut [[u8; BLOCK_BYTES]; MAX_ROUND_KEYS]) {
+    let nk = key.len() / 4;
+    debug_assert!(nk == 4 || nk == 8);
+    let total_words = 4 * (nk + 7);
+
+    let mut w = [[0u8; 4]; 4 * MAX_ROUND_KEYS];
+    for i in 0..nk {
+        w[i].copy_from_slice(&key[4 * i..4 * i + 4]);
+    }
+    let mut rcon = 1u8;
+    for i in nk..total_words {
+        let mut temp = w[i - 1];
+        if i % nk == 0 {
+            temp = [temp[1], temp[2], temp[3], temp[0]];
+            sub_bytes(&mut temp);
+            temp[0] ^= rcon;
+            rcon = xtime(rcon);
+        } else if nk > 6 && i % nk == 4 {
+            sub_bytes(&mut temp);
+        }
+        for j in 0..4 {
+            w[i][j] = w[i - nk][j] ^ temp[j];
+        }
+    }
+
+    for (i, word) in w[..total_words].iter().enumerate() {
+        round_keys[i / 4][4 * (i % 4)..4 * (i % 4) + 4].copy_from_slice(word);
+    }
+}
+
+/// Encrypt the blocks of `blocks` in place with the given round keys.
+pub(crate) fn encrypt_blocks(round_keys: &[[u8; BLOCK_BYTES]], blocks: &mut [u8]) {
+    let rounds = round_keys.len() - 1;
+    // `sub_bytes` handles at most 64 bytes at once
+    for chunk in blocks.chunks_mut(64) {
+        add_round_key(chunk, &round_keys[0]);
+        for round_key in &round_keys[1..rounds] {
+            sub_bytes(chunk);
+            shift_rows(chunk);
+            mix_columns(chunk);
+            add_round_key(chunk, round_key);
+        }
+        sub_bytes(chunk);
+        shift_rows(chunk);
+        add_round_key(chunk, &round_keys[rounds]);
+    }
+}
+
+fn add_round_key(blocks: &mut [u8], round_key: &[u8; BLOCK_BYTES]) {
+    for block in blocks.chunks_mut(BLOCK_BYTES) {
+        for (b, k) in block.iter_mut().zip(round_key.iter()) {
+            *b ^= *k;
+        }
+    }
+}
+
+// The state is stored column by column, so byte `r + 4 * c` is in row `r`
+// and column `c`; row `r` is rotated left by `r` columns.
+fn shift_rows(blocks: &mut [u8]) {
+    for block in blocks.chunks_mut(BLOCK_BYTES) {
+        let mut s = [0u8; BLOCK_BYTES];
+        s.copy_from_slice(block);
+        for r in 1..4 {
+            for c in 0..4 {
+                block[r + 4 * c] = s[r + 4 * ((c + r) % 4)];
+            }
+        }
+    }
+}
+
+fn mix_columns(blocks: &mut [u8]) {
+    for column in blocks.chunks_mut(4) {
+        let (a0, a1, a2, a3) = (column[0], column[1], column[2], column[3]);
+        let all = a0 ^ a1 ^ a2 ^ a3;
+        column[0] ^= all ^ xtime(a0 ^ a1);
+        column[1] ^= all ^ xtime(a1 ^ a2);
+        column[2] ^= all ^ xtime(a2 ^ a3);
+        column[3] ^= all ^ xtime(a3 ^ a0);
+    }
+}
+
+/// Multiplication by `x` in GF(2<sup>8</sup>), without branches.
+#[inline]
+fn xtime(b: u8) -> u8 {
+    (b << 1) ^ ((b >> 7) * 0x1b)
+}
+
+/// Apply the AES S-box to each of up to 64 bytes.
+fn sub_bytes(bytes: &mut [u8]) {
+    debug_assert!(bytes.len() <= 64);
+    // Bitslice: bit `j` of `u[i]` is bit `7 - i` of byte `j`
+    let mut u = [0u64; 8];
+    for (j, &b) in bytes.iter().enumerate() {
+        for (i, plane) in u.iter_mut().enumerate() {
+            *plane |= (((b >> (7 - i)) & 1) as u64) << j;
+        }
+    }
+    let s = sbox_circuit(u);
+    for (j, b) in bytes.iter_mut().enumerate() {
+        let mut x = 0;
+        for (i, plane) in s.iter().enumerate() {
+            x |= (((plane >> j) & 1) as u8) << (7 - i);
+        }
+        *b = x;
+    }
+}
+
+/// The AES S-box as a circuit of 113 gates, from J. Boyar and R. Peralta,
+/// [*A depth-16 circuit for the AES S-box*](https://eprint.iacr.org/2011/332),
+/// applied to bitsliced bytes, most significant bit first.
+fn sbox_circuit(u: [u64; 8]) -> [u64; 8] {
+    let (u0, u1, u2, u3, u4, u5, u6, u7) = (u[0], u[1], u[2], u[3], u[4], u[5], u[6], u[7]);
+
+    // Top linear transformation
+    let t1 = u0 ^ u3;    let t2 = u0 ^ u5;    let t3 = u0 ^ u6;
+    let t4 = u3 ^ u5;    let t5 = u4 ^ u6;    let t6 = t1 ^ t5;
+    let t7 = u1 ^ u2;    let t8 = u7 ^ t6;    let t9 = u7 ^ t7;
+    let t10 = t6 ^ t7;   let t11 = u1 ^ u5;   let t12 = u2 ^ u5;
+    let t13 = t3 ^ t4;   let t14 = t6 ^ t11;  let t15 = t5 ^ t11;
+    let t16 = t5 ^ t12;  let t17 = t9 ^ t16;  let t18 = u3 ^ u7;
+    let t19 = t7 ^ t18;  let t20 = t1 ^ t19;  let t21 = u6 ^ u7;
+    let t22 = t7 ^ t21;  let t23 = t2 ^ t22;  let t24 = t2 ^ t10;
+    let t25 = t20 ^ t17; let t26 = t3 ^ t16;  let t27 = t1 ^ t12;
+
+    // Middle non-linear section
+    let m1 = t13 & t6;   let m2 = t23 & t8;   let m3 = t14 ^ m1;
+    let m4 = t19 & u7;   let m5 = m4 ^ m1;    let m6 = t3 & t16;
+    let m7 = t22 & t9;   let m8 = t26 ^ m6;   let m9 = t20 & t17;
+    let m10 = m9 ^ m6;   let m11 = t1 & t15;  let m12 = t4 & t27;
+    let m13 = m12 ^ m11; let m14 = t2 & t10;  let m15 = m14 ^ m11;
+    let m16 = m3 ^ m2;   let m17 = m5 ^ t24;  let m18 = m8 ^ m7;
+    let m19 = m10 ^ m15; let m20 = m16 ^ m13; let m21 = m17 ^ m15;
+    let m22 = m18 ^ m13; let m23 = m19 ^ t25; let m24 = m22 ^ m23;
+    let m25 = m22 & m20; let m26 = m21 ^ m25; let m27 = m20 ^ m21;
+    let m28 = m23 ^ m25; let m29 = m28 & m27; let m30 = m26 & m24;
+    let m31 = m20 & m23; let m32 = m27 & m31; let m33 = m27 ^ m25;
+    let m34 = m21 & m22; let m35 = m24 & m34; let m36 = m24 ^ m25;
+    let m37 = m21 ^ m29; let m38 = m32 ^ m33; let m39 = m23 ^ m30;
+    let m40 = m35 ^ m36; let m41 = m38 ^ m40; let m42 = m37 ^ m39;
+    let m43 = m37 ^ m38; let m44 = m39 ^ m40; let m45 = m42 ^ m41;
+    let m46 = m44 & t6;  let m47 = m40 & t8;  let m48 = m39 & u7;
+    let m49 = m43 & t16; let m50 = m38 & t9;  let m51 = m37 & t17;
+    let m52 = m42 & t15; let m53 = m45 & t27; let m54 = m41 & t10;
+    let m55 = m44 & t13; let m56 = m40 & t23; let m57 = m39 & t19;
+    let m58 = m43 & t3;  let m59 = m38 & t22; let m60 = m37 & t20;
+    let m61 = m42 & t1;  let m62 = m45 & t4;  let m63 = m41 & t2;
+
+    // Bottom linear transformation
+    let l0 = m61 ^ m62;  let l1 = m50 ^ m56;  let l2 = m46 ^ m48;
+    let l3 = m47 ^ m55;  let l4 = m54 ^ m58;  let l5 = m49 ^ m61;
+    let l6 = m62 ^ l5;   let l7 = m46 ^ l3;   let l8 = m51 ^ m59;
+    let l9 = m52 ^ m53;  let l10 = m53 ^ l4;  let l11 = m60 ^ l2;
+    let l12 = m48 ^ m51; let l13 = m50 ^ l0;  let l14 = m52 ^ m61;
+    let l15 = m55 ^ l1;  let l16 = m56 ^ l0;  let l17 = m57 ^ l1;
+    let l18 = m58 ^ l8;  let l19 = m63 ^ l4;  let l20 = l0 ^ l1;
+    let l21 = l1 ^ l7;   let l22 = l3 ^ l12;  let l23 = l18 ^ l2;
+    let l24 = l15 ^ l9;  let l25 = l6 ^ l10;  let l26 = l7 ^ l9;
+    let l27 = l8 ^ l10;  let l28 = l11 ^ l14; let l29 = l11 ^ l17;
+    let s0 = l6 ^ l24;     let s1 = !(l16 ^ l26); let s2 = !(l19 ^ l28);
+    let s3 = l6 ^ l21;     let s4 = l20 ^ l22;    let s5 = l25 ^ l29;
+    let s6 = !(l13 ^ l27); let s7 = !(l6 ^ l23);
+
+    [s0, s1, s2, s3, s4, s5, s6, s7]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sub_bytes, xtime};
+
+    // Reference S-box: multiplicative inverse followed by the affine map
+    fn sbox(x: u8) -> u8 {
+        let mut inv = 0;
+        for y in 1..256 {
+            let y = y as u8;
+            let mut p = 0;
+            let (mut a, mut b) = (x, y);
+            for _ in 0..8 {
+                if b & 1 == 1 { p ^= a; }
+                a = xtime(a);
+                b >>= 1;
+            }
+            if p == 1 { inv = y; }
+        }
+        let mut s = inv;
+        for i in 1..5 {
+            s ^= inv.rotate_left(i);
+        }
+        s ^ 0x63
+    }
+
+    #[test]
+    fn test_sbox() {
+        let mut bytes = [0u8; 64];
+        for chunk in 0..4 {
+            for (i, b) in bytes.iter_mut().enumerate() {
+                *b = (chunk * 64 + i) as u8;
+            }
+            sub_bytes(&mut bytes);
+            for (i, &b) in bytes.iter().enumerate() {
+                assert_eq!(b, sbox((chunk * 64 + i) as u8));
+            }
+        }
+        // Example from FIPS 197 section 5.1.1
+        assert_eq!(sbox(0x53), 0xed);
+    }
+}
@@ -4,4 +4,6 @@ fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     let ac = autocfg::new();
     ac.emit_rustc_version(1, 26);
+    ac.emit_rustc_version(1, 27);
+    ac.emit_rustc_version(1, 59);
 }
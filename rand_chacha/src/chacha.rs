@@ -12,262 +12,428 @@
 use core::fmt;
 use rand_core::{CryptoRng, RngCore, SeedableRng, Error, le};
 use rand_core::block::{BlockRngCore, BlockRng};
+use simd;
 
 const SEED_WORDS: usize = 8; // 8 words for the 256-bit key
-const STATE_WORDS: usize = 16;
+pub(crate) const STATE_WORDS: usize = 16;
+pub(crate) const BUF_BLOCKS: usize = 8; // blocks generated at once
+pub(crate) const BUF_WORDS: usize = BUF_BLOCKS * STATE_WORDS;
 
-/// A cryptographically secure random number generator that uses the ChaCha
-/// algorithm.
-///
-/// ChaCha is a stream cipher designed by Daniel J. Bernstein[^1], that we use
-/// as an RNG. It is an improved variant of the Salsa20 cipher family, which was
-/// selected as one of the "stream ciphers suitable for widespread adoption" by
-/// eSTREAM[^2].
-///
-/// ChaCha uses add-rotate-xor (ARX) operations as its basis. These are safe
-/// against timing attacks, although that is mostly a concern for ciphers and
-/// not for RNGs. Also it is very suitable for SIMD implementation.
-/// Here we do not provide a SIMD implementation yet, except for what is
-/// provided by auto-vectorisation.
-///
-/// With the ChaCha algorithm it is possible to choose the number of rounds the
-/// core algorithm should run. The number of rounds is a tradeoff between
-/// performance and security, where 8 rounds is the minimum potentially
-/// secure configuration, and 20 rounds is widely used as a conservative choice.
-/// We use 20 rounds in this implementation, but hope to allow type-level
-/// configuration in the future.
-///
-/// We use a 64-bit counter and 64-bit stream identifier as in Bernstein's
-/// implementation[^1] except that we use a stream identifier in place of a
-/// nonce. A 64-bit counter over 64-byte (16 word) blocks allows 1 ZiB of output
-/// before cycling, and the stream identifier allows 2<sup>64</sup> unique
-/// streams of output per seed. Both counter and stream are initialized to zero
-/// but may be set via [`set_word_pos`] and [`set_stream`].
-///
-/// The word layout is:
-///
-/// ```text
-/// constant  constant  constant  constant
-/// seed      seed      seed      seed
-/// seed      seed      seed      seed
-/// counter   counter   stream_id stream_id
-/// ```
-///
-/// This implementation uses an output buffer of sixteen `u32` words, and uses
-/// [`BlockRng`] to implement the [`RngCore`] methods.
-///
-/// [^1]: D. J. Bernstein, [*ChaCha, a variant of Salsa20*](
-///       https://cr.yp.to/chacha.html)
-///
-/// [^2]: [eSTREAM: the ECRYPT Stream Cipher Project](
-///       http://www.ecrypt.eu.org/stream/)
-///
-/// [`set_word_pos`]: ChaChaRng::set_word_pos
-/// [`set_stream`]: ChaChaRng::set_stream
-#[derive(Clone, Debug)]
-pub struct ChaChaRng(BlockRng<ChaChaCore>);
-
-impl RngCore for ChaChaRng {
-    #[inline]
-    fn next_u32(&mut self) -> u32 {
-        self.0.next_u32()
+macro_rules! quarter_round{
+    ($a: expr, $b: expr, $c: expr, $d: expr) => {{
+        $a = $a.wrapping_add($b); $d ^= $a; $d = $d.rotate_left(16);
+        $c = $c.wrapping_add($d); $b ^= $c; $b = $b.rotate_left(12);
+        $a = $a.wrapping_add($b); $d ^= $a; $d = $d.rotate_left( 8);
+        $c = $c.wrapping_add($d); $b ^= $c; $b = $b.rotate_left( 7);
+    }}
+}
+
+macro_rules! double_round{
+    ($x: expr) => {{
+        // Column round
+        quarter_round!($x[ 0], $x[ 4], $x[ 8], $x[12]);
+        quarter_round!($x[ 1], $x[ 5], $x[ 9], $x[13]);
+        quarter_round!($x[ 2], $x[ 6], $x[10], $x[14]);
+        quarter_round!($x[ 3], $x[ 7], $x[11], $x[15]);
+        // Diagonal round
+        quarter_round!($x[ 0], $x[ 5], $x[10], $x[15]);
+        quarter_round!($x[ 1], $x[ 6], $x[11], $x[12]);
+        quarter_round!($x[ 2], $x[ 7], $x[ 8], $x[13]);
+        quarter_round!($x[ 3], $x[ 4], $x[ 9], $x[14]);
+    }}
+}
+
+// For some reason extracting this part into a separate function improves
+// performance by 50%.
+fn chacha_block(results: &mut [u32], state: &[u32; STATE_WORDS], rounds: usize) {
+    let mut tmp = *state;
+    for _ in 0..rounds / 2 {
+        double_round!(tmp);
+    }
+    for i in 0..STATE_WORDS {
+        results[i] = tmp[i].wrapping_add(state[i]);
     }
+}
 
-    #[inline]
-    fn next_u64(&mut self) -> u64 {
-        self.0.next_u64()
+/// Compute the `BUF_BLOCKS` consecutive blocks starting with the counter in
+/// `state`, one at a time.
+pub(crate) fn chacha_blocks_scalar(results: &mut [u32; BUF_WORDS],
+                                   state: &[u32; STATE_WORDS], rounds: usize)
+{
+    let mut state = *state;
+    for block in results.chunks_mut(STATE_WORDS) {
+        chacha_block(block, &state, rounds);
+        state[12] = state[12].wrapping_add(1);
+        if state[12] == 0 {
+            state[13] = state[13].wrapping_add(1);
+        }
     }
+}
 
-    #[inline]
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        self.0.fill_bytes(dest)
+/// Compute the `BUF_BLOCKS` consecutive blocks starting with the counter in
+/// `state`, with the fastest implementation supported by the CPU.
+fn chacha_blocks(results: &mut [u32; BUF_WORDS], state: &[u32; STATE_WORDS], rounds: usize) {
+    if !simd::chacha_blocks(results, state, rounds) {
+        chacha_blocks_scalar(results, state, rounds);
     }
+}
+
+/// The output buffer of the ChaCha cores, holding several consecutive
+/// 16-word blocks.
+#[derive(Clone)]
+pub struct ChaChaResults([u32; BUF_WORDS]);
 
-    #[inline]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.0.try_fill_bytes(dest)
+impl Default for ChaChaResults {
+    fn default() -> ChaChaResults {
+        ChaChaResults([0; BUF_WORDS])
     }
 }
 
-impl SeedableRng for ChaChaRng {
-    type Seed = <ChaChaCore as SeedableRng>::Seed;
+impl AsRef<[u32]> for ChaChaResults {
+    fn as_ref(&self) -> &[u32] {
+        &self.0
+    }
+}
 
-    fn from_seed(seed: Self::Seed) -> Self {
-        ChaChaRng(BlockRng::<ChaChaCore>::from_seed(seed))
+impl AsMut<[u32]> for ChaChaResults {
+    fn as_mut(&mut self) -> &mut [u32] {
+        &mut self.0
     }
+}
 
-    fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
-        BlockRng::<ChaChaCore>::from_rng(rng).map(ChaChaRng)
+impl fmt::Debug for ChaChaResults {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ChaChaResults {{}}")
     }
 }
 
-impl CryptoRng for ChaChaRng {}
-
-impl ChaChaRng {
-    /// Get the offset from the start of the stream, in 32-bit words.
-    ///
-    /// Since the generated blocks are 16 words (2<sup>4</sup>) long and the
-    /// counter is 64-bits, the offset is a 68-bit number. Sub-word offsets are
-    /// not supported, hence the result can simply be multiplied by 4 to get a
-    /// byte-offset.
-    ///
-    /// Note: this function is currently only available with Rust 1.26 or later.
-    #[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
-    pub fn get_word_pos(&self) -> u128 {
-        let mut c = (self.0.core.state[13] as u64) << 32
-                  | (self.0.core.state[12] as u64);
-        let mut index = self.0.index();
-        // c is the end of the last block generated, unless index is at end
-        if index >= STATE_WORDS {
-            index = 0;
-        } else {
-            c = c.wrapping_sub(1);
+fn initial_state(seed: [u8; SEED_WORDS*4]) -> [u32; STATE_WORDS] {
+    let mut seed_le = [0u32; SEED_WORDS];
+    le::read_u32_into(&seed, &mut seed_le);
+    [0x61707865, 0x3320646E, 0x79622D32, 0x6B206574, // constants
+     seed_le[0], seed_le[1], seed_le[2], seed_le[3], // seed
+     seed_le[4], seed_le[5], seed_le[6], seed_le[7], // seed
+     0, 0, 0, 0] // counter
+}
+
+macro_rules! chacha_impl {
+    ($ChaChaXCore:ident, $ChaChaXRng:ident, $rounds:expr, $doc:expr, $core_doc:expr) => {
+        #[doc=$doc]
+        ///
+        /// ChaCha is a stream cipher designed by Daniel J. Bernstein[^1], that we
+        /// use as an RNG. It is an improved variant of the Salsa20 cipher family,
+        /// which was selected as one of the "stream ciphers suitable for
+        /// widespread adoption" by eSTREAM[^2].
+        ///
+        /// ChaCha uses add-rotate-xor (ARX) operations as its basis. These are
+        /// safe against timing attacks, although that is mostly a concern for
+        /// ciphers and not for RNGs. Also it is very suitable for SIMD
+        /// implementation: we compute several consecutive blocks at once, using
+        /// SSE2 or AVX2 on x86 and NEON on AArch64 where available, with the
+        /// same output as the portable implementation. With the `std` feature,
+        /// AVX2 support is detected at runtime; otherwise it is only used when
+        /// enabled at compile time.
+        ///
+        /// With the ChaCha algorithm it is possible to choose the number of
+        /// rounds the core algorithm should run. The number of rounds is a
+        /// tradeoff between performance and security, where 8 rounds is the
+        /// minimum potentially secure configuration, and 20 rounds is widely
+        /// used as a conservative choice. We provide [`ChaCha8Rng`],
+        /// [`ChaCha12Rng`] and [`ChaCha20Rng`], which differ only in their number
+        /// of rounds; [`ChaChaRng`] is an alias of `ChaCha20Rng`.
+        ///
+        /// We use a 64-bit counter and 64-bit stream identifier as in Bernstein's
+        /// implementation[^1] except that we use a stream identifier in place of
+        /// a nonce. A 64-bit counter over 64-byte (16 word) blocks allows 1 ZiB of
+        /// output before cycling, and the stream identifier allows
+        /// 2<sup>64</sup> unique streams of output per seed. Both counter and
+        /// stream are initialized to zero but may be set via [`set_word_pos`] and
+        /// [`set_stream`].
+        ///
+        /// The word layout is:
+        ///
+        /// ```text
+        /// constant  constant  constant  constant
+        /// seed      seed      seed      seed
+        /// seed      seed      seed      seed
+        /// counter   counter   stream_id stream_id
+        /// ```
+        ///
+        /// This implementation uses an output buffer of eight blocks (128 `u32`
+        /// words), and uses [`BlockRng`] to implement the [`RngCore`] methods.
+        ///
+        /// [^1]: D. J. Bernstein, [*ChaCha, a variant of Salsa20*](
+        ///       https://cr.yp.to/chacha.html)
+        ///
+        /// [^2]: [eSTREAM: the ECRYPT Stream Cipher Project](
+        ///       http://www.ecrypt.eu.org/stream/)
+        ///
+        /// [`ChaCha8Rng`]: crate::ChaCha8Rng
+        /// [`ChaCha12Rng`]: crate::ChaCha12Rng
+        /// [`ChaCha20Rng`]: crate::ChaCha20Rng
+        /// [`ChaChaRng`]: crate::ChaChaRng
+        /// [`set_word_pos`]: #method.set_word_pos
+        /// [`set_stream`]: #method.set_stream
+        /// [`BlockRng`]: rand_core::block::BlockRng
+        /// [`RngCore`]: rand_core::RngCore
+        #[derive(Clone, Debug)]
+        pub struct $ChaChaXRng(BlockRng<$ChaChaXCore>);
+
+        impl RngCore for $ChaChaXRng {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                self.0.next_u32()
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                self.0.next_u64()
+            }
+
+            #[inline]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.0.fill_bytes(dest)
+            }
+
+            #[inline]
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.0.try_fill_bytes(dest)
+            }
         }
-        ((c as u128) << 4) | (index as u128)
-    }
 
-    /// Set the offset from the start of the stream, in 32-bit words.
-    ///
-    /// As with `get_word_pos`, we use a 68-bit number. Since the generator
-    /// simply cycles at the end of its period (1 ZiB), we ignore the upper
-    /// 60 bits.
-    ///
-    /// Note: this function is currently only available with Rust 1.26 or later.
-    #[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
-    pub fn set_word_pos(&mut self, word_offset: u128) {
-        let index = (word_offset as usize) & 0xF;
-        let counter = (word_offset >> 4) as u64;
-        self.0.core.state[12] = counter as u32;
-        self.0.core.state[13] = (counter >> 32) as u32;
-        if index != 0 {
-            self.0.generate_and_set(index); // also increments counter
-        } else {
-            self.0.reset();
+        impl SeedableRng for $ChaChaXRng {
+            type Seed = <$ChaChaXCore as SeedableRng>::Seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                $ChaChaXRng(BlockRng::<$ChaChaXCore>::from_seed(seed))
+            }
+
+            fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
+                BlockRng::<$ChaChaXCore>::from_rng(rng).map($ChaChaXRng)
+            }
         }
-    }
 
-    /// Set the stream number.
-    ///
-    /// This is initialized to zero; 2<sup>64</sup> unique streams of output
-    /// are available per seed/key.
-    ///
-    /// Note that in order to reproduce ChaCha output with a specific 64-bit
-    /// nonce, one can convert that nonce to a `u64` in little-endian fashion
-    /// and pass to this function. In theory a 96-bit nonce can be used by
-    /// passing the last 64-bits to this function and using the first 32-bits as
-    /// the most significant half of the 64-bit counter (which may be set
-    /// indirectly via `set_word_pos`), but this is not directly supported.
-    pub fn set_stream(&mut self, stream: u64) {
-        let index = self.0.index();
-        self.0.core.state[14] = stream as u32;
-        self.0.core.state[15] = (stream >> 32) as u32;
-        if index < STATE_WORDS {
-            // we need to regenerate a partial result buffer
+        impl CryptoRng for $ChaChaXRng {}
+
+        impl $ChaChaXRng {
+            /// Get the offset from the start of the stream, in 32-bit words.
+            ///
+            /// Since the generated blocks are 16 words (2<sup>4</sup>) long and
+            /// the counter is 64-bits, the offset is a 68-bit number. Sub-word
+            /// offsets are not supported, hence the result can simply be
+            /// multiplied by 4 to get a byte-offset.
+            ///
+            /// Note: this function is currently only available with Rust 1.26 or
+            /// later.
+            #[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+            pub fn get_word_pos(&self) -> u128 {
+                let mut c = (self.0.core.state[13] as u64) << 32
+                          | (self.0.core.state[12] as u64);
+                let mut index = self.0.index();
+                // c is the end of the last blocks generated, unless index is at end
+                if index >= BUF_WORDS {
+                    index = 0;
+                } else {
+                    c = c.wrapping_sub(BUF_BLOCKS as u64);
+                }
+                (((c as u128) << 4) + (index as u128)) & ((1 << 68) - 1)
+            }
+
+            /// Set the offset from the start of the stream, in 32-bit words.
+            ///
+            /// As with `get_word_pos`, we use a 68-bit number. Since the
+            /// generator simply cycles at the end of its period (1 ZiB), we
+            /// ignore the upper 60 bits.
+            ///
+            /// Note: this function is currently only available with Rust 1.26 or
+            /// later.
+            #[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+            pub fn set_word_pos(&mut self, word_offset: u128) {
+                let counter = (word_offset >> 4) as u64;
+                // Generate blocks in batches aligned to `BUF_BLOCKS`, so that
+                // the last batch of an RFC 8439 keystream ends at its end.
+                let offset = counter & (BUF_BLOCKS as u64 - 1);
+                let counter = counter - offset;
+                let index = offset as usize * STATE_WORDS + (word_offset as usize & 0xF);
+                self.0.core.state[12] = counter as u32;
+                self.0.core.state[13] = (counter >> 32) as u32;
+                if index != 0 {
+                    // generate the blocks from `counter`; also increments counter
+                    self.0.generate_and_set(index);
+                } else {
+                    self.0.reset();
+                }
+            }
+
+            /// Set the stream number.
+            ///
+            /// This is initialized to zero; 2<sup>64</sup> unique streams of
+            /// output are available per seed/key.
+            ///
+            /// Note that in order to reproduce ChaCha output with a specific
+            /// 64-bit nonce, one can convert that nonce to a `u64` in
+            /// little-endian fashion and pass to this function. For a 96-bit
+            /// nonce as in RFC 8439, use [`from_key_and_nonce`] instead.
+            ///
+            /// [`from_key_and_nonce`]: #method.from_key_and_nonce
+            pub fn set_stream(&mut self, stream: u64) {
+                let index = self.0.index();
+                self.0.core.state[14] = stream as u32;
+                self.0.core.state[15] = (stream >> 32) as u32;
+                if index < BUF_WORDS {
+                    // we need to regenerate a partial result buffer
+                    {
+                        // reverse of counter adjustment in generate()
+                        let counter = ((self.0.core.state[13] as u64) << 32
+                                       | (self.0.core.state[12] as u64))
+                                      .wrapping_sub(BUF_BLOCKS as u64);
+                        self.0.core.state[12] = counter as u32;
+                        self.0.core.state[13] = (counter >> 32) as u32;
+                    }
+                    self.0.generate_and_set(index);
+                }
+            }
+
+            /// Construct a generator producing the keystream of the IETF
+            /// variant of ChaCha from [RFC 8439], given a 256-bit `key`, a
+            /// 96-bit `nonce` and the 32-bit block `counter` to start from.
+            ///
+            /// The IETF variant uses a 32-bit block counter, and the nonce in
+            /// place of the upper half of our 64-bit counter and the stream
+            /// identifier. The word layout is:
+            ///
+            /// ```text
+            /// constant  constant  constant  constant
+            /// key       key       key       key
+            /// key       key       key       key
+            /// counter   nonce     nonce     nonce
+            /// ```
+            ///
+            /// The RFC specifies 20 rounds, i.e. [`ChaCha20Rng`], and limits
+            /// a keystream to 2<sup>32</sup> blocks (256 GiB): the counter
+            /// must not carry into the nonce. The generator therefore panics
+            /// when output beyond the block with counter 2<sup>32</sup> - 1 is
+            /// requested.
+            ///
+            /// [`set_word_pos`] and [`get_word_pos`] take the first word of
+            /// the nonce as the upper half of the counter; seeking outside of
+            /// the keystream makes the generator panic likewise.
+            /// [`set_stream`] replaces the last 64 bits of the nonce, and so
+            /// switches to the keystream of another nonce.
+            ///
+            /// [RFC 8439]: https://tools.ietf.org/html/rfc8439
+            /// [`ChaCha20Rng`]: crate::ChaCha20Rng
+            /// [`set_word_pos`]: #method.set_word_pos
+            /// [`get_word_pos`]: #method.get_word_pos
+            /// [`set_stream`]: #method.set_stream
+            pub fn from_key_and_nonce(key: [u8; SEED_WORDS*4], nonce: [u8; 12],
+                                      counter: u32) -> Self
             {
-                // reverse of counter adjustment in generate()
-                if self.0.core.state[12] == 0 {
-                    self.0.core.state[13] = self.0.core.state[13].wrapping_sub(1);
+                let mut state = initial_state(key);
+                // Generate blocks in batches aligned to `BUF_BLOCKS`, so that
+                // the last batch ends at the end of the keystream, and skip
+                // the blocks before `counter`.
+                let offset = counter as usize & (BUF_BLOCKS - 1);
+                state[12] = counter - offset as u32;
+                le::read_u32_into(&nonce, &mut state[13..16]);
+                // The position at which the 32-bit counter wraps
+                let keystream_end = ((state[13] as u64) << 32).wrapping_add(1 << 32);
+                let mut rng = $ChaChaXRng(BlockRng::new($ChaChaXCore {
+                    state,
+                    keystream_end: Some(keystream_end),
+                }));
+                if offset != 0 {
+                    rng.0.generate_and_set(offset * STATE_WORDS);
                 }
-                self.0.core.state[12] = self.0.core.state[12].wrapping_sub(1);
+                rng
             }
-            self.0.generate_and_set(index);
         }
-    }
-}
-
-/// The core of `ChaChaRng`, used with `BlockRng`.
-#[derive(Clone)]
-pub struct ChaChaCore {
-    state: [u32; STATE_WORDS],
-}
 
-// Custom Debug implementation that does not expose the internal state
-impl fmt::Debug for ChaChaCore {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ChaChaCore {{}}")
-    }
-}
+        #[doc=$core_doc]
+        #[derive(Clone)]
+        pub struct $ChaChaXCore {
+            state: [u32; STATE_WORDS],
+            // The 64-bit block position at which the keystream of
+            // `from_key_and_nonce` ends, or `None` if the counter may wrap.
+            keystream_end: Option<u64>,
+        }
 
-macro_rules! quarter_round{
-    ($a: expr, $b: expr, $c: expr, $d: expr) => {{
-        $a = $a.wrapping_add($b); $d ^= $a; $d = $d.rotate_left(16);
-        $c = $c.wrapping_add($d); $b ^= $c; $b = $b.rotate_left(12);
-        $a = $a.wrapping_add($b); $d ^= $a; $d = $d.rotate_left( 8);
-        $c = $c.wrapping_add($d); $b ^= $c; $b = $b.rotate_left( 7);
-    }}
-}
+        // Custom Debug implementation that does not expose the internal state
+        impl fmt::Debug for $ChaChaXCore {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, concat!(stringify!($ChaChaXCore), " {{}}"))
+            }
+        }
 
-macro_rules! double_round{
-    ($x: expr) => {{
-        // Column round
-        quarter_round!($x[ 0], $x[ 4], $x[ 8], $x[12]);
-        quarter_round!($x[ 1], $x[ 5], $x[ 9], $x[13]);
-        quarter_round!($x[ 2], $x[ 6], $x[10], $x[14]);
-        quarter_round!($x[ 3], $x[ 7], $x[11], $x[15]);
-        // Diagonal round
-        quarter_round!($x[ 0], $x[ 5], $x[10], $x[15]);
-        quarter_round!($x[ 1], $x[ 6], $x[11], $x[12]);
-        quarter_round!($x[ 2], $x[ 7], $x[ 8], $x[13]);
-        quarter_round!($x[ 3], $x[ 4], $x[ 9], $x[14]);
-    }}
-}
+        impl BlockRngCore for $ChaChaXCore {
+            type Item = u32;
+            type Results = ChaChaResults;
+
+            fn generate(&mut self, results: &mut Self::Results) {
+                let counter = (self.state[13] as u64) << 32 | (self.state[12] as u64);
+                if let Some(end) = self.keystream_end {
+                    // Batches are aligned, so the last one ends at `end`.
+                    let left = end.wrapping_sub(counter);
+                    if left < BUF_BLOCKS as u64 || left > 1 << 32 {
+                        panic!("ChaCha: end of the RFC 8439 keystream reached");
+                    }
+                }
+                chacha_blocks(&mut results.0, &self.state, $rounds);
 
-impl BlockRngCore for ChaChaCore {
-    type Item = u32;
-    type Results = [u32; STATE_WORDS];
-
-    fn generate(&mut self, results: &mut Self::Results) {
-        // For some reason extracting this part into a separate function
-        // improves performance by 50%.
-        fn core(results: &mut [u32; STATE_WORDS],
-                state: &[u32; STATE_WORDS])
-        {
-            let mut tmp = *state;
-            let rounds = 20;
-            for _ in 0..rounds / 2 {
-                double_round!(tmp);
+                // update 64-bit counter
+                let counter = counter.wrapping_add(BUF_BLOCKS as u64);
+                self.state[12] = counter as u32;
+                self.state[13] = (counter >> 32) as u32;
             }
-            for i in 0..STATE_WORDS {
-                results[i] = tmp[i].wrapping_add(state[i]);
+        }
+
+        impl SeedableRng for $ChaChaXCore {
+            type Seed = [u8; SEED_WORDS*4];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                $ChaChaXCore { state: initial_state(seed), keystream_end: None }
             }
         }
 
-        core(results, &self.state);
+        impl CryptoRng for $ChaChaXCore {}
 
-        // update 64-bit counter
-        self.state[12] = self.state[12].wrapping_add(1);
-        if self.state[12] != 0 { return; };
-        self.state[13] = self.state[13].wrapping_add(1);
+        impl From<$ChaChaXCore> for $ChaChaXRng {
+            fn from(core: $ChaChaXCore) -> Self {
+                $ChaChaXRng(BlockRng::new(core))
+            }
+        }
     }
 }
 
-impl SeedableRng for ChaChaCore {
-    type Seed = [u8; SEED_WORDS*4];
-
-    fn from_seed(seed: Self::Seed) -> Self {
-        let mut seed_le = [0u32; SEED_WORDS];
-        le::read_u32_into(&seed, &mut seed_le);
-        Self {
-            state: [0x61707865, 0x3320646E, 0x79622D32, 0x6B206574, // constants
-                    seed_le[0], seed_le[1], seed_le[2], seed_le[3], // seed
-                    seed_le[4], seed_le[5], seed_le[6], seed_le[7], // seed
-                    0, 0, 0, 0], // counter
-         }
-    }
-}
+chacha_impl!(ChaCha20Core, ChaCha20Rng, 20,
+             "A cryptographically secure random number generator that uses the \
+              ChaCha algorithm with 20 rounds.",
+             "The core of `ChaCha20Rng`, used with `BlockRng`.");
+chacha_impl!(ChaCha12Core, ChaCha12Rng, 12,
+             "A cryptographically secure random number generator that uses the \
+              ChaCha algorithm with 12 rounds.",
+             "The core of `ChaCha12Rng`, used with `BlockRng`.");
+chacha_impl!(ChaCha8Core, ChaCha8Rng, 8,
+             "A cryptographically secure random number generator that uses the \
+              ChaCha algorithm with 8 rounds.",
+             "The core of `ChaCha8Rng`, used with `BlockRng`.");
 
-impl CryptoRng for ChaChaCore {}
+/// A cryptographically secure random number generator that uses the ChaCha
+/// algorithm with 20 rounds; an alias of [`ChaCha20Rng`].
+///
+/// [`ChaCha20Rng`]: crate::ChaCha20Rng
+pub type ChaChaRng = ChaCha20Rng;
 
-impl From<ChaChaCore> for ChaChaRng {
-    fn from(core: ChaChaCore) -> Self {
-        ChaChaRng(BlockRng::new(core))
-    }
-}
+/// The core of `ChaChaRng`, used with `BlockRng`; an alias of
+/// [`ChaCha20Core`].
+///
+/// [`ChaCha20Core`]: crate::ChaCha20Core
+pub type ChaChaCore = ChaCha20Core;
 
 #[cfg(test)]
 mod test {
     use ::rand_core::{RngCore, SeedableRng};
-    use super::ChaChaRng;
+    use super::{ChaChaRng, ChaCha8Rng, ChaCha12Rng};
 
     #[test]
     fn test_chacha_construction() {
@@ -426,6 +592,116 @@ mod test {
         assert_eq!(results, expected);
     }
 
+    #[test]
+    fn test_chacha_ietf_block() {
+        // Test vector for the ChaCha20 block function from
+        // https://tools.ietf.org/html/rfc8439#section-2.3.2
+        let key = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                   0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+                   0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+                   0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f];
+        let nonce = [0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a,
+                     0x00, 0x00, 0x00, 0x00];
+        let mut rng = ChaChaRng::from_key_and_nonce(key, nonce, 1);
+
+        let mut results = [0u32; 16];
+        for i in results.iter_mut() { *i = rng.next_u32(); }
+        let expected = [0xe4e7f110, 0x15593bd1, 0x1fdd0f50, 0xc47120a3,
+                        0xc7f4d1c7, 0x0368c033, 0x9aaa2204, 0x4e6cd4c3,
+                        0x466482d2, 0x09aa9f07, 0x05d7c214, 0xa2028bd9,
+                        0xd19c12b5, 0xb94e16de, 0xe883d0cb, 0x4e3c50a2];
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_chacha_ietf_encryption() {
+        // Test vector for ChaCha20 encryption from
+        // https://tools.ietf.org/html/rfc8439#section-2.4.2
+        let key = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                   0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+                   0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+                   0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f];
+        let nonce = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4a,
+                     0x00, 0x00, 0x00, 0x00];
+        let mut rng = ChaChaRng::from_key_and_nonce(key, nonce, 1);
+
+        let plaintext = b"Ladies and Gentlemen of the class of '99: \
+                          If I could offer you only one tip for the future, \
+                          sunscreen would be it.";
+        let expected = [
+            0x6e, 0x2e, 0x35, 0x9a, 0x25, 0x68, 0xf9, 0x80, 0x41, 0xba, 0x07, 0x28,
+            0xdd, 0x0d, 0x69, 0x81, 0xe9, 0x7e, 0x7a, 0xec, 0x1d, 0x43, 0x60, 0xc2,
+            0x0a, 0x27, 0xaf, 0xcc, 0xfd, 0x9f, 0xae, 0x0b, 0xf9, 0x1b, 0x65, 0xc5,
+            0x52, 0x47, 0x33, 0xab, 0x8f, 0x59, 0x3d, 0xab, 0xcd, 0x62, 0xb3, 0x57,
+            0x16, 0x39, 0xd6, 0x24, 0xe6, 0x51, 0x52, 0xab, 0x8f, 0x53, 0x0c, 0x35,
+            0x9f, 0x08, 0x61, 0xd8, 0x07, 0xca, 0x0d, 0xbf, 0x50, 0x0d, 0x6a, 0x61,
+            0x56, 0xa3, 0x8e, 0x08, 0x8a, 0x22, 0xb6, 0x5e, 0x52, 0xbc, 0x51, 0x4d,
+            0x16, 0xcc, 0xf8, 0x06, 0x81, 0x8c, 0xe9, 0x1a, 0xb7, 0x79, 0x37, 0x36,
+            0x5a, 0xf9, 0x0b, 0xbf, 0x74, 0xa3, 0x5b, 0xe6, 0xb4, 0x0b, 0x8e, 0xed,
+            0xf2, 0x78, 0x5e, 0x42, 0x87, 0x4d];
+        assert_eq!(plaintext.len(), expected.len());
+
+        let mut keystream = [0u8; 114];
+        rng.fill_bytes(&mut keystream[..]);
+        for ((p, k), c) in plaintext.iter().zip(keystream.iter()).zip(expected.iter()) {
+            assert_eq!(p ^ k, *c);
+        }
+    }
+
+    #[test]
+    fn test_chacha_ietf_counter_max() {
+        let key = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                   0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+                   0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+                   0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f];
+        let nonce = [0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a,
+                     0x00, 0x00, 0x00, 0x00];
+        // The block with counter `u32::MAX`
+        let expected = [0xb84129ff, 0xcbf640d7, 0xbf3609b5, 0x52bd7e99,
+                        0x8d10cb18, 0xc6413fc5, 0x21d04148, 0x0c436781,
+                        0x0c773ba0, 0x64cb4ca7, 0x4d19282a, 0xedd2ed1d,
+                        0x251e1513, 0xae7f5dec, 0xbf60d0b6, 0x46b1e6b7];
+        for &counter in [0xffff_fff8, 0xffff_fffa, 0xffff_ffff].iter() {
+            let mut rng = ChaChaRng::from_key_and_nonce(key, nonce, counter);
+            let mut results = [0u32; 16];
+            for _ in counter..0xffff_ffff {
+                for i in results.iter_mut() { *i = rng.next_u32(); }
+            }
+            for i in results.iter_mut() { *i = rng.next_u32(); }
+            assert_eq!(results, expected);
+        }
+    }
+
+    #[test]
+    #[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+    fn test_chacha_ietf_word_pos() {
+        let nonce = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        // The keystream ends at block 2^33, as the nonce continues the counter
+        let end = 1u128 << (33 + 4);
+        let mut rng1 = ChaChaRng::from_key_and_nonce([0; 32], nonce, 0xffff_fffd);
+        assert_eq!(rng1.get_word_pos(), end - 3 * 16);
+        for _ in 0..37 { rng1.next_u32(); }
+        assert_eq!(rng1.get_word_pos(), end - 11);
+
+        let mut rng2 = ChaChaRng::from_key_and_nonce([0; 32], nonce, 0);
+        rng2.set_word_pos(end - 11);
+        assert_eq!(rng2.get_word_pos(), end - 11);
+        for _ in 0..11 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+        assert_eq!(rng2.get_word_pos(), end);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chacha_ietf_keystream_end_panics() {
+        let mut rng = ChaChaRng::from_key_and_nonce([0; 32], [0; 12], 0xffff_ffff);
+        let mut buf = [0u8; 64];
+        rng.fill_bytes(&mut buf);
+        // The counter must not carry into the nonce
+        rng.next_u32();
+    }
+
     #[test]
     fn test_chacha_clone_streams() {
         let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
@@ -444,4 +720,85 @@ mod test {
             assert_eq!(rng.next_u32(), clone.next_u32());
         }
     }
+
+    #[test]
+    fn test_chacha8_true_values() {
+        // Test vector TC1 (8 rounds, 256-bit key) from
+        // https://tools.ietf.org/html/draft-strombergson-chacha-test-vectors-00
+        let seed = [0u8; 32];
+        let mut rng = ChaCha8Rng::from_seed(seed);
+
+        let mut results = [0u32; 16];
+        for i in results.iter_mut() { *i = rng.next_u32(); }
+        let expected = [0x2fef003e, 0xd6405f89, 0xe8b85b7f, 0xa1a5091f,
+                        0xc30e842c, 0x3b7f9ace, 0x88e11b18, 0x1e1a71ef,
+                        0x72e14c98, 0x416f21b9, 0x6753449f, 0x19566d45,
+                        0xa3424a31, 0x01b086da, 0xb8fd7b38, 0x42fe0c0e];
+        assert_eq!(results, expected);
+
+        for i in results.iter_mut() { *i = rng.next_u32(); }
+        let expected = [0x0dfaaed2, 0x51c1a5ea, 0x6cdb0abf, 0xada5f201,
+                        0x1258fdc0, 0xaaa2f959, 0x8f0ff2dc, 0x6ba266d5,
+                        0x38ec3250, 0x98dac5bb, 0x566f0cee, 0x652a878b,
+                        0x25bf8aa0, 0xbb21eb1d, 0xd8e5564b, 0xaa681e82];
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_chacha12_true_values() {
+        // Test vector TC1 (12 rounds, 256-bit key) from
+        // https://tools.ietf.org/html/draft-strombergson-chacha-test-vectors-00
+        let seed = [0u8; 32];
+        let mut rng = ChaCha12Rng::from_seed(seed);
+
+        let mut results = [0u32; 16];
+        for i in results.iter_mut() { *i = rng.next_u32(); }
+        let expected = [0x6a9af49b, 0x53f95507, 0x12ce1f81, 0xd583265f,
+                        0xbbc32904, 0x1474e049, 0xa589007e, 0x5f15ae2e,
+                        0x79f86405, 0xc0e37ad2, 0x3428e82c, 0x798cfaac,
+                        0x2c9f623a, 0x1969dea0, 0x2fe80b61, 0xbe261341];
+        assert_eq!(results, expected);
+
+        for i in results.iter_mut() { *i = rng.next_u32(); }
+        let expected = [0x4188d50b, 0xfe743e20, 0x3371fc86, 0x3d17e08c,
+                        0xb7eb28c6, 0xcccbbd19, 0x21851515, 0xb489c04c,
+                        0xcd8d2542, 0x11f14ca1, 0x97b802c6, 0x43c88c1b,
+                        0xca461ee9, 0xc0515190, 0xb0a64427, 0x1693e617];
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    #[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+    fn test_chacha8_word_pos() {
+        let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
+        let mut rng1 = ChaCha8Rng::from_seed(seed);
+        for _ in 0..37 { rng1.next_u32(); }
+        assert_eq!(rng1.get_word_pos(), 37);
+
+        let mut rng2 = ChaCha8Rng::from_seed(seed);
+        rng2.set_word_pos(37);
+        assert_eq!(rng2.get_word_pos(), 37);
+        for _ in 0..20 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_chacha12_clone_streams() {
+        let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
+        let mut rng = ChaCha12Rng::from_seed(seed);
+        let mut clone = rng.clone();
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), clone.next_u64());
+        }
+
+        rng.set_stream(51);
+        for _ in 0..7 {
+            assert!(rng.next_u32() != clone.next_u32());
+        }
+        clone.set_stream(51);   // switch part way through block
+        for _ in 7..16 {
+            assert_eq!(rng.next_u32(), clone.next_u32());
+        }
+    }
 }
@@ -18,8 +18,12 @@
 
 #![no_std]
 
+#[cfg(feature="std")] #[macro_use] extern crate std;
+
 pub extern crate rand_core;
 
 mod chacha;
+mod simd;
 
-pub use chacha::{ChaChaRng, ChaChaCore};
+pub use chacha::{ChaChaRng, ChaChaCore, ChaChaResults};
+pub use chacha::{ChaCha8Rng, ChaCha8Core, ChaCha12Rng, ChaCha12Core, ChaCha20Rng, ChaCha20Core};
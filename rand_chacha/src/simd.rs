@@ -0,0 +1,367 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! SIMD implementations of the ChaCha block function.
+//!
+//! These compute several consecutive blocks at once: each vector holds the
+//! same state word of consecutive blocks, one block per lane, so the rounds
+//! are exactly the scalar rounds applied lane-wise and the output is the same
+//! as that of the scalar implementation.
+
+use chacha::{STATE_WORDS, BUF_WORDS};
+
+/// Compute the `BUF_BLOCKS` consecutive blocks starting with the counter in
+/// `state` with SIMD instructions, if supported by the CPU.
+///
+/// Returns `false`, leaving `results` untouched, if no SIMD implementation
+/// is available.
+#[allow(unused_variables, unreachable_code)]
+pub(crate) fn chacha_blocks(results: &mut [u32; BUF_WORDS], state: &[u32; STATE_WORDS],
+                            rounds: usize) -> bool
+{
+    #[cfg(all(rustc_1_27, any(target_arch = "x86", target_arch = "x86_64")))]
+    {
+        if x86::has_avx2() {
+            unsafe { avx2::chacha_blocks(results, state, rounds) };
+            return true;
+        }
+        if x86::has_sse2() {
+            unsafe { sse2::chacha_blocks(results, state, rounds) };
+            return true;
+        }
+    }
+    #[cfg(all(rustc_1_59, target_arch = "aarch64", target_feature = "neon"))]
+    {
+        unsafe { neon::chacha_blocks(results, state, rounds) };
+        return true;
+    }
+    false
+}
+
+macro_rules! simd_quarter_round {
+    ($x: expr, $a: expr, $b: expr, $c: expr, $d: expr) => {{
+        $x[$a] = add($x[$a], $x[$b]); $x[$d] = rotl16(xor($x[$d], $x[$a]));
+        $x[$c] = add($x[$c], $x[$d]); $x[$b] = rotl12(xor($x[$b], $x[$c]));
+        $x[$a] = add($x[$a], $x[$b]); $x[$d] = rotl8(xor($x[$d], $x[$a]));
+        $x[$c] = add($x[$c], $x[$d]); $x[$b] = rotl7(xor($x[$b], $x[$c]));
+    }}
+}
+
+macro_rules! simd_double_round {
+    ($x: expr) => {{
+        // Column round
+        simd_quarter_round!($x,  0,  4,  8, 12);
+        simd_quarter_round!($x,  1,  5,  9, 13);
+        simd_quarter_round!($x,  2,  6, 10, 14);
+        simd_quarter_round!($x,  3,  7, 11, 15);
+        // Diagonal round
+        simd_quarter_round!($x,  0,  5, 10, 15);
+        simd_quarter_round!($x,  1,  6, 11, 12);
+        simd_quarter_round!($x,  2,  7,  8, 13);
+        simd_quarter_round!($x,  3,  4,  9, 14);
+    }}
+}
+
+// Defines `chacha_blocks` from the vector type `Vector` with `LANES` lanes
+// of `u32`, and the functions `splat`, `load`, `store`, `add`, `xor` and
+// `rotl16`, `rotl12`, `rotl8`, `rotl7` of the calling module.
+macro_rules! simd_chacha_blocks {
+    ($(#[$attr: meta])*) => {
+        use chacha::{STATE_WORDS, BUF_BLOCKS, BUF_WORDS};
+
+        $(#[$attr])*
+        pub(crate) unsafe fn chacha_blocks(results: &mut [u32; BUF_WORDS],
+                                           state: &[u32; STATE_WORDS],
+                                           rounds: usize)
+        {
+            let counter = (state[13] as u64) << 32 | (state[12] as u64);
+            let mut init = [splat(0); STATE_WORDS];
+            for i in 0..STATE_WORDS {
+                init[i] = splat(state[i]);
+            }
+            let mut first = 0;
+            while first < BUF_BLOCKS {
+                // The 64-bit block counters of the lanes
+                let mut low = [0u32; LANES];
+                let mut high = [0u32; LANES];
+                for lane in 0..LANES {
+                    let c = counter.wrapping_add((first + lane) as u64);
+                    low[lane] = c as u32;
+                    high[lane] = (c >> 32) as u32;
+                }
+                init[12] = load(&low);
+                init[13] = load(&high);
+
+                let mut x = init;
+                for _ in 0..rounds / 2 {
+                    simd_double_round!(x);
+                }
+
+                let mut words = [0u32; LANES];
+                for i in 0..STATE_WORDS {
+                    store(&mut words, add(x[i], init[i]));
+                    for lane in 0..LANES {
+                        results[(first + lane) * STATE_WORDS + i] = words[lane];
+                    }
+                }
+                first += LANES;
+            }
+        }
+    }
+}
+
+#[cfg(all(rustc_1_27, any(target_arch = "x86", target_arch = "x86_64")))]
+mod x86 {
+    /// Whether SSE2 is available.
+    #[inline]
+    pub fn has_sse2() -> bool {
+        #[cfg(feature="std")] {
+            is_x86_feature_detected!("sse2")
+        }
+        #[cfg(not(feature="std"))] {
+            cfg!(target_feature = "sse2")
+        }
+    }
+
+    /// Whether AVX2 is available.
+    #[inline]
+    pub fn has_avx2() -> bool {
+        #[cfg(feature="std")] {
+            is_x86_feature_detected!("avx2")
+        }
+        #[cfg(not(feature="std"))] {
+            cfg!(target_feature = "avx2")
+        }
+    }
+}
+
+#[cfg(all(rustc_1_27, any(target_arch = "x86", target_arch = "x86_64")))]
+mod sse2 {
+    #[cfg(target_arch = "x86")] use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")] use core::arch::x86_64::*;
+
+    const LANES: usize = 4;
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn splat(x: u32) -> __m128i {
+        _mm_set1_epi32(x as i32)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn load(x: &[u32; LANES]) -> __m128i {
+        _mm_loadu_si128(x.as_ptr() as *const __m128i)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn store(x: &mut [u32; LANES], v: __m128i) {
+        _mm_storeu_si128(x.as_mut_ptr() as *mut __m128i, v)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn add(a: __m128i, b: __m128i) -> __m128i {
+        _mm_add_epi32(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn xor(a: __m128i, b: __m128i) -> __m128i {
+        _mm_xor_si128(a, b)
+    }
+
+    macro_rules! rotl {
+        ($name: ident, $n: expr) => {
+            #[inline]
+            #[target_feature(enable = "sse2")]
+            unsafe fn $name(a: __m128i) -> __m128i {
+                _mm_or_si128(_mm_slli_epi32(a, $n), _mm_srli_epi32(a, 32 - $n))
+            }
+        }
+    }
+
+    rotl!(rotl16, 16);
+    rotl!(rotl12, 12);
+    rotl!(rotl8, 8);
+    rotl!(rotl7, 7);
+
+    simd_chacha_blocks!(#[target_feature(enable = "sse2")]);
+}
+
+#[cfg(all(rustc_1_27, any(target_arch = "x86", target_arch = "x86_64")))]
+mod avx2 {
+    #[cfg(target_arch = "x86")] use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")] use core::arch::x86_64::*;
+
+    const LANES: usize = 8;
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn splat(x: u32) -> __m256i {
+        _mm256_set1_epi32(x as i32)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn load(x: &[u32; LANES]) -> __m256i {
+        _mm256_loadu_si256(x.as_ptr() as *const __m256i)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn store(x: &mut [u32; LANES], v: __m256i) {
+        _mm256_storeu_si256(x.as_mut_ptr() as *mut __m256i, v)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn add(a: __m256i, b: __m256i) -> __m256i {
+        _mm256_add_epi32(a, b)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn xor(a: __m256i, b: __m256i) -> __m256i {
+        _mm256_xor_si256(a, b)
+    }
+
+    // Rotations by whole bytes are byte shuffles.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn rotl16(a: __m256i) -> __m256i {
+        _mm256_shuffle_epi8(a, _mm256_set_epi8(13, 12, 15, 14, 9, 8, 11, 10,
+                                               5, 4, 7, 6, 1, 0, 3, 2,
+                                               13, 12, 15, 14, 9, 8, 11, 10,
+                                               5, 4, 7, 6, 1, 0, 3, 2))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn rotl8(a: __m256i) -> __m256i {
+        _mm256_shuffle_epi8(a, _mm256_set_epi8(14, 13, 12, 15, 10, 9, 8, 11,
+                                               6, 5, 4, 7, 2, 1, 0, 3,
+                                               14, 13, 12, 15, 10, 9, 8, 11,
+                                               6, 5, 4, 7, 2, 1, 0, 3))
+    }
+
+    macro_rules! rotl {
+        ($name: ident, $n: expr) => {
+            #[inline]
+            #[target_feature(enable = "avx2")]
+            unsafe fn $name(a: __m256i) -> __m256i {
+                _mm256_or_si256(_mm256_slli_epi32(a, $n), _mm256_srli_epi32(a, 32 - $n))
+            }
+        }
+    }
+
+    rotl!(rotl12, 12);
+    rotl!(rotl7, 7);
+
+    simd_chacha_blocks!(#[target_feature(enable = "avx2")]);
+}
+
+#[cfg(all(rustc_1_59, target_arch = "aarch64", target_feature = "neon"))]
+mod neon {
+    use core::arch::aarch64::*;
+
+    const LANES: usize = 4;
+
+    #[inline(always)]
+    unsafe fn splat(x: u32) -> uint32x4_t {
+        vdupq_n_u32(x)
+    }
+
+    #[inline(always)]
+    unsafe fn load(x: &[u32; LANES]) -> uint32x4_t {
+        vld1q_u32(x.as_ptr())
+    }
+
+    #[inline(always)]
+    unsafe fn store(x: &mut [u32; LANES], v: uint32x4_t) {
+        vst1q_u32(x.as_mut_ptr(), v)
+    }
+
+    #[inline(always)]
+    unsafe fn add(a: uint32x4_t, b: uint32x4_t) -> uint32x4_t {
+        vaddq_u32(a, b)
+    }
+
+    #[inline(always)]
+    unsafe fn xor(a: uint32x4_t, b: uint32x4_t) -> uint32x4_t {
+        veorq_u32(a, b)
+    }
+
+    // Rotation by 16 bits swaps the 16-bit halves of each lane.
+    #[inline(always)]
+    unsafe fn rotl16(a: uint32x4_t) -> uint32x4_t {
+        vreinterpretq_u32_u16(vrev32q_u16(vreinterpretq_u16_u32(a)))
+    }
+
+    macro_rules! rotl {
+        ($name: ident, $n: expr) => {
+            #[inline(always)]
+            unsafe fn $name(a: uint32x4_t) -> uint32x4_t {
+                vsriq_n_u32(vshlq_n_u32(a, $n), a, 32 - $n)
+            }
+        }
+    }
+
+    rotl!(rotl12, 12);
+    rotl!(rotl8, 8);
+    rotl!(rotl7, 7);
+
+    simd_chacha_blocks!();
+}
+
+#[cfg(test)]
+mod test {
+    use chacha::{STATE_WORDS, BUF_WORDS, chacha_blocks_scalar};
+
+    fn test_state(counter: u64) -> [u32; STATE_WORDS] {
+        let mut state = [0u32; STATE_WORDS];
+        for (i, x) in state.iter_mut().enumerate() {
+            *x = (i as u32).wrapping_mul(0x9E3779B9) ^ 0x5bd1e995;
+        }
+        state[12] = counter as u32;
+        state[13] = (counter >> 32) as u32;
+        state
+    }
+
+    #[test]
+    fn test_simd_matches_scalar() {
+        // Includes counters overflowing the low word, and the whole counter,
+        // within the generated blocks.
+        let counters = [0, 1, 0xffff_fffc, 0x1_2345_6789, 0xffff_ffff_ffff_fffd];
+        for &rounds in [8, 12, 20].iter() {
+            for &counter in counters.iter() {
+                let state = test_state(counter);
+                let mut expected = [0u32; BUF_WORDS];
+                chacha_blocks_scalar(&mut expected, &state, rounds);
+                let mut results = [0u32; BUF_WORDS];
+                if super::chacha_blocks(&mut results, &state, rounds) {
+                    assert_eq!(&results[..], &expected[..]);
+                }
+                #[cfg(all(rustc_1_27, any(target_arch = "x86", target_arch = "x86_64")))]
+                {
+                    if super::x86::has_sse2() {
+                        let mut results = [0u32; BUF_WORDS];
+                        unsafe { super::sse2::chacha_blocks(&mut results, &state, rounds) };
+                        assert_eq!(&results[..], &expected[..]);
+                    }
+                    if super::x86::has_avx2() {
+                        let mut results = [0u32; BUF_WORDS];
+                        unsafe { super::avx2::chacha_blocks(&mut results, &state, rounds) };
+                        assert_eq!(&results[..], &expected[..]);
+                    }
+                }
+            }
+        }
+    }
+}
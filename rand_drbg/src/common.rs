@@ -0,0 +1,345 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Parameters and functionality shared by the DRBGs.
+
+use core::fmt;
+use error::DrbgError;
+
+/// The security strength of both DRBGs, in bytes.
+pub(crate) const SECURITY_STRENGTH: usize = 32;
+/// The maximum length of the entropy input, nonce, personalization string
+/// and additional input, in bytes (2<sup>35</sup> bits).
+const MAX_LENGTH: u64 = 1 << 32;
+/// The maximum number of bytes per request (2<sup>19</sup> bits).
+pub(crate) const MAX_REQUEST: usize = 1 << 16;
+/// The maximum number of requests between reseeds.
+pub(crate) const MAX_RESEED_INTERVAL: u64 = 1 << 48;
+
+/// The number of words of output generated per request by the cores.
+pub(crate) const RESULTS_WORDS: usize = 32;
+
+/// The seed of the DRBGs with `SeedableRng`: a 32-byte entropy input
+/// followed by a 16-byte nonce.
+#[derive(Clone)]
+pub struct DrbgSeed(pub [u8; 48]);
+
+// Custom Debug implementation that does not expose the seed
+impl fmt::Debug for DrbgSeed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DrbgSeed {{}}")
+    }
+}
+
+impl Default for DrbgSeed {
+    fn default() -> DrbgSeed {
+        DrbgSeed([0; 48])
+    }
+}
+
+impl AsMut<[u8]> for DrbgSeed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl DrbgSeed {
+    pub(crate) fn entropy_input(&self) -> &[u8] {
+        &self.0[..SECURITY_STRENGTH]
+    }
+
+    pub(crate) fn nonce(&self) -> &[u8] {
+        &self.0[SECURITY_STRENGTH..]
+    }
+}
+
+fn check_length(input: &[u8]) -> Result<(), DrbgError> {
+    if input.len() as u64 > MAX_LENGTH {
+        return Err(DrbgError::InputTooLong);
+    }
+    Ok(())
+}
+
+/// Check the inputs of the instantiate function.
+pub(crate) fn check_instantiate(entropy_input: &[u8], nonce: &[u8],
+                                personalization_string: &[u8])
+    -> Result<(), DrbgError>
+{
+    check_reseed(entropy_input, personalization_string)?;
+    if nonce.len() < SECURITY_STRENGTH / 2 {
+        return Err(DrbgError::NonceTooShort);
+    }
+    check_length(nonce)
+}
+
+/// Check the inputs of the reseed function.
+pub(crate) fn check_reseed(entropy_input: &[u8], additional_input: &[u8])
+    -> Result<(), DrbgError>
+{
+    if entropy_input.len() < SECURITY_STRENGTH {
+        return Err(DrbgError::EntropyInputTooShort);
+    }
+    check_length(entropy_input)?;
+    check_length(additional_input)
+}
+
+/// Check the inputs of the generate function.
+pub(crate) fn check_generate(output: &[u8], additional_input: &[u8])
+    -> Result<(), DrbgError>
+{
+    if output.len() > MAX_REQUEST {
+        return Err(DrbgError::RequestTooLarge);
+    }
+    check_length(additional_input)
+}
+
+/// The big-endian encoding of `x`.
+pub(crate) fn be_bytes(x: u32) -> [u8; 4] {
+    [(x >> 24) as u8, (x >> 16) as u8, (x >> 8) as u8, x as u8]
+}
+
+/// The reseed counter: the number of requests since the DRBG was
+/// instantiated or reseeded, plus one, checked against the reseed interval.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ReseedCounter {
+    counter: u64,
+    interval: u64,
+}
+
+impl ReseedCounter {
+    pub(crate) fn new() -> ReseedCounter {
+        ReseedCounter { counter: 1, interval: MAX_RESEED_INTERVAL }
+    }
+
+    pub(crate) fn set_interval(&mut self, interval: u64) {
+        assert!(interval > 0 && interval <= MAX_RESEED_INTERVAL,
+                "reseed interval must be between 1 and 2^48");
+        self.interval = interval;
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.counter = 1;
+    }
+
+    pub(crate) fn check(&self) -> Result<(), DrbgError> {
+        if self.counter > self.interval {
+            return Err(DrbgError::ReseedRequired);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn value(&self) -> u64 {
+        self.counter
+    }
+
+    pub(crate) fn increment(&mut self) {
+        self.counter += 1;
+    }
+
+    /// The number of requests allowed before a reseed is required.
+    pub(crate) fn remaining(&self) -> u64 {
+        (self.interval + 1).saturating_sub(self.counter)
+    }
+}
+
+// Defines the methods common to the DRBG cores, which have a `reseed` field
+// holding their `ReseedCounter` and the inherent methods `generate` and
+// `reseed`.
+macro_rules! drbg_core_impl {
+    ($DrbgCore:ident) => {
+        impl $DrbgCore {
+            /// Set the reseed interval: the maximum number of requests to
+            /// [`generate`] between reseeds, 2<sup>48</sup> by default.
+            ///
+            /// Once the interval is reached, `generate` returns
+            /// [`DrbgError::ReseedRequired`] until the DRBG is reseeded.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `interval` is zero or greater than 2<sup>48</sup>.
+            ///
+            /// [`generate`]: #method.generate
+            /// [`DrbgError::ReseedRequired`]: crate::DrbgError::ReseedRequired
+            pub fn with_reseed_interval(mut self, interval: u64) -> Self {
+                self.reseed.set_interval(interval);
+                self
+            }
+
+            /// Whether the reseed interval is reached.
+            pub fn reseed_required(&self) -> bool {
+                self.reseed.check().is_err()
+            }
+        }
+
+        impl BlockRngCore for $DrbgCore {
+            type Item = u32;
+            type Results = [u32; RESULTS_WORDS];
+
+            /// Generate the next request of 128 bytes.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the reseed interval is reached.
+            fn generate(&mut self, results: &mut Self::Results) {
+                let mut bytes = [0u8; RESULTS_WORDS * 4];
+                if let Err(e) = $DrbgCore::generate(self, &mut bytes, &[]) {
+                    panic!(concat!(stringify!($DrbgCore), ": {}"), e);
+                }
+                le::read_u32_into(&bytes, results);
+            }
+        }
+
+        impl SeedableRng for $DrbgCore {
+            type Seed = DrbgSeed;
+
+            /// Instantiate the DRBG with the entropy input and nonce of the
+            /// seed, and no personalization string.
+            fn from_seed(seed: Self::Seed) -> Self {
+                $DrbgCore::new(seed.entropy_input(), seed.nonce(), &[])
+                    .expect("seed has valid lengths")
+            }
+        }
+
+        impl CryptoRng for $DrbgCore {}
+    }
+}
+
+// Defines the generator `$DrbgRng`, wrapping `$DrbgCore` in a `BlockRng`.
+macro_rules! drbg_rng_impl {
+    ($DrbgRng:ident, $DrbgCore:ident, $doc:expr) => {
+        #[doc=$doc]
+        ///
+        /// This wraps the core in a [`BlockRng`] which buffers the output of
+        /// requests of 128 bytes each, so the reseed interval counts these
+        /// requests. Once it is reached, [`try_fill_bytes`] returns an error,
+        /// and the other [`RngCore`] methods panic, until the DRBG is
+        /// [reseeded].
+        ///
+        /// [`BlockRng`]: rand_core::block::BlockRng
+        /// [`try_fill_bytes`]: rand_core::RngCore::try_fill_bytes
+        /// [`RngCore`]: rand_core::RngCore
+        /// [reseeded]: #method.reseed
+        #[derive(Clone, Debug)]
+        pub struct $DrbgRng(BlockRng<$DrbgCore>);
+
+        impl RngCore for $DrbgRng {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                self.0.next_u32()
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                self.0.next_u64()
+            }
+
+            #[inline]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.0.fill_bytes(dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                // Bytes left in the buffer, and from the requests allowed
+                // before reseeding
+                let buffered = (RESULTS_WORDS - self.0.index().min(RESULTS_WORDS)) * 4;
+                let available = self.0.core.reseed.remaining()
+                    .saturating_mul(RESULTS_WORDS as u64 * 4)
+                    .saturating_add(buffered as u64);
+                if dest.len() as u64 > available {
+                    return Err(DrbgError::ReseedRequired.into());
+                }
+                self.0.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        impl SeedableRng for $DrbgRng {
+            type Seed = <$DrbgCore as SeedableRng>::Seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                $DrbgRng(BlockRng::<$DrbgCore>::from_seed(seed))
+            }
+
+            fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
+                BlockRng::<$DrbgCore>::from_rng(rng).map($DrbgRng)
+            }
+        }
+
+        impl CryptoRng for $DrbgRng {}
+
+        impl $DrbgRng {
+            /// Reseed the DRBG with fresh `entropy_input` and optional
+            /// `additional_input`, discarding buffered output.
+            pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8])
+                -> Result<(), DrbgError>
+            {
+                self.0.core.reseed(entropy_input, additional_input)?;
+                self.0.reset();
+                Ok(())
+            }
+
+            /// Fill `output` with the next request, with optional
+            /// `additional_input`, discarding buffered output.
+            ///
+            /// Unlike the [`RngCore`] methods, this makes a single request of
+            /// at most 2<sup>16</sup> bytes, and returns an error if the
+            /// reseed interval is reached.
+            ///
+            /// [`RngCore`]: rand_core::RngCore
+            pub fn generate(&mut self, output: &mut [u8], additional_input: &[u8])
+                -> Result<(), DrbgError>
+            {
+                self.0.reset();
+                self.0.core.generate(output, additional_input)
+            }
+        }
+
+        impl From<$DrbgCore> for $DrbgRng {
+            fn from(core: $DrbgCore) -> Self {
+                $DrbgRng(BlockRng::new(core))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use core::ops::Deref;
+
+    /// Bytes decoded from hexadecimal, up to 128.
+    pub(crate) struct Hex {
+        bytes: [u8; 128],
+        len: usize,
+    }
+
+    impl Deref for Hex {
+        type Target = [u8];
+
+        fn deref(&self) -> &[u8] {
+            &self.bytes[..self.len]
+        }
+    }
+
+    pub(crate) fn hex(s: &str) -> Hex {
+        let mut bytes = [0u8; 128];
+        let len = s.len() / 2;
+        for (i, b) in bytes[..len].iter_mut().enumerate() {
+            *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        Hex { bytes, len }
+    }
+
+    /// The bytes `start, start + 1, ..., start + 31`.
+    pub(crate) fn range(start: u8) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = start + i as u8;
+        }
+        bytes
+    }
+}
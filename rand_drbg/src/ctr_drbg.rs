@@ -0,0 +1,342 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The CTR_DRBG with AES-256 and a derivation function.
+
+use core::fmt;
+use rand_core::{CryptoRng, RngCore, SeedableRng, Error, le};
+use rand_core::block::{BlockRng, BlockRngCore};
+use rand_aes::Aes256;
+use common::{DrbgSeed, ReseedCounter, RESULTS_WORDS};
+use common::{check_instantiate, check_reseed, check_generate, be_bytes};
+use error::DrbgError;
+
+const BLOCK_BYTES: usize = 16;
+const KEY_BYTES: usize = 32;
+/// The length of the seed material of the update function.
+const SEED_BYTES: usize = KEY_BYTES + BLOCK_BYTES;
+/// The number of output blocks encrypted at once by `generate`.
+const BUF_BLOCKS: usize = 8;
+
+/// The CTR_DRBG core, without buffering.
+///
+/// The functions of NIST SP 800-90A Rev. 1, section 10.2.1, with AES-256, the
+/// block cipher derivation function and a security strength of 256 bits:
+/// [`new`] instantiates the DRBG, [`reseed`] reseeds it and [`generate`]
+/// produces output, each optionally taking a personalization string or
+/// additional input. The counter is the whole 128-bit block.
+///
+/// As a [`BlockRngCore`], each block is a request of 128 bytes without
+/// additional input. This panics when the reseed interval is reached, so the
+/// core is typically wrapped in a [`ReseedingRng`] with a threshold below
+/// 128 times the reseed interval, or used through [`CtrDrbgRng`].
+///
+/// [`new`]: CtrDrbgCore::new
+/// [`reseed`]: CtrDrbgCore::reseed
+/// [`generate`]: CtrDrbgCore::generate
+/// [`BlockRngCore`]: rand_core::block::BlockRngCore
+/// [`ReseedingRng`]: ../rand/rngs/adapter/struct.ReseedingRng.html
+/// [`CtrDrbgRng`]: CtrDrbgRng
+#[derive(Clone)]
+pub struct CtrDrbgCore {
+    cipher: Aes256,
+    v: [u8; BLOCK_BYTES],
+    reseed: ReseedCounter,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for CtrDrbgCore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CtrDrbgCore {{}}")
+    }
+}
+
+impl CtrDrbgCore {
+    /// Instantiate the DRBG from `entropy_input`, a `nonce` and an optional
+    /// `personalization_string`, which may be empty.
+    ///
+    /// Returns an error if the entropy input is shorter than 32 bytes, the
+    /// nonce shorter than 16 bytes, or any input longer than 2<sup>32</sup>
+    /// bytes.
+    pub fn new(entropy_input: &[u8], nonce: &[u8], personalization_string: &[u8])
+        -> Result<Self, DrbgError>
+    {
+        check_instantiate(entropy_input, nonce, personalization_string)?;
+        let mut drbg = CtrDrbgCore {
+            cipher: Aes256::new([0; KEY_BYTES]),
+            v: [0; BLOCK_BYTES],
+            reseed: ReseedCounter::new(),
+        };
+        drbg.update(&derive(&[entropy_input, nonce, personalization_string]));
+        Ok(drbg)
+    }
+
+    /// Reseed the DRBG with fresh `entropy_input` and optional
+    /// `additional_input`, which may be empty.
+    ///
+    /// Returns an error if the entropy input is shorter than 32 bytes, or any
+    /// input longer than 2<sup>32</sup> bytes.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8])
+        -> Result<(), DrbgError>
+    {
+        check_reseed(entropy_input, additional_input)?;
+        self.update(&derive(&[entropy_input, additional_input]));
+        self.reseed.reset();
+        Ok(())
+    }
+
+    /// Fill `output` with the next request, with optional
+    /// `additional_input`, which may be empty.
+    ///
+    /// Returns an error if the reseed interval is reached, more than
+    /// 2<sup>16</sup> bytes are requested, or the additional input is longer
+    /// than 2<sup>32</sup> bytes.
+    pub fn generate(&mut self, output: &mut [u8], additional_input: &[u8])
+        -> Result<(), DrbgError>
+    {
+        check_generate(output, additional_input)?;
+        self.reseed.check()?;
+        let additional = if additional_input.is_empty() {
+            [0; SEED_BYTES]
+        } else {
+            let additional = derive(&[additional_input]);
+            self.update(&additional);
+            additional
+        };
+        let mut buf = [0u8; BUF_BLOCKS * BLOCK_BYTES];
+        for chunk in output.chunks_mut(buf.len()) {
+            // Round up to whole blocks
+            let n = (chunk.len() + BLOCK_BYTES - 1) & !(BLOCK_BYTES - 1);
+            self.encrypt_counter(&mut buf[..n]);
+            chunk.copy_from_slice(&buf[..chunk.len()]);
+        }
+        self.update(&additional);
+        self.reseed.increment();
+        Ok(())
+    }
+
+    /// Fill `blocks` with the encryption of the next values of the counter.
+    fn encrypt_counter(&mut self, blocks: &mut [u8]) {
+        for block in blocks.chunks_mut(BLOCK_BYTES) {
+            increment(&mut self.v);
+            block.copy_from_slice(&self.v);
+        }
+        self.cipher.encrypt_blocks(blocks);
+    }
+
+    /// The CTR_DRBG_Update function.
+    fn update(&mut self, provided: &[u8; SEED_BYTES]) {
+        let mut temp = [0u8; SEED_BYTES];
+        self.encrypt_counter(&mut temp);
+        for (t, p) in temp.iter_mut().zip(provided.iter()) {
+            *t ^= *p;
+        }
+        let mut key = [0u8; KEY_BYTES];
+        key.copy_from_slice(&temp[..KEY_BYTES]);
+        self.cipher = Aes256::new(key);
+        self.v.copy_from_slice(&temp[KEY_BYTES..]);
+    }
+}
+
+/// Increment a big-endian counter block.
+fn increment(v: &mut [u8; BLOCK_BYTES]) {
+    for byte in v.iter_mut().rev() {
+        *byte = byte.wrapping_add(1);
+        if *byte != 0 {
+            break;
+        }
+    }
+}
+
+/// The BCC function, absorbing its input in pieces.
+struct Bcc<'a> {
+    cipher: &'a Aes256,
+    chaining_value: [u8; BLOCK_BYTES],
+    pos: usize,
+}
+
+impl<'a> Bcc<'a> {
+    fn new(cipher: &'a Aes256) -> Bcc<'a> {
+        Bcc { cipher, chaining_value: [0; BLOCK_BYTES], pos: 0 }
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.chaining_value[self.pos] ^= byte;
+            self.pos += 1;
+            if self.pos == BLOCK_BYTES {
+                self.cipher.encrypt_block(&mut self.chaining_value);
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Pad the input with zeros to a whole block and return the output.
+    fn finish(mut self) -> [u8; BLOCK_BYTES] {
+        if self.pos != 0 {
+            self.cipher.encrypt_block(&mut self.chaining_value);
+        }
+        self.chaining_value
+    }
+}
+
+/// The Block_Cipher_df function, deriving seed material from the
+/// concatenation of `inputs`.
+fn derive(inputs: &[&[u8]]) -> [u8; SEED_BYTES] {
+    let mut key = [0u8; KEY_BYTES];
+    for (i, k) in key.iter_mut().enumerate() {
+        *k = i as u8;
+    }
+    let cipher = Aes256::new(key);
+    let len = inputs.iter().map(|d| d.len()).sum::<usize>() as u32;
+
+    let mut temp = [0u8; SEED_BYTES];
+    for (i, block) in temp.chunks_mut(BLOCK_BYTES).enumerate() {
+        let mut bcc = Bcc::new(&cipher);
+        let mut iv = [0u8; BLOCK_BYTES];
+        iv[..4].copy_from_slice(&be_bytes(i as u32));
+        bcc.absorb(&iv);
+        bcc.absorb(&be_bytes(len));
+        bcc.absorb(&be_bytes(SEED_BYTES as u32));
+        for d in inputs {
+            bcc.absorb(d);
+        }
+        bcc.absorb(&[0x80]);
+        block.copy_from_slice(&bcc.finish());
+    }
+
+    key.copy_from_slice(&temp[..KEY_BYTES]);
+    let cipher = Aes256::new(key);
+    let mut x = [0u8; BLOCK_BYTES];
+    x.copy_from_slice(&temp[KEY_BYTES..]);
+    for block in temp.chunks_mut(BLOCK_BYTES) {
+        cipher.encrypt_block(&mut x);
+        block.copy_from_slice(&x);
+    }
+    temp
+}
+
+drbg_core_impl!(CtrDrbgCore);
+
+drbg_rng_impl!(CtrDrbgRng, CtrDrbgCore,
+    "The CTR_DRBG of NIST SP 800-90A with AES-256 and a derivation function, \
+     a cryptographically secure random number generator.\n\n\
+     See [`CtrDrbgCore`](CtrDrbgCore) for the DRBG functions; `reseed` \
+     and `generate` are available here as well, to pass additional input.");
+
+#[cfg(test)]
+mod test {
+    use rand_core::{RngCore, SeedableRng};
+    use super::{CtrDrbgCore, CtrDrbgRng, increment};
+    use common::DrbgSeed;
+    use common::test::{hex, range};
+    use error::DrbgError;
+
+    fn generate(drbg: &mut CtrDrbgCore) -> [u8; 64] {
+        let mut results = [0u8; 64];
+        drbg.generate(&mut results, &[]).unwrap();
+        results
+    }
+
+    #[test]
+    fn test_ctr_drbg_cavp() {
+        // NIST CAVP CTR_DRBG.rsp, [AES-256 use df], no reseed, COUNT = 0: the
+        // second of two requests of 512 bits
+        let mut drbg = CtrDrbgCore::new(
+            &hex("36401940fa8b1fba91a1661f211d78a0b9389a74e5bccfece8d766af1a6d3b14"),
+            &hex("496f25b0f1301b4f501be30380a137eb"), &[]).unwrap();
+        generate(&mut drbg);
+        let expected = hex("5862eb38bd558dd978a696e6df164782ddd887e7e9a6c9f3f1fbafb78941b535\
+                            a64912dfd224c6dc7454e5250b3d97165e16260c2faf1cc7735cb75fb4f07e1d");
+        assert_eq!(&generate(&mut drbg)[..], &*expected);
+    }
+
+    #[test]
+    fn test_ctr_drbg_personalization_reseed() {
+        // The derivation function is applied to the concatenation of the
+        // entropy input, nonce and personalization string, and of the
+        // entropy input and additional input when reseeding.
+        let mut nonce_pers = [0u8; 48];
+        nonce_pers[..16].copy_from_slice(&range(32)[..16]);
+        nonce_pers[16..].copy_from_slice(&range(48));
+        let mut drbg = CtrDrbgCore::new(&range(0), &nonce_pers[..16], &range(48)).unwrap();
+        let mut concatenated = CtrDrbgCore::new(&range(0), &nonce_pers, &[]).unwrap();
+        let mut plain = CtrDrbgCore::new(&range(0), &nonce_pers[..16], &[]).unwrap();
+        let results = generate(&mut drbg);
+        assert_eq!(&results[..], &generate(&mut concatenated)[..]);
+        assert!(results[..] != generate(&mut plain)[..]);
+
+        let mut entropy_add = [0u8; 64];
+        entropy_add[..32].copy_from_slice(&range(80));
+        entropy_add[32..].copy_from_slice(&range(112));
+        drbg.reseed(&entropy_add[..32], &entropy_add[32..]).unwrap();
+        concatenated.reseed(&entropy_add, &[]).unwrap();
+        plain.reseed(&entropy_add[..32], &[]).unwrap();
+        let results = generate(&mut drbg);
+        assert_eq!(&results[..], &generate(&mut concatenated)[..]);
+        assert!(results[..] != generate(&mut plain)[..]);
+    }
+
+    #[test]
+    fn test_ctr_drbg_partial_block() {
+        let mut drbg = CtrDrbgCore::new(&range(0), &range(32)[..16], &[]).unwrap();
+        let full = generate(&mut drbg.clone());
+        let mut results = [0u8; 37];
+        drbg.generate(&mut results, &[]).unwrap();
+        assert_eq!(&results[..], &full[..37]);
+    }
+
+    #[test]
+    fn test_ctr_drbg_increment() {
+        let mut v = [0xff; 16];
+        v[0] = 0x12;
+        increment(&mut v);
+        let mut expected = [0; 16];
+        expected[0] = 0x13;
+        assert_eq!(v, expected);
+    }
+
+    #[test]
+    fn test_ctr_drbg_reseed_interval() {
+        let mut drbg = CtrDrbgCore::new(&range(0), &range(32)[..16], &[]).unwrap()
+            .with_reseed_interval(1);
+        let mut results = [0u8; 16];
+        drbg.generate(&mut results, &[]).unwrap();
+        assert_eq!(drbg.generate(&mut results, &[]), Err(DrbgError::ReseedRequired));
+        assert_eq!(drbg.reseed(&range(0)[..16], &[]), Err(DrbgError::EntropyInputTooShort));
+        drbg.reseed(&range(80), &[]).unwrap();
+        drbg.generate(&mut results, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_ctr_drbg_rng() {
+        let mut seed = DrbgSeed::default();
+        seed.0[..32].copy_from_slice(&range(0));
+        seed.0[32..].copy_from_slice(&range(32)[..16]);
+        let mut rng = CtrDrbgRng::from_seed(seed.clone());
+        let mut core = CtrDrbgCore::from_seed(seed);
+        let mut expected = [0u8; 256];
+        core.generate(&mut expected[..128], &[]).unwrap();
+        core.generate(&mut expected[128..], &[]).unwrap();
+        let mut results = [0u8; 256];
+        for chunk in results.chunks_mut(24) {
+            rng.fill_bytes(chunk);
+        }
+        assert_eq!(&results[..], &expected[..]);
+
+        // With additional input, buffered output is discarded
+        rng.next_u32();
+        core.generate(&mut expected[..128], &[]).unwrap();
+        core.generate(&mut expected[..37], &range(64)).unwrap();
+        rng.generate(&mut results[..37], &range(64)).unwrap();
+        assert_eq!(&results[..37], &expected[..37]);
+        core.generate(&mut expected[..128], &[]).unwrap();
+        rng.fill_bytes(&mut results[..128]);
+        assert_eq!(&results[..128], &expected[..128]);
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The error type of the DRBGs.
+
+use core::fmt;
+#[cfg(feature="std")] use std::error;
+use rand_core::{Error, ErrorKind};
+
+/// Error type returned from the functions of the DRBGs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrbgError {
+    /// The entropy input is shorter than the security strength, 32 bytes.
+    EntropyInputTooShort,
+    /// The nonce is shorter than half the security strength, 16 bytes.
+    NonceTooShort,
+    /// An input is longer than 2<sup>32</sup> bytes.
+    InputTooLong,
+    /// More than 2<sup>16</sup> bytes were requested at once.
+    RequestTooLarge,
+    /// The reseed interval is reached: the DRBG must be reseeded before it
+    /// generates more output.
+    ReseedRequired,
+}
+
+impl DrbgError {
+    fn msg(&self) -> &'static str {
+        match *self {
+            DrbgError::EntropyInputTooShort => "entropy input shorter than security strength",
+            DrbgError::NonceTooShort => "nonce shorter than half the security strength",
+            DrbgError::InputTooLong => "input longer than maximum length",
+            DrbgError::RequestTooLarge => "more bytes requested than allowed per request",
+            DrbgError::ReseedRequired => "reseed interval reached, reseed required",
+        }
+    }
+}
+
+impl fmt::Display for DrbgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+#[cfg(feature="std")]
+impl error::Error for DrbgError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl From<DrbgError> for Error {
+    fn from(err: DrbgError) -> Error {
+        Error::new(ErrorKind::Unavailable, err.msg())
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Hash_DRBG with SHA-256.
+
+use core::fmt;
+use rand_core::{CryptoRng, RngCore, SeedableRng, Error, le};
+use rand_core::block::{BlockRng, BlockRngCore};
+use common::{DrbgSeed, ReseedCounter, RESULTS_WORDS};
+use common::{check_instantiate, check_reseed, check_generate, be_bytes};
+use error::DrbgError;
+use sha256::{Sha256, DIGEST_BYTES};
+
+/// The length of `V` and `C`, 440 bits for SHA-256.
+const SEED_BYTES: usize = 55;
+
+/// The Hash_DRBG core, without buffering.
+///
+/// The functions of NIST SP 800-90A Rev. 1, section 10.1.1, with SHA-256 and
+/// a security strength of 256 bits: [`new`] instantiates the DRBG,
+/// [`reseed`] reseeds it and [`generate`] produces output, each optionally
+/// taking a personalization string or additional input.
+///
+/// As a [`BlockRngCore`], each block is a request of 128 bytes without
+/// additional input. This panics when the reseed interval is reached, so the
+/// core is typically wrapped in a [`ReseedingRng`] with a threshold below
+/// 128 times the reseed interval, or used through [`HashDrbgRng`].
+///
+/// [`new`]: HashDrbgCore::new
+/// [`reseed`]: HashDrbgCore::reseed
+/// [`generate`]: HashDrbgCore::generate
+/// [`BlockRngCore`]: rand_core::block::BlockRngCore
+/// [`ReseedingRng`]: ../rand/rngs/adapter/struct.ReseedingRng.html
+/// [`HashDrbgRng`]: HashDrbgRng
+#[derive(Clone)]
+pub struct HashDrbgCore {
+    v: [u8; SEED_BYTES],
+    c: [u8; SEED_BYTES],
+    reseed: ReseedCounter,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for HashDrbgCore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HashDrbgCore {{}}")
+    }
+}
+
+impl HashDrbgCore {
+    /// Instantiate the DRBG from `entropy_input`, a `nonce` and an optional
+    /// `personalization_string`, which may be empty.
+    ///
+    /// Returns an error if the entropy input is shorter than 32 bytes, the
+    /// nonce shorter than 16 bytes, or any input longer than 2<sup>32</sup>
+    /// bytes.
+    pub fn new(entropy_input: &[u8], nonce: &[u8], personalization_string: &[u8])
+        -> Result<Self, DrbgError>
+    {
+        check_instantiate(entropy_input, nonce, personalization_string)?;
+        let v = derive(&[entropy_input, nonce, personalization_string]);
+        Ok(HashDrbgCore {
+            v,
+            c: derive(&[&[0], &v]),
+            reseed: ReseedCounter::new(),
+        })
+    }
+
+    /// Reseed the DRBG with fresh `entropy_input` and optional
+    /// `additional_input`, which may be empty.
+    ///
+    /// Returns an error if the entropy input is shorter than 32 bytes, or any
+    /// input longer than 2<sup>32</sup> bytes.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8])
+        -> Result<(), DrbgError>
+    {
+        check_reseed(entropy_input, additional_input)?;
+        self.v = derive(&[&[1], &self.v, entropy_input, additional_input]);
+        self.c = derive(&[&[0], &self.v]);
+        self.reseed.reset();
+        Ok(())
+    }
+
+    /// Fill `output` with the next request, with optional
+    /// `additional_input`, which may be empty.
+    ///
+    /// Returns an error if the reseed interval is reached, more than
+    /// 2<sup>16</sup> bytes are requested, or the additional input is longer
+    /// than 2<sup>32</sup> bytes.
+    pub fn generate(&mut self, output: &mut [u8], additional_input: &[u8])
+        -> Result<(), DrbgError>
+    {
+        check_generate(output, additional_input)?;
+        self.reseed.check()?;
+        if !additional_input.is_empty() {
+            let w = hash(&[&[2], &self.v, additional_input]);
+            add(&mut self.v, &w);
+        }
+        // The Hashgen function
+        let mut data = self.v;
+        for chunk in output.chunks_mut(DIGEST_BYTES) {
+            chunk.copy_from_slice(&hash(&[&data])[..chunk.len()]);
+            add(&mut data, &[1]);
+        }
+        let h = hash(&[&[3], &self.v]);
+        let c = self.c;
+        let mut counter = [0u8; 8];
+        counter[..4].copy_from_slice(&be_bytes((self.reseed.value() >> 32) as u32));
+        counter[4..].copy_from_slice(&be_bytes(self.reseed.value() as u32));
+        add(&mut self.v, &h);
+        add(&mut self.v, &c);
+        add(&mut self.v, &counter);
+        self.reseed.increment();
+        Ok(())
+    }
+}
+
+fn hash(inputs: &[&[u8]]) -> [u8; DIGEST_BYTES] {
+    let mut sha = Sha256::new();
+    for d in inputs {
+        sha.update(d);
+    }
+    sha.finish()
+}
+
+/// Add the big-endian number `x` to `v`, modulo 2<sup>440</sup>.
+fn add(v: &mut [u8; SEED_BYTES], x: &[u8]) {
+    let mut carry = 0u16;
+    let mut x = x.iter().rev();
+    for byte in v.iter_mut().rev() {
+        let sum = *byte as u16 + *x.next().unwrap_or(&0) as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+}
+
+/// The Hash_df function, deriving seed material from the concatenation of
+/// `inputs`.
+fn derive(inputs: &[&[u8]]) -> [u8; SEED_BYTES] {
+    let mut seed = [0u8; SEED_BYTES];
+    for (i, chunk) in seed.chunks_mut(DIGEST_BYTES).enumerate() {
+        let mut sha = Sha256::new();
+        sha.update(&[i as u8 + 1]);
+        sha.update(&be_bytes(SEED_BYTES as u32 * 8));
+        for d in inputs {
+            sha.update(d);
+        }
+        chunk.copy_from_slice(&sha.finish()[..chunk.len()]);
+    }
+    seed
+}
+
+drbg_core_impl!(HashDrbgCore);
+
+drbg_rng_impl!(HashDrbgRng, HashDrbgCore,
+    "The Hash_DRBG of NIST SP 800-90A with SHA-256, a cryptographically \
+     secure random number generator.\n\n\
+     See [`HashDrbgCore`](HashDrbgCore) for the DRBG functions; `reseed` \
+     and `generate` are available here as well, to pass additional input.");
+
+#[cfg(test)]
+mod test {
+    use rand_core::{RngCore, SeedableRng};
+    use super::{HashDrbgCore, HashDrbgRng, add};
+    use common::DrbgSeed;
+    use common::test::{hex, range};
+    use error::DrbgError;
+
+    fn generate(drbg: &mut HashDrbgCore) -> [u8; 64] {
+        let mut results = [0u8; 64];
+        drbg.generate(&mut results, &[]).unwrap();
+        results
+    }
+
+    #[test]
+    fn test_hash_drbg_cavp() {
+        // NIST CAVP Hash_DRBG.rsp, [SHA-256], no reseed, COUNT = 0: the second
+        // of two requests of 1024 bits
+        let mut drbg = HashDrbgCore::new(
+            &hex("a65ad0f345db4e0effe875c3a2e71f42c7129d620ff5c119a9ef55f05185e0fb"),
+            &hex("8581f9317517276e06e9607ddbcbcc2e"), &[]).unwrap();
+        let mut results = [0u8; 128];
+        drbg.generate(&mut results, &[]).unwrap();
+        drbg.generate(&mut results, &[]).unwrap();
+        let expected = hex("d3e160c35b99f340b2628264d1751060e0045da383ff57a57d73a673d2b8d80d\
+                            aaf6a6c35a91bb4579d73fd0c8fed111b0391306828adfed528f018121b3febd\
+                            c343e797b87dbb63db1333ded9d1ece177cfa6b71fe8ab1da46624ed6415e51c\
+                            cde2c7ca86e283990eeaeb91120415528b2295910281b02dd431f4c9f70427df");
+        assert_eq!(&results[..], &*expected);
+    }
+
+    #[test]
+    fn test_hash_drbg_personalization_reseed() {
+        // Hash_df is applied to the concatenation of the entropy input, nonce
+        // and personalization string, and of `V`, the entropy input and
+        // additional input when reseeding.
+        let mut nonce_pers = [0u8; 48];
+        nonce_pers[..16].copy_from_slice(&range(32)[..16]);
+        nonce_pers[16..].copy_from_slice(&range(48));
+        let mut drbg = HashDrbgCore::new(&range(0), &nonce_pers[..16], &range(48)).unwrap();
+        let mut concatenated = HashDrbgCore::new(&range(0), &nonce_pers, &[]).unwrap();
+        let mut plain = HashDrbgCore::new(&range(0), &nonce_pers[..16], &[]).unwrap();
+        let results = generate(&mut drbg);
+        assert_eq!(&results[..], &generate(&mut concatenated)[..]);
+        assert!(results[..] != generate(&mut plain)[..]);
+
+        let mut entropy_add = [0u8; 64];
+        entropy_add[..32].copy_from_slice(&range(80));
+        entropy_add[32..].copy_from_slice(&range(112));
+        drbg.reseed(&entropy_add[..32], &entropy_add[32..]).unwrap();
+        concatenated.reseed(&entropy_add, &[]).unwrap();
+        plain.reseed(&entropy_add[..32], &[]).unwrap();
+        let results = generate(&mut drbg);
+        assert_eq!(&results[..], &generate(&mut concatenated)[..]);
+        assert!(results[..] != generate(&mut plain)[..]);
+    }
+
+    #[test]
+    fn test_hash_drbg_add() {
+        let mut v = [0xff; 55];
+        v[0] = 0x12;
+        add(&mut v, &[0, 1]);
+        let mut expected = [0; 55];
+        expected[0] = 0x13;
+        assert_eq!(&v[..], &expected[..]);
+        // Modulo 2^440
+        let mut v = [0xff; 55];
+        add(&mut v, &[0xff, 0, 0, 0, 0, 0, 0, 0, 1]);
+        let mut expected = [0; 55];
+        expected[46] = 0xff;
+        assert_eq!(&v[..], &expected[..]);
+    }
+
+    #[test]
+    fn test_hash_drbg_reseed_interval() {
+        let mut drbg = HashDrbgCore::new(&range(0), &range(32)[..16], &[]).unwrap()
+            .with_reseed_interval(1);
+        let mut results = [0u8; 16];
+        drbg.generate(&mut results, &[]).unwrap();
+        assert_eq!(drbg.generate(&mut results, &[]), Err(DrbgError::ReseedRequired));
+        drbg.reseed(&range(80), &[]).unwrap();
+        drbg.generate(&mut results, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_hash_drbg_rng() {
+        let mut seed = DrbgSeed::default();
+        seed.0[..32].copy_from_slice(&range(0));
+        seed.0[32..].copy_from_slice(&range(32)[..16]);
+        let mut rng = HashDrbgRng::from_seed(seed.clone());
+        let mut core = HashDrbgCore::from_seed(seed);
+        let mut expected = [0u8; 256];
+        core.generate(&mut expected[..128], &[]).unwrap();
+        core.generate(&mut expected[128..], &[]).unwrap();
+        let mut results = [0u8; 256];
+        for chunk in results.chunks_mut(24) {
+            rng.fill_bytes(chunk);
+        }
+        assert_eq!(&results[..], &expected[..]);
+
+        // With additional input, buffered output is discarded
+        rng.next_u32();
+        core.generate(&mut expected[..128], &[]).unwrap();
+        core.generate(&mut expected[..37], &range(64)).unwrap();
+        rng.generate(&mut results[..37], &range(64)).unwrap();
+        assert_eq!(&results[..37], &expected[..37]);
+        core.generate(&mut expected[..128], &[]).unwrap();
+        rng.fill_bytes(&mut results[..128]);
+        assert_eq!(&results[..128], &expected[..128]);
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The HMAC_DRBG with SHA-256.
+
+use core::fmt;
+use rand_core::{CryptoRng, RngCore, SeedableRng, Error, le};
+use rand_core::block::{BlockRng, BlockRngCore};
+use common::{DrbgSeed, ReseedCounter, RESULTS_WORDS};
+use common::{check_instantiate, check_reseed, check_generate};
+use error::DrbgError;
+use sha256::{HmacSha256, DIGEST_BYTES};
+
+/// The HMAC_DRBG core, without buffering.
+///
+/// The functions of NIST SP 800-90A Rev. 1, section 10.1.2, with HMAC-SHA-256
+/// and a security strength of 256 bits: [`new`] instantiates the DRBG,
+/// [`reseed`] reseeds it and [`generate`] produces output, each optionally
+/// taking a personalization string or additional input.
+///
+/// As a [`BlockRngCore`], each block is a request of 128 bytes without
+/// additional input. This panics when the reseed interval is reached, so the
+/// core is typically wrapped in a [`ReseedingRng`] with a threshold below
+/// 128 times the reseed interval, or used through [`HmacDrbgRng`].
+///
+/// [`new`]: HmacDrbgCore::new
+/// [`reseed`]: HmacDrbgCore::reseed
+/// [`generate`]: HmacDrbgCore::generate
+/// [`BlockRngCore`]: rand_core::block::BlockRngCore
+/// [`ReseedingRng`]: ../rand/rngs/adapter/struct.ReseedingRng.html
+/// [`HmacDrbgRng`]: HmacDrbgRng
+#[derive(Clone)]
+pub struct HmacDrbgCore {
+    k: [u8; DIGEST_BYTES],
+    v: [u8; DIGEST_BYTES],
+    reseed: ReseedCounter,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for HmacDrbgCore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HmacDrbgCore {{}}")
+    }
+}
+
+impl HmacDrbgCore {
+    /// Instantiate the DRBG from `entropy_input`, a `nonce` and an optional
+    /// `personalization_string`, which may be empty.
+    ///
+    /// Returns an error if the entropy input is shorter than 32 bytes, the
+    /// nonce shorter than 16 bytes, or any input longer than 2<sup>32</sup>
+    /// bytes.
+    pub fn new(entropy_input: &[u8], nonce: &[u8], personalization_string: &[u8])
+        -> Result<Self, DrbgError>
+    {
+        check_instantiate(entropy_input, nonce, personalization_string)?;
+        let mut drbg = HmacDrbgCore {
+            k: [0; DIGEST_BYTES],
+            v: [1; DIGEST_BYTES],
+            reseed: ReseedCounter::new(),
+        };
+        drbg.update(&[entropy_input, nonce, personalization_string]);
+        Ok(drbg)
+    }
+
+    /// Reseed the DRBG with fresh `entropy_input` and optional
+    /// `additional_input`, which may be empty.
+    ///
+    /// Returns an error if the entropy input is shorter than 32 bytes, or any
+    /// input longer than 2<sup>32</sup> bytes.
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8])
+        -> Result<(), DrbgError>
+    {
+        check_reseed(entropy_input, additional_input)?;
+        self.update(&[entropy_input, additional_input]);
+        self.reseed.reset();
+        Ok(())
+    }
+
+    /// Fill `output` with the next request, with optional
+    /// `additional_input`, which may be empty.
+    ///
+    /// Returns an error if the reseed interval is reached, more than
+    /// 2<sup>16</sup> bytes are requested, or the additional input is longer
+    /// than 2<sup>32</sup> bytes.
+    pub fn generate(&mut self, output: &mut [u8], additional_input: &[u8])
+        -> Result<(), DrbgError>
+    {
+        check_generate(output, additional_input)?;
+        self.reseed.check()?;
+        if !additional_input.is_empty() {
+            self.update(&[additional_input]);
+        }
+        for chunk in output.chunks_mut(DIGEST_BYTES) {
+            self.v = self.hmac(&[&self.v]);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[additional_input]);
+        self.reseed.increment();
+        Ok(())
+    }
+
+    fn hmac(&self, data: &[&[u8]]) -> [u8; DIGEST_BYTES] {
+        let mut mac = HmacSha256::new(&self.k);
+        for d in data {
+            mac.update(d);
+        }
+        mac.finish()
+    }
+
+    /// The HMAC_DRBG_Update function, with the concatenation of `provided`
+    /// as provided data.
+    fn update(&mut self, provided: &[&[u8]]) {
+        let empty = provided.iter().all(|d| d.is_empty());
+        for &byte in &[0u8, 1] {
+            let mut mac = HmacSha256::new(&self.k);
+            mac.update(&self.v);
+            mac.update(&[byte]);
+            for d in provided {
+                mac.update(d);
+            }
+            self.k = mac.finish();
+            self.v = self.hmac(&[&self.v]);
+            if empty {
+                break;
+            }
+        }
+    }
+}
+
+drbg_core_impl!(HmacDrbgCore);
+
+drbg_rng_impl!(HmacDrbgRng, HmacDrbgCore,
+    "The HMAC_DRBG of NIST SP 800-90A with SHA-256, a cryptographically \
+     secure random number generator.\n\n\
+     See [`HmacDrbgCore`](HmacDrbgCore) for the DRBG functions; `reseed` \
+     and `generate` are available here as well, to pass additional input.");
+
+#[cfg(test)]
+mod test {
+    use rand_core::{RngCore, SeedableRng};
+    use super::{HmacDrbgCore, HmacDrbgRng};
+    use common::DrbgSeed;
+    use common::test::{hex, range};
+    use error::DrbgError;
+
+    // The test vectors are from the NIST CAVP files HMAC_DRBG.rsp, for
+    // [SHA-256] with 256-bit entropy input and 128-bit nonce, and the
+    // second of two requests of 1024 bits.
+
+    #[test]
+    fn test_hmac_drbg_cavp() {
+        // No reseed, COUNT = 0
+        let mut drbg = HmacDrbgCore::new(
+            &hex("ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488"),
+            &hex("659ba96c601dc69fc902940805ec0ca8"), &[]).unwrap();
+        let mut results = [0u8; 128];
+        drbg.generate(&mut results, &[]).unwrap();
+        drbg.generate(&mut results, &[]).unwrap();
+        let expected = hex("e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89\
+                            d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1\
+                            07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668\
+                            961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8");
+        assert_eq!(&results[..], &*expected);
+    }
+
+    #[test]
+    fn test_hmac_drbg_cavp_additional_input() {
+        // No reseed, [AdditionalInputLen = 256], COUNT = 0
+        let mut drbg = HmacDrbgCore::new(
+            &hex("d3cc4d1acf3dde0c4bd2290d262337042dc632948223d3a2eaab87da44295fbd"),
+            &hex("0109b0e729f457328aa18569a9224921"), &[]).unwrap();
+        let mut results = [0u8; 128];
+        drbg.generate(&mut results,
+            &hex("3c311848183c9a212a26f27f8c6647e40375e466a0857cc39c4e47575d53f1f6")).unwrap();
+        drbg.generate(&mut results,
+            &hex("fcb9abd19ccfbccef88c9c39bfb3dd7b1c12266c9808992e305bc3cff566e4e4")).unwrap();
+        let expected = hex("9c7b758b212cd0fcecd5daa489821712e3cdea4467b560ef5ddc24ab47749a1f\
+                            1ffdbbb118f4e62fcfca3371b8fbfc5b0646b83e06bfbbab5fac30ea09ea2bc7\
+                            6f1ea568c9be0444b2cc90517b20ca825f2d0eccd88e7175538b85d90ab39018\
+                            3ca6395535d34473af6b5a5b88f5a59ee7561573337ea819da0dcc3573a22974");
+        assert_eq!(&results[..], &*expected);
+    }
+
+    #[test]
+    fn test_hmac_drbg_cavp_personalization() {
+        // No reseed, [PersonalizationStringLen = 256], COUNT = 0
+        let mut drbg = HmacDrbgCore::new(
+            &hex("5cacc68165a2e2ee20812f35ec73a79dbf30fd475476ac0c44fc6174cdac2b55"),
+            &hex("6f885496c1e63af620becd9e71ecb824"),
+            &hex("e72dd8590d4ed5295515c35ed6199e9d211b8f069b3058caa6670b96ef1208d0")).unwrap();
+        let mut results = [0u8; 128];
+        drbg.generate(&mut results, &[]).unwrap();
+        drbg.generate(&mut results, &[]).unwrap();
+        let expected = hex("f1012cf543f94533df27fedfbf58e5b79a3dc517a9c402bdbfc9a0c0f721f9d5\
+                            3faf4aafdc4b8f7a1b580fcaa52338d4bd95f58966a243cdcd3f446ed4bc546d\
+                            9f607b190dd69954450d16cd0e2d6437067d8b44d19a6af7a7cfa8794e5fbd72\
+                            8e8fb2f2e8db5dd4ff1aa275f35886098e80ff844886060da8b1e7137846b23b");
+        assert_eq!(&results[..], &*expected);
+    }
+
+    #[test]
+    fn test_hmac_drbg_cavp_reseed() {
+        // [PredictionResistance = False], COUNT = 0
+        let mut drbg = HmacDrbgCore::new(
+            &hex("06032cd5eed33f39265f49ecb142c511da9aff2af71203bffaf34a9ca5bd9c0d"),
+            &hex("0e66f71edc43e42a45ad3c6fc6cdc4df"), &[]).unwrap();
+        drbg.reseed(&hex("01920a4e669ed3a85ae8a33b35a74ad7fb2a6bb4cf395ce00334a9c9a5a5d552"),
+                    &[]).unwrap();
+        let mut results = [0u8; 128];
+        drbg.generate(&mut results, &[]).unwrap();
+        drbg.generate(&mut results, &[]).unwrap();
+        let expected = hex("76fc79fe9b50beccc991a11b5635783a83536add03c157fb30645e611c2898bb\
+                            2b1bc215000209208cd506cb28da2a51bdb03826aaf2bd2335d576d519160842\
+                            e7158ad0949d1a9ec3e66ea1b1a064b005de914eac2e9d4f2d72a8616a802254\
+                            22918250ff66a41bd2f864a6a38cc5b6499dc43f7f2bd09e1e0f8f5885935124");
+        assert_eq!(&results[..], &*expected);
+    }
+
+    #[test]
+    fn test_hmac_drbg_partial_block() {
+        let mut drbg = HmacDrbgCore::new(&range(0), &range(32)[..16], &[]).unwrap();
+        let mut full = [0u8; 64];
+        drbg.clone().generate(&mut full, &[]).unwrap();
+        let mut results = [0u8; 37];
+        drbg.generate(&mut results, &[]).unwrap();
+        assert_eq!(&results[..], &full[..37]);
+    }
+
+    #[test]
+    fn test_hmac_drbg_reseed_interval() {
+        let mut drbg = HmacDrbgCore::new(&range(0), &range(32)[..16], &[]).unwrap()
+            .with_reseed_interval(2);
+        let mut results = [0u8; 16];
+        drbg.generate(&mut results, &[]).unwrap();
+        drbg.generate(&mut results, &[]).unwrap();
+        assert!(drbg.reseed_required());
+        assert_eq!(drbg.generate(&mut results, &[]), Err(DrbgError::ReseedRequired));
+        drbg.reseed(&range(80), &[]).unwrap();
+        assert!(!drbg.reseed_required());
+        drbg.generate(&mut results, &[]).unwrap();
+
+        // The wrapper reports an error before running out of requests
+        let mut rng = HmacDrbgRng::from(HmacDrbgCore::new(&range(0), &range(32)[..16], &[])
+                                        .unwrap().with_reseed_interval(2));
+        let mut buf = [0u8; 200];
+        rng.fill_bytes(&mut buf);
+        assert!(rng.try_fill_bytes(&mut buf[..57]).is_err());
+        rng.try_fill_bytes(&mut buf[..56]).unwrap();
+        rng.reseed(&range(80), &[]).unwrap();
+        rng.try_fill_bytes(&mut buf).unwrap();
+    }
+
+    #[test]
+    fn test_hmac_drbg_errors() {
+        let e = range(0);
+        assert_eq!(HmacDrbgCore::new(&e[..31], &e[..16], &[]).err(),
+                   Some(DrbgError::EntropyInputTooShort));
+        assert_eq!(HmacDrbgCore::new(&e, &e[..15], &[]).err(),
+                   Some(DrbgError::NonceTooShort));
+        let mut drbg = HmacDrbgCore::new(&e, &e[..16], &[]).unwrap();
+        assert_eq!(drbg.reseed(&e[..16], &[]), Err(DrbgError::EntropyInputTooShort));
+        let mut big = [0u8; (1 << 16) + 1];
+        assert_eq!(drbg.generate(&mut big, &[]), Err(DrbgError::RequestTooLarge));
+        drbg.generate(&mut big[..1 << 16], &[]).unwrap();
+    }
+
+    #[test]
+    fn test_hmac_drbg_rng() {
+        // The wrapper outputs the requests of the core
+        let mut seed = DrbgSeed::default();
+        seed.0[..32].copy_from_slice(&range(0));
+        seed.0[32..].copy_from_slice(&range(32)[..16]);
+        let mut rng = HmacDrbgRng::from_seed(seed.clone());
+        let mut core = HmacDrbgCore::from_seed(seed);
+        let mut expected = [0u8; 256];
+        core.generate(&mut expected[..128], &[]).unwrap();
+        core.generate(&mut expected[128..], &[]).unwrap();
+        let mut results = [0u8; 256];
+        for chunk in results.chunks_mut(24) {
+            rng.fill_bytes(chunk);
+        }
+        assert_eq!(&results[..], &expected[..]);
+
+        // With additional input, buffered output is discarded
+        rng.next_u32();
+        core.generate(&mut expected[..128], &[]).unwrap();
+        core.generate(&mut expected[..37], &range(64)).unwrap();
+        rng.generate(&mut results[..37], &range(64)).unwrap();
+        assert_eq!(&results[..37], &expected[..37]);
+        core.generate(&mut expected[..128], &[]).unwrap();
+        rng.fill_bytes(&mut results[..128]);
+        assert_eq!(&results[..128], &expected[..128]);
+    }
+}
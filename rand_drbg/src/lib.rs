@@ -0,0 +1,76 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The deterministic random bit generators of NIST SP 800-90A.
+//!
+//! This crate implements the three DRBGs of the standard, each with a
+//! security strength of 256 bits:
+//!
+//! - Hash_DRBG with SHA-256, as [`HashDrbgRng`]
+//! - HMAC_DRBG with SHA-256, as [`HmacDrbgRng`]
+//! - CTR_DRBG with AES-256 and the block cipher derivation function, as
+//!   [`CtrDrbgRng`]
+//!
+//! The cores, [`HashDrbgCore`], [`HmacDrbgCore`] and [`CtrDrbgCore`],
+//! provide the instantiate, reseed and generate functions of the standard,
+//! with personalization strings and additional input. They count the
+//! requests since the last reseed and return [`DrbgError::ReseedRequired`]
+//! once the reseed interval, 2<sup>48</sup> by default, is reached.
+//!
+//! The cores implement [`BlockRngCore`] and [`SeedableRng`], with a 32-byte
+//! entropy input followed by a 16-byte nonce as seed, so they can be used as
+//! the generator of [`ReseedingRng`]. Note that `ReseedingRng` reseeds by
+//! instantiating a new DRBG from its reseeder:
+//!
+//! ```
+//! # extern crate rand;
+//! # extern crate rand_drbg;
+//! use rand::prelude::*;
+//! use rand::rngs::OsRng;
+//! use rand::rngs::adapter::ReseedingRng;
+//! use rand_drbg::HmacDrbgCore;
+//!
+//! # fn main() {
+//! let drbg = HmacDrbgCore::from_rng(OsRng::new().unwrap()).unwrap();
+//! let mut rng = ReseedingRng::new(drbg, 1 << 20, OsRng::new().unwrap());
+//! let x: u64 = rng.gen();
+//! # }
+//! ```
+//!
+//! [`BlockRngCore`]: rand_core::block::BlockRngCore
+//! [`SeedableRng`]: rand_core::SeedableRng
+//! [`ReseedingRng`]: ../rand/rngs/adapter/struct.ReseedingRng.html
+
+#![doc(html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk.png",
+       html_favicon_url = "https://www.rust-lang.org/favicon.ico",
+       html_root_url = "https://rust-random.github.io/rand/")]
+
+#![deny(missing_docs)]
+#![deny(missing_debug_implementations)]
+#![doc(test(attr(allow(unused_variables), deny(warnings))))]
+
+#![no_std]
+
+#[cfg(feature="std")] extern crate std;
+
+pub extern crate rand_core;
+extern crate rand_aes;
+
+#[macro_use]
+mod common;
+mod ctr_drbg;
+mod error;
+mod hash_drbg;
+mod hmac_drbg;
+mod sha256;
+
+pub use common::DrbgSeed;
+pub use ctr_drbg::{CtrDrbgRng, CtrDrbgCore};
+pub use error::DrbgError;
+pub use hash_drbg::{HashDrbgRng, HashDrbgCore};
+pub use hmac_drbg::{HmacDrbgRng, HmacDrbgCore};
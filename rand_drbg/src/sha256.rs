@@ -0,0 +1,217 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The SHA-256 hash function and HMAC-SHA-256.
+
+pub(crate) const DIGEST_BYTES: usize = 32;
+const BLOCK_BYTES: usize = 64;
+
+// Initial hash value, FIPS 180-4 section 5.3.3
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+    0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// Round constants, FIPS 180-4 section 4.2.2
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5,
+    0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3,
+    0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc,
+    0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+    0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13,
+    0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3,
+    0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5,
+    0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208,
+    0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// The SHA-256 hash function from FIPS 180-4, computed incrementally.
+#[derive(Clone)]
+pub(crate) struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; BLOCK_BYTES],
+    buffered: usize,
+    length: u64,
+}
+
+impl Sha256 {
+    pub(crate) fn new() -> Sha256 {
+        Sha256 { state: H0, buffer: [0; BLOCK_BYTES], buffered: 0, length: 0 }
+    }
+
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        if self.buffered > 0 {
+            let n = (BLOCK_BYTES - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + n].copy_from_slice(&data[..n]);
+            self.buffered += n;
+            data = &data[n..];
+            if self.buffered < BLOCK_BYTES {
+                return;
+            }
+            compress(&mut self.state, &self.buffer);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks(BLOCK_BYTES);
+        for block in &mut blocks {
+            if block.len() < BLOCK_BYTES {
+                self.buffer[..block.len()].copy_from_slice(block);
+                self.buffered = block.len();
+            } else {
+                compress(&mut self.state, block);
+            }
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> [u8; DIGEST_BYTES] {
+        let bit_length = self.length.wrapping_mul(8);
+        // Pad with a one bit, zeros and the 64-bit message length
+        let mut padding = [0u8; BLOCK_BYTES + 8];
+        padding[0] = 0x80;
+        let zeros = (BLOCK_BYTES + 56 - 1 - self.buffered) % BLOCK_BYTES;
+        for i in 0..8 {
+            padding[1 + zeros + i] = (bit_length >> (56 - 8 * i)) as u8;
+        }
+        self.update(&padding[..1 + zeros + 8]);
+        debug_assert_eq!(self.buffered, 0);
+
+        let mut digest = [0u8; DIGEST_BYTES];
+        for (bytes, word) in digest.chunks_mut(4).zip(self.state.iter()) {
+            for (i, b) in bytes.iter_mut().enumerate() {
+                *b = (word >> (24 - 8 * i)) as u8;
+            }
+        }
+        digest
+    }
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 64];
+    for (w, bytes) in w.iter_mut().zip(block.chunks(4)) {
+        *w = (bytes[0] as u32) << 24 | (bytes[1] as u32) << 16
+           | (bytes[2] as u32) << 8 | bytes[3] as u32;
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16].wrapping_add(s0).wrapping_add(w[t - 7]).wrapping_add(s1);
+    }
+
+    let mut v = *state;
+    for t in 0..64 {
+        let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+        let ch = (v[4] & v[5]) ^ (!v[4] & v[6]);
+        let temp1 = v[7].wrapping_add(s1).wrapping_add(ch)
+                        .wrapping_add(K[t]).wrapping_add(w[t]);
+        let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+        let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+        let temp2 = s0.wrapping_add(maj);
+        v = [temp1.wrapping_add(temp2), v[0], v[1], v[2],
+             v[3].wrapping_add(temp1), v[4], v[5], v[6]];
+    }
+    for (s, v) in state.iter_mut().zip(v.iter()) {
+        *s = s.wrapping_add(*v);
+    }
+}
+
+/// HMAC with SHA-256 from FIPS 198-1, computed incrementally.
+#[derive(Clone)]
+pub(crate) struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    pub(crate) fn new(key: &[u8]) -> HmacSha256 {
+        let mut block_key = [0u8; BLOCK_BYTES];
+        if key.len() > BLOCK_BYTES {
+            let mut hash = Sha256::new();
+            hash.update(key);
+            block_key[..DIGEST_BYTES].copy_from_slice(&hash.finish());
+        } else {
+            block_key[..key.len()].copy_from_slice(key);
+        }
+
+        let mut pad = [0u8; BLOCK_BYTES];
+        let mut inner = Sha256::new();
+        for (p, k) in pad.iter_mut().zip(block_key.iter()) {
+            *p = k ^ 0x36;
+        }
+        inner.update(&pad);
+        let mut outer = Sha256::new();
+        for (p, k) in pad.iter_mut().zip(block_key.iter()) {
+            *p = k ^ 0x5c;
+        }
+        outer.update(&pad);
+        HmacSha256 { inner, outer }
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub(crate) fn finish(self) -> [u8; DIGEST_BYTES] {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finish());
+        outer.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Sha256, HmacSha256};
+
+    fn sha256(data: &[u8]) -> [u8; 32] {
+        let mut hash = Sha256::new();
+        hash.update(data);
+        hash.finish()
+    }
+
+    #[test]
+    fn test_sha256() {
+        // Examples from FIPS 180-4
+        assert_eq!(sha256(b"abc"),
+                   [0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea,
+                    0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+                    0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c,
+                    0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad]);
+        let two_blocks = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        let expected = [0x24, 0x8d, 0x6a, 0x61, 0xd2, 0x06, 0x38, 0xb8,
+                        0xe5, 0xc0, 0x26, 0x93, 0x0c, 0x3e, 0x60, 0x39,
+                        0xa3, 0x3c, 0xe4, 0x59, 0x64, 0xff, 0x21, 0x67,
+                        0xf6, 0xec, 0xed, 0xd4, 0x19, 0xdb, 0x06, 0xc1];
+        assert_eq!(sha256(two_blocks), expected);
+
+        // The same message in uneven pieces
+        let mut hash = Sha256::new();
+        for piece in two_blocks.chunks(7) {
+            hash.update(piece);
+        }
+        assert_eq!(hash.finish(), expected);
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // Test case 2 from RFC 4231
+        let mut hmac = HmacSha256::new(b"Jefe");
+        hmac.update(b"what do ya want ");
+        hmac.update(b"for nothing?");
+        assert_eq!(hmac.finish(),
+                   [0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e,
+                    0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+                    0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83,
+                    0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43]);
+    }
+}
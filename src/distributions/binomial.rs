@@ -12,6 +12,7 @@
 use Rng;
 use distributions::{Distribution, Bernoulli, Cauchy};
 use distributions::utils::log_gamma;
+use std::{error, fmt};
 
 /// The binomial distribution `Binomial(n, p)`.
 ///
@@ -23,7 +24,7 @@ use distributions::utils::log_gamma;
 /// ```
 /// use rand::distributions::{Binomial, Distribution};
 ///
-/// let bin = Binomial::new(20, 0.3);
+/// let bin = Binomial::new(20, 0.3).unwrap();
 /// let v = bin.sample(&mut rand::thread_rng());
 /// println!("{} is from a binomial distribution", v);
 /// ```
@@ -35,15 +36,49 @@ pub struct Binomial {
     p: f64,
 }
 
+/// Error type returned from `Binomial::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinomialError {
+    /// `p < 0` or `nan`.
+    ProbabilityTooSmall,
+    /// `p > 1`.
+    ProbabilityTooLarge,
+}
+
+impl BinomialError {
+    fn msg(&self) -> &str {
+        match *self {
+            BinomialError::ProbabilityTooSmall => "p < 0 or is NaN in binomial distribution",
+            BinomialError::ProbabilityTooLarge => "p > 1 in binomial distribution",
+        }
+    }
+}
+
+impl fmt::Display for BinomialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for BinomialError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
 impl Binomial {
     /// Construct a new `Binomial` with the given shape parameters `n` (number
     /// of trials) and `p` (probability of success).
     ///
-    /// Panics if `p < 0` or `p > 1`.
-    pub fn new(n: u64, p: f64) -> Binomial {
-        assert!(p >= 0.0, "Binomial::new called with p < 0");
-        assert!(p <= 1.0, "Binomial::new called with p > 1");
-        Binomial { n, p }
+    /// Returns an error if `p < 0` or `p > 1`.
+    pub fn new(n: u64, p: f64) -> Result<Binomial, BinomialError> {
+        if !(p >= 0.0) {
+            return Err(BinomialError::ProbabilityTooSmall);
+        }
+        if !(p <= 1.0) {
+            return Err(BinomialError::ProbabilityTooLarge);
+        }
+        Ok(Binomial { n, p })
     }
 }
 
@@ -61,7 +96,7 @@ impl Distribution<u64> for Binomial {
         // appears to be faster for approx n < 300.
         if self.n < 300 {
             let mut result = 0;
-            let d = Bernoulli::new(self.p);
+            let d = Bernoulli::new(self.p).unwrap();
             for _ in 0 .. self.n {
                 result += rng.sample(d) as u32;
             }
@@ -90,7 +125,7 @@ impl Distribution<u64> for Binomial {
 
         // we use the Cauchy distribution as the comparison distribution
         // f(x) ~ 1/(1+x^2)
-        let cauchy = Cauchy::new(0.0, 1.0);
+        let cauchy = Cauchy::new(0.0, 1.0).unwrap();
         loop {
             let mut comp_dev: f64;
             loop {
@@ -132,10 +167,10 @@ impl Distribution<u64> for Binomial {
 mod test {
     use Rng;
     use distributions::Distribution;
-    use super::Binomial;
+    use super::{Binomial, BinomialError};
 
     fn test_binomial_mean_and_variance<R: Rng>(n: u64, p: f64, rng: &mut R) {
-        let binomial = Binomial::new(n, p);
+        let binomial = Binomial::new(n, p).unwrap();
 
         let expected_mean = n as f64 * p;
         let expected_variance = n as f64 * p * (1.0 - p);
@@ -165,13 +200,13 @@ mod test {
     #[test]
     fn test_binomial_end_points() {
         let mut rng = ::test::rng(352);
-        assert_eq!(rng.sample(Binomial::new(20, 0.0)), 0);
-        assert_eq!(rng.sample(Binomial::new(20, 1.0)), 20);
+        assert_eq!(rng.sample(Binomial::new(20, 0.0).unwrap()), 0);
+        assert_eq!(rng.sample(Binomial::new(20, 1.0).unwrap()), 20);
     }
 
     #[test]
-    #[should_panic]
     fn test_binomial_invalid_lambda_neg() {
-        Binomial::new(20, -10.0);
+        assert_eq!(Binomial::new(20, -10.0).unwrap_err(), BinomialError::ProbabilityTooSmall);
+        assert_eq!(Binomial::new(20, 1.5).unwrap_err(), BinomialError::ProbabilityTooLarge);
     }
 }
@@ -10,8 +10,8 @@
 //! The Cauchy distribution.
 
 use Rng;
-use distributions::Distribution;
-use std::f64::consts::PI;
+use distributions::{Distribution, Float, Standard};
+use std::{error, fmt};
 
 /// The Cauchy distribution `Cauchy(median, scale)`.
 ///
@@ -23,36 +23,65 @@ use std::f64::consts::PI;
 /// ```
 /// use rand::distributions::{Cauchy, Distribution};
 ///
-/// let cau = Cauchy::new(2.0, 5.0);
+/// let cau = Cauchy::new(2.0, 5.0).unwrap();
 /// let v = cau.sample(&mut rand::thread_rng());
 /// println!("{} is from a Cauchy(2, 5) distribution", v);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Cauchy {
-    median: f64,
-    scale: f64
+pub struct Cauchy<F> {
+    median: F,
+    scale: F
 }
 
-impl Cauchy {
+/// Error type returned from `Cauchy::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CauchyError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+}
+
+impl CauchyError {
+    fn msg(&self) -> &str {
+        match *self {
+            CauchyError::ScaleNotPositive => "scale is not positive in Cauchy distribution",
+        }
+    }
+}
+
+impl fmt::Display for CauchyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for CauchyError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Cauchy<F> {
     /// Construct a new `Cauchy` with the given shape parameters
     /// `median` the peak location and `scale` the scale factor.
-    /// Panics if `scale <= 0`.
-    pub fn new(median: f64, scale: f64) -> Cauchy {
-        assert!(scale > 0.0, "Cauchy::new called with scale factor <= 0");
-        Cauchy {
+    /// Returns an error if `scale <= 0`.
+    pub fn new(median: F, scale: F) -> Result<Cauchy<F>, CauchyError> {
+        if !(scale > F::zero()) {
+            return Err(CauchyError::ScaleNotPositive);
+        }
+        Ok(Cauchy {
             median,
             scale
-        }
+        })
     }
 }
 
-impl Distribution<f64> for Cauchy {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for Cauchy<F> where Standard: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
         // sample from [0, 1)
-        let x = rng.gen::<f64>();
+        let x: F = rng.gen();
         // get standard cauchy random number
         // note that π/2 is not exactly representable, even if x=0.5 the result is finite
-        let comp_dev = (PI * x).tan();
+        let comp_dev = (F::pi() * x).tan();
         // shift and scale according to parameters
         let result = self.median + self.scale * comp_dev;
         result
@@ -62,7 +91,7 @@ impl Distribution<f64> for Cauchy {
 #[cfg(test)]
 mod test {
     use distributions::Distribution;
-    use super::Cauchy;
+    use super::{Cauchy, CauchyError};
 
     fn median(mut numbers: &mut [f64]) -> f64 {
         sort(&mut numbers);
@@ -76,7 +105,7 @@ mod test {
 
     #[test]
     fn test_cauchy_median() {
-        let cauchy = Cauchy::new(10.0, 5.0);
+        let cauchy = Cauchy::new(10.0, 5.0).unwrap();
         let mut rng = ::test::rng(123);
         let mut numbers: [f64; 1000] = [0.0; 1000];
         for i in 0..1000 {
@@ -89,9 +118,9 @@ mod test {
 
     #[test]
     fn test_cauchy_mean() {
-        let cauchy = Cauchy::new(10.0, 5.0);
+        let cauchy = Cauchy::new(10.0, 5.0).unwrap();
         let mut rng = ::test::rng(123);
-        let mut sum = 0.0;
+        let mut sum = 0.0f64;
         for _ in 0..1000 {
             sum += cauchy.sample(&mut rng);
         }
@@ -102,14 +131,24 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
+    fn test_cauchy_f32() {
+        let cauchy = Cauchy::new(10.0f32, 5.0).unwrap();
+        let mut rng = ::test::rng(124);
+        let mut numbers = [0.0f32; 1000];
+        for x in numbers.iter_mut() {
+            *x = cauchy.sample(&mut rng);
+        }
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((numbers[500] - 10.0).abs() < 0.5);
+    }
+
+    #[test]
     fn test_cauchy_invalid_scale_zero() {
-        Cauchy::new(0.0, 0.0);
+        assert_eq!(Cauchy::new(0.0, 0.0).unwrap_err(), CauchyError::ScaleNotPositive);
     }
 
     #[test]
-    #[should_panic]
     fn test_cauchy_invalid_scale_neg() {
-        Cauchy::new(0.0, -10.0);
+        assert_eq!(Cauchy::new(0.0, -10.0).unwrap_err(), CauchyError::ScaleNotPositive);
     }
 }
@@ -0,0 +1,243 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Adapters combining distributions, created by the methods of
+//! [`Distribution`].
+
+use core::marker::PhantomData;
+use Rng;
+use distributions::Distribution;
+
+/// A distribution of values of `S` derived from a distribution of `T` by
+/// applying a function.
+///
+/// This `struct` is created by the [`map`] method on [`Distribution`].
+///
+/// [`map`]: Distribution::map
+#[derive(Debug)]
+pub struct DistMap<D, F, T, S> {
+    distr: D,
+    func: F,
+    phantom: PhantomData<fn(T) -> S>,
+}
+
+impl<D, F, T, S> DistMap<D, F, T, S> {
+    pub(crate) fn new(distr: D, func: F) -> DistMap<D, F, T, S> {
+        DistMap { distr, func, phantom: PhantomData }
+    }
+}
+
+impl<D: Clone, F: Clone, T, S> Clone for DistMap<D, F, T, S> {
+    fn clone(&self) -> Self {
+        DistMap::new(self.distr.clone(), self.func.clone())
+    }
+}
+
+impl<D, F, T, S> Distribution<S> for DistMap<D, F, T, S>
+    where D: Distribution<T>, F: Fn(T) -> S
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S {
+        (self.func)(self.distr.sample(rng))
+    }
+}
+
+/// A distribution of the values of another distribution which satisfy a
+/// predicate, sampled by rejection.
+///
+/// This `struct` is created by the [`filter`] method on [`Distribution`].
+///
+/// [`filter`]: Distribution::filter
+#[derive(Debug)]
+pub struct DistFilter<D, P, T> {
+    distr: D,
+    predicate: P,
+    max_attempts: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<D, P, T> DistFilter<D, P, T> {
+    pub(crate) fn new(distr: D, predicate: P, max_attempts: usize) -> DistFilter<D, P, T> {
+        DistFilter { distr, predicate, max_attempts, phantom: PhantomData }
+    }
+}
+
+impl<D: Clone, P: Clone, T> Clone for DistFilter<D, P, T> {
+    fn clone(&self) -> Self {
+        DistFilter::new(self.distr.clone(), self.predicate.clone(), self.max_attempts)
+    }
+}
+
+impl<D, P, T> DistFilter<D, P, T> where D: Distribution<T>, P: Fn(&T) -> bool {
+    /// Sample until a value satisfies the predicate, giving up after
+    /// `max_attempts` samples.
+    ///
+    /// Returns `None` if no sample satisfied the predicate.
+    pub fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<T> {
+        for _ in 0..self.max_attempts {
+            let x = self.distr.sample(rng);
+            if (self.predicate)(&x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
+impl<D, P, T> Distribution<T> for DistFilter<D, P, T>
+    where D: Distribution<T>, P: Fn(&T) -> bool
+{
+    /// Sample until a value satisfies the predicate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no sample satisfies the predicate within `max_attempts`
+    /// samples. Use [`try_sample`] to handle this case.
+    ///
+    /// [`try_sample`]: DistFilter::try_sample
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        match self.try_sample(rng) {
+            Some(x) => x,
+            None => panic!("DistFilter: no sample accepted after {} attempts",
+                           self.max_attempts),
+        }
+    }
+}
+
+/// A distribution of pairs of independent samples from two distributions.
+///
+/// This `struct` is created by the [`zip`] method on [`Distribution`].
+///
+/// [`zip`]: Distribution::zip
+#[derive(Clone, Copy, Debug)]
+pub struct DistZip<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> DistZip<A, B> {
+    pub(crate) fn new(a: A, b: B) -> DistZip<A, B> {
+        DistZip { a, b }
+    }
+}
+
+impl<A, B, T, U> Distribution<(T, U)> for DistZip<A, B>
+    where A: Distribution<T>, B: Distribution<U>
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (T, U) {
+        let t = self.a.sample(rng);
+        (t, self.b.sample(rng))
+    }
+}
+
+/// A distribution which samples from a distribution chosen by a sample of
+/// another distribution.
+///
+/// This `struct` is created by the [`flat_map`] method on [`Distribution`].
+///
+/// [`flat_map`]: Distribution::flat_map
+#[derive(Debug)]
+pub struct DistFlatMap<D, F, T> {
+    distr: D,
+    func: F,
+    phantom: PhantomData<fn(T)>,
+}
+
+impl<D, F, T> DistFlatMap<D, F, T> {
+    pub(crate) fn new(distr: D, func: F) -> DistFlatMap<D, F, T> {
+        DistFlatMap { distr, func, phantom: PhantomData }
+    }
+}
+
+impl<D: Clone, F: Clone, T> Clone for DistFlatMap<D, F, T> {
+    fn clone(&self) -> Self {
+        DistFlatMap::new(self.distr.clone(), self.func.clone())
+    }
+}
+
+impl<D, F, T, E, S> Distribution<S> for DistFlatMap<D, F, T>
+    where D: Distribution<T>, F: Fn(T) -> E, E: Distribution<S>
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S {
+        (self.func)(self.distr.sample(rng)).sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::{Distribution, Uniform, Bernoulli, Standard};
+
+    #[test]
+    fn test_map() {
+        let mut rng = ::test::rng(850);
+        let d = Uniform::new(0u32, 10).map(|x| x * 2);
+        for _ in 0..100 {
+            let x = d.sample(&mut rng);
+            assert!(x < 20 && x % 2 == 0);
+        }
+        let d = d.map(|x| x as f64 / 2.0);
+        let x: f64 = d.sample(&mut rng);
+        assert!(x < 10.0);
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut rng = ::test::rng(851);
+        let d = Uniform::new(0u32, 100).filter(|x| x % 7 == 0, 1000);
+        for _ in 0..100 {
+            assert_eq!(d.sample(&mut rng) % 7, 0);
+        }
+        let never = Uniform::new(0u32, 100).filter(|&x| x >= 100, 10);
+        assert_eq!(never.try_sample(&mut rng), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_filter_exhausted() {
+        let never = Uniform::new(0u32, 100).filter(|&x| x >= 100, 10);
+        never.sample(&mut ::test::rng(852));
+    }
+
+    #[test]
+    fn test_zip() {
+        let mut rng = ::test::rng(853);
+        let d = Uniform::new(0, 10).zip(Bernoulli::new(0.5).unwrap());
+        let mut trues = 0;
+        for _ in 0..1000 {
+            let (x, b): (i32, bool) = d.sample(&mut rng);
+            assert!(x >= 0 && x < 10);
+            if b {
+                trues += 1;
+            }
+        }
+        assert!(trues > 400 && trues < 600);
+
+        // Sampling from references does not consume the distributions
+        let u = Uniform::new(0.0, 1.0);
+        let (x, y): (f64, f64) = (&u).zip(&u).sample(&mut rng);
+        assert!(x < 1.0 && y < 1.0);
+    }
+
+    #[test]
+    fn test_flat_map() {
+        let mut rng = ::test::rng(854);
+        // Choose the range of a uniform distribution at random
+        let d = Uniform::new(1u32, 5).flat_map(|n| Uniform::new(0, n * 10));
+        for _ in 0..100 {
+            let x: u32 = d.sample(&mut rng);
+            assert!(x < 40);
+        }
+        let d = Standard.flat_map(|b: bool| if b { Uniform::new(0, 1) } else { Uniform::new(5, 6) });
+        for _ in 0..100 {
+            let x: i32 = d.sample(&mut rng);
+            assert!(x == 0 || x == 5);
+        }
+    }
+}
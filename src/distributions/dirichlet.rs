@@ -12,6 +12,7 @@
 use Rng;
 use distributions::Distribution;
 use distributions::gamma::Gamma;
+use std::{error, fmt};
 
 /// The dirichelet distribution `Dirichlet(alpha)`.
 ///
@@ -25,7 +26,7 @@ use distributions::gamma::Gamma;
 /// use rand::prelude::*;
 /// use rand::distributions::Dirichlet;
 ///
-/// let dirichlet = Dirichlet::new(vec![1.0, 2.0, 3.0]);
+/// let dirichlet = Dirichlet::new(vec![1.0, 2.0, 3.0]).unwrap();
 /// let samples = dirichlet.sample(&mut rand::thread_rng());
 /// println!("{:?} is from a Dirichlet([1.0, 2.0, 3.0]) distribution", samples);
 /// ```
@@ -36,36 +37,75 @@ pub struct Dirichlet {
     alpha: Vec<f64>,
 }
 
+/// Error type returned from `Dirichlet::new` and `Dirichlet::new_with_param`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirichletError {
+    /// `alpha.len() < 2`.
+    AlphaTooShort,
+    /// `alpha <= 0.0` or `nan`.
+    AlphaNotPositive,
+}
+
+impl DirichletError {
+    fn msg(&self) -> &str {
+        match *self {
+            DirichletError::AlphaTooShort => "less than 2 dimensions in Dirichlet distribution",
+            DirichletError::AlphaNotPositive => "alpha is not positive in Dirichlet distribution",
+        }
+    }
+}
+
+impl fmt::Display for DirichletError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for DirichletError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
 impl Dirichlet {
     /// Construct a new `Dirichlet` with the given alpha parameter `alpha`.
     ///
-    /// # Panics
+    /// # Errors
     /// - if `alpha.len() < 2`
+    /// - if any element of `alpha` is `<= 0.0`
     ///
     #[inline]
-    pub fn new<V: Into<Vec<f64>>>(alpha: V) -> Dirichlet {
+    pub fn new<V: Into<Vec<f64>>>(alpha: V) -> Result<Dirichlet, DirichletError> {
         let a = alpha.into();
-        assert!(a.len() > 1);
-        for i in 0..a.len() {
-            assert!(a[i] > 0.0);
+        if a.len() < 2 {
+            return Err(DirichletError::AlphaTooShort);
+        }
+        for &ai in &a {
+            if !(ai > 0.0) {
+                return Err(DirichletError::AlphaNotPositive);
+            }
         }
 
-        Dirichlet { alpha: a }
+        Ok(Dirichlet { alpha: a })
     }
 
     /// Construct a new `Dirichlet` with the given shape parameter `alpha` and `size`.
     ///
-    /// # Panics
+    /// # Errors
     /// - if `alpha <= 0.0`
     /// - if `size < 2`
     ///
     #[inline]
-    pub fn new_with_param(alpha: f64, size: usize) -> Dirichlet {
-        assert!(alpha > 0.0);
-        assert!(size > 1);
-        Dirichlet {
-            alpha: vec![alpha; size],
+    pub fn new_with_param(alpha: f64, size: usize) -> Result<Dirichlet, DirichletError> {
+        if !(alpha > 0.0) {
+            return Err(DirichletError::AlphaNotPositive);
         }
+        if size < 2 {
+            return Err(DirichletError::AlphaTooShort);
+        }
+        Ok(Dirichlet {
+            alpha: vec![alpha; size],
+        })
     }
 }
 
@@ -76,7 +116,7 @@ impl Distribution<Vec<f64>> for Dirichlet {
         let mut sum = 0.0f64;
 
         for i in 0..n {
-            let g = Gamma::new(self.alpha[i], 1.0);
+            let g = Gamma::new(self.alpha[i], 1.0).unwrap();
             samples[i] = g.sample(rng);
             sum += samples[i];
         }
@@ -90,12 +130,12 @@ impl Distribution<Vec<f64>> for Dirichlet {
 
 #[cfg(test)]
 mod test {
-    use super::Dirichlet;
+    use super::{Dirichlet, DirichletError};
     use distributions::Distribution;
 
     #[test]
     fn test_dirichlet() {
-        let d = Dirichlet::new(vec![1.0, 2.0, 3.0]);
+        let d = Dirichlet::new(vec![1.0, 2.0, 3.0]).unwrap();
         let mut rng = ::test::rng(221);
         let samples = d.sample(&mut rng);
         let _: Vec<f64> = samples
@@ -111,7 +151,7 @@ mod test {
     fn test_dirichlet_with_param() {
         let alpha = 0.5f64;
         let size = 2;
-        let d = Dirichlet::new_with_param(alpha, size);
+        let d = Dirichlet::new_with_param(alpha, size).unwrap();
         let mut rng = ::test::rng(221);
         let samples = d.sample(&mut rng);
         let _: Vec<f64> = samples
@@ -124,14 +164,18 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
     fn test_dirichlet_invalid_length() {
-        Dirichlet::new_with_param(0.5f64, 1);
+        assert_eq!(Dirichlet::new_with_param(0.5f64, 1).unwrap_err(),
+                   DirichletError::AlphaTooShort);
+        assert_eq!(Dirichlet::new(vec![0.5f64]).unwrap_err(),
+                   DirichletError::AlphaTooShort);
     }
 
     #[test]
-    #[should_panic]
     fn test_dirichlet_invalid_alpha() {
-        Dirichlet::new_with_param(0.0f64, 2);
+        assert_eq!(Dirichlet::new_with_param(0.0f64, 2).unwrap_err(),
+                   DirichletError::AlphaNotPositive);
+        assert_eq!(Dirichlet::new(vec![1.0, -1.0]).unwrap_err(),
+                   DirichletError::AlphaNotPositive);
     }
 }
@@ -0,0 +1,212 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The empirical distribution.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+use std::{error, fmt};
+
+/// The empirical distribution of a sample of observations.
+///
+/// Without interpolation, this distribution samples one of the observations
+/// uniformly at random, i.e. it resamples the data with replacement, and
+/// only ever returns observed values.
+///
+/// With interpolation, the observations are sorted and the distribution
+/// interpolates linearly between consecutive order statistics: the
+/// cumulative distribution function increases linearly by `1 / (n - 1)`
+/// between each pair of consecutive observations, from the smallest to the
+/// largest observation. This gives a continuous distribution over the range
+/// of the data.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Empirical};
+///
+/// let latencies = [12.0, 15.5, 11.2, 48.0, 13.1, 12.7];
+/// let mut rng = rand::thread_rng();
+///
+/// let replay = Empirical::new(&latencies).unwrap();
+/// let v = replay.sample(&mut rng);
+/// assert!(latencies.contains(&v));
+///
+/// let smooth = Empirical::new_interpolated(&latencies).unwrap();
+/// let v = smooth.sample(&mut rng);
+/// assert!(v >= 11.2 && v <= 48.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Empirical {
+    observations: Vec<f64>,
+    method: Method,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Method {
+    // Pick an observation.
+    Discrete(Uniform<usize>),
+    // Pick a position in `[0, n - 1)` and interpolate between the sorted
+    // observations around it.
+    Interpolated(Uniform<f64>),
+}
+
+/// Error type returned from `Empirical::new` and `Empirical::new_interpolated`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmpiricalError {
+    /// There are no observations.
+    NoObservations,
+    /// An observation is infinite or `nan`.
+    ObservationNotFinite,
+}
+
+impl EmpiricalError {
+    fn msg(&self) -> &str {
+        match *self {
+            EmpiricalError::NoObservations =>
+                "no observations in empirical distribution",
+            EmpiricalError::ObservationNotFinite =>
+                "observation is not finite in empirical distribution",
+        }
+    }
+}
+
+impl fmt::Display for EmpiricalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for EmpiricalError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl Empirical {
+    /// Construct a new `Empirical` distribution sampling one of the given
+    /// observations uniformly at random.
+    ///
+    /// Returns an error if there are no observations or any observation is
+    /// not finite.
+    pub fn new(observations: &[f64]) -> Result<Empirical, EmpiricalError> {
+        let observations = Empirical::check(observations)?;
+        let method = Method::Discrete(Uniform::new(0, observations.len()));
+        Ok(Empirical { observations, method })
+    }
+
+    /// Construct a new `Empirical` distribution interpolating linearly
+    /// between the sorted observations.
+    ///
+    /// Returns an error if there are no observations or any observation is
+    /// not finite. With a single observation, that value is always returned.
+    pub fn new_interpolated(observations: &[f64]) -> Result<Empirical, EmpiricalError> {
+        let mut observations = Empirical::check(observations)?;
+        observations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = observations.len();
+        let method = if n == 1 {
+            Method::Discrete(Uniform::new(0, 1))
+        } else {
+            Method::Interpolated(Uniform::new(0.0, (n - 1) as f64))
+        };
+        Ok(Empirical { observations, method })
+    }
+
+    fn check(observations: &[f64]) -> Result<Vec<f64>, EmpiricalError> {
+        if observations.is_empty() {
+            return Err(EmpiricalError::NoObservations);
+        }
+        if observations.iter().any(|x| !x.is_finite()) {
+            return Err(EmpiricalError::ObservationNotFinite);
+        }
+        Ok(observations.to_vec())
+    }
+
+    /// Returns the observations. They are sorted if the distribution
+    /// interpolates between them.
+    pub fn observations(&self) -> &[f64] {
+        &self.observations
+    }
+}
+
+impl Distribution<f64> for Empirical {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self.method {
+            Method::Discrete(index) => self.observations[index.sample(rng)],
+            Method::Interpolated(position) => {
+                let position = position.sample(rng);
+                // `position` is non-negative, so the cast truncates to its
+                // floor; guard against rounding up to `n - 1`.
+                let i = (position as usize).min(self.observations.len() - 2);
+                let frac = position - i as f64;
+                let (low, high) = (self.observations[i], self.observations[i + 1]);
+                (low + frac * (high - low)).min(high)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{Empirical, EmpiricalError};
+
+    #[test]
+    fn test_empirical() {
+        let mut rng = ::test::rng(870);
+        let data = [3.0, 1.0, 2.0, 1.0];
+        let d = Empirical::new(&data).unwrap();
+        assert_eq!(d.observations(), &data);
+        let mut counts = [0; 3];
+        for _ in 0..4000 {
+            let x = d.sample(&mut rng);
+            counts[x as usize - 1] += 1;
+        }
+        // 1.0 is observed twice as often as the other values.
+        assert!(counts[0] > 1800 && counts[0] < 2200, "{:?}", counts);
+        assert!(counts[1] > 850 && counts[1] < 1150, "{:?}", counts);
+        assert!(counts[2] > 850 && counts[2] < 1150, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_empirical_interpolated() {
+        let mut rng = ::test::rng(871);
+        let d = Empirical::new_interpolated(&[4.0, 0.0, 1.0]).unwrap();
+        assert_eq!(d.observations(), &[0.0, 1.0, 4.0]);
+        const N: usize = 10000;
+        let mut below_one = 0;
+        let mut sum = 0.0;
+        for _ in 0..N {
+            let x = d.sample(&mut rng);
+            assert!(x >= 0.0 && x <= 4.0);
+            if x < 1.0 {
+                below_one += 1;
+            }
+            sum += x;
+        }
+        // Half the mass is uniform on [0, 1] and half uniform on [1, 4].
+        let p = below_one as f64 / N as f64;
+        assert!((p - 0.5).abs() < 0.02, "{}", p);
+        let mean = sum / N as f64;
+        assert!((mean - 1.5).abs() < 0.05, "{}", mean);
+
+        let single = Empirical::new_interpolated(&[7.0]).unwrap();
+        assert_eq!(single.sample(&mut rng), 7.0);
+    }
+
+    #[test]
+    fn test_empirical_invalid() {
+        assert_eq!(Empirical::new(&[]).unwrap_err(), EmpiricalError::NoObservations);
+        assert_eq!(Empirical::new_interpolated(&[]).unwrap_err(),
+                   EmpiricalError::NoObservations);
+        assert_eq!(Empirical::new(&[1.0, ::std::f64::NAN]).unwrap_err(),
+                   EmpiricalError::ObservationNotFinite);
+        assert_eq!(Empirical::new_interpolated(&[::std::f64::INFINITY]).unwrap_err(),
+                   EmpiricalError::ObservationNotFinite);
+    }
+}
@@ -10,8 +10,9 @@
 //! The exponential distribution.
 
 use {Rng};
-use distributions::{ziggurat_tables, Distribution};
+use distributions::{ziggurat_tables, Distribution, Float};
 use distributions::utils::ziggurat;
+use std::{error, fmt};
 
 /// Samples floating-point numbers according to the exponential distribution,
 /// with rate parameter `λ = 1`. This is equivalent to `Exp::new(1.0)` or
@@ -19,9 +20,9 @@ use distributions::utils::ziggurat;
 ///
 /// See `Exp` for the general exponential distribution.
 ///
-/// Implemented via the ZIGNOR variant[^1] of the Ziggurat method. The exact
-/// description in the paper was adjusted to use tables for the exponential
-/// distribution rather than normal.
+/// Implemented via the ZIGNOR variant[^1] of the Ziggurat method, for both
+/// `f32` and `f64`. The exact description in the paper was adjusted to use
+/// tables for the exponential distribution rather than normal.
 ///
 /// [^1]: Jurgen A. Doornik (2005). [*An Improved Ziggurat Method to
 ///       Generate Normal Random Samples*](
@@ -39,26 +40,35 @@ use distributions::utils::ziggurat;
 #[derive(Clone, Copy, Debug)]
 pub struct Exp1;
 
-// This could be done via `-rng.gen::<f64>().ln()` but that is slower.
-impl Distribution<f64> for Exp1 {
-    #[inline]
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        #[inline]
-        fn pdf(x: f64) -> f64 {
-            (-x).exp()
-        }
-        #[inline]
-        fn zero_case<R: Rng + ?Sized>(rng: &mut R, _u: f64) -> f64 {
-            ziggurat_tables::ZIG_EXP_R - rng.gen::<f64>().ln()
-        }
+macro_rules! exp1_impl {
+    ($ty:ident, $zig_r:expr, $zig_x:expr, $zig_f:expr) => {
+        // This could be done via `-rng.gen::<$ty>().ln()` but that is slower.
+        impl Distribution<$ty> for Exp1 {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                #[inline]
+                fn pdf(x: $ty) -> $ty {
+                    (-x).exp()
+                }
+                #[inline]
+                fn zero_case<R: Rng + ?Sized>(rng: &mut R, _u: $ty) -> $ty {
+                    $zig_r - rng.gen::<$ty>().ln()
+                }
 
-        ziggurat(rng, false,
-                 &ziggurat_tables::ZIG_EXP_X,
-                 &ziggurat_tables::ZIG_EXP_F,
-                 pdf, zero_case)
+                ziggurat(rng, false,
+                         &$zig_x,
+                         &$zig_f,
+                         pdf, zero_case)
+            }
+        }
     }
 }
 
+exp1_impl! { f32, ziggurat_tables::ZIG_EXP_R_F32,
+    ziggurat_tables::ZIG_EXP_X_F32, ziggurat_tables::ZIG_EXP_F_F32 }
+exp1_impl! { f64, ziggurat_tables::ZIG_EXP_R,
+    ziggurat_tables::ZIG_EXP_X, ziggurat_tables::ZIG_EXP_F }
+
 /// The exponential distribution `Exp(lambda)`.
 ///
 /// This distribution has density function: `f(x) = lambda * exp(-lambda * x)`
@@ -71,29 +81,58 @@ impl Distribution<f64> for Exp1 {
 /// ```
 /// use rand::distributions::{Exp, Distribution};
 ///
-/// let exp = Exp::new(2.0);
+/// let exp = Exp::new(2.0).unwrap();
 /// let v = exp.sample(&mut rand::thread_rng());
 /// println!("{} is from a Exp(2) distribution", v);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Exp {
+pub struct Exp<F> {
     /// `lambda` stored as `1/lambda`, since this is what we scale by.
-    lambda_inverse: f64
+    lambda_inverse: F
+}
+
+/// Error type returned from `Exp::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpError {
+    /// `lambda <= 0` or `nan`.
+    LambdaNotPositive,
+}
+
+impl ExpError {
+    fn msg(&self) -> &str {
+        match *self {
+            ExpError::LambdaNotPositive => "lambda is not positive in exponential distribution",
+        }
+    }
 }
 
-impl Exp {
+impl fmt::Display for ExpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for ExpError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Exp<F> {
     /// Construct a new `Exp` with the given shape parameter
-    /// `lambda`. Panics if `lambda <= 0`.
+    /// `lambda`. Returns an error if `lambda <= 0`.
     #[inline]
-    pub fn new(lambda: f64) -> Exp {
-        assert!(lambda > 0.0, "Exp::new called with `lambda` <= 0");
-        Exp { lambda_inverse: 1.0 / lambda }
+    pub fn new(lambda: F) -> Result<Exp<F>, ExpError> {
+        if !(lambda > F::zero()) {
+            return Err(ExpError::LambdaNotPositive);
+        }
+        Ok(Exp { lambda_inverse: F::one() / lambda })
     }
 }
 
-impl Distribution<f64> for Exp {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let n: f64 = rng.sample(Exp1);
+impl<F: Float> Distribution<F> for Exp<F> where Exp1: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let n: F = rng.sample(Exp1);
         n * self.lambda_inverse
     }
 }
@@ -101,24 +140,34 @@ impl Distribution<f64> for Exp {
 #[cfg(test)]
 mod test {
     use distributions::Distribution;
-    use super::Exp;
+    use super::{Exp, ExpError};
 
     #[test]
     fn test_exp() {
-        let exp = Exp::new(10.0);
+        let exp = Exp::new(10.0).unwrap();
         let mut rng = ::test::rng(221);
         for _ in 0..1000 {
             assert!(exp.sample(&mut rng) >= 0.0);
         }
     }
     #[test]
-    #[should_panic]
+    fn test_exp_f32() {
+        let exp = Exp::new(10.0f32).unwrap();
+        let mut rng = ::test::rng(222);
+        let mut sum = 0.0f32;
+        for _ in 0..1000 {
+            let x: f32 = exp.sample(&mut rng);
+            assert!(x >= 0.0);
+            sum += x;
+        }
+        assert!((sum / 1000.0 - 0.1).abs() < 0.02);
+    }
+    #[test]
     fn test_exp_invalid_lambda_zero() {
-        Exp::new(0.0);
+        assert_eq!(Exp::new(0.0).unwrap_err(), ExpError::LambdaNotPositive);
     }
     #[test]
-    #[should_panic]
     fn test_exp_invalid_lambda_neg() {
-        Exp::new(-10.0);
+        assert_eq!(Exp::new(-10.0).unwrap_err(), ExpError::LambdaNotPositive);
     }
 }
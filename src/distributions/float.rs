@@ -9,6 +9,8 @@
 //! Basic floating-point number distributions
 
 use core::mem;
+#[cfg(feature="std")] use core::fmt::Debug;
+#[cfg(feature="std")] use core::ops::{Add, Sub, Mul, Div, Neg};
 use Rng;
 use distributions::{Distribution, Standard};
 use distributions::utils::FloatSIMDUtils;
@@ -69,6 +71,104 @@ pub struct OpenClosed01;
 pub struct Open01;
 
 
+/// A floating-point type, `f32` or `f64`, usable as the parameter and sample
+/// type of the continuous distributions such as [`Normal`] and [`Gamma`].
+///
+/// This trait only provides the operations those distributions need; it is
+/// implemented for `f32` and `f64` and is not intended to be implemented for
+/// other types.
+///
+/// [`Normal`]: crate::distributions::Normal
+/// [`Gamma`]: crate::distributions::Gamma
+#[cfg(feature="std")]
+pub trait Float: Copy + PartialOrd + Debug
+    + Add<Output = Self> + Sub<Output = Self>
+    + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+{
+    /// Convert an `f64` to `Self`, rounding to the nearest value if necessary.
+    fn from_f64(x: f64) -> Self;
+    /// The additive identity, `0.0`.
+    fn zero() -> Self;
+    /// The multiplicative identity, `1.0`.
+    fn one() -> Self;
+    /// Archimedes' constant, `π`.
+    fn pi() -> Self;
+    /// The difference between `1.0` and the next larger representable number.
+    fn epsilon() -> Self;
+
+    /// Returns `true` if this value is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+    /// Computes the absolute value of `self`.
+    fn abs(self) -> Self;
+    /// Returns the largest integer less than or equal to `self`.
+    fn floor(self) -> Self;
+    /// Returns the square root of `self`.
+    fn sqrt(self) -> Self;
+    /// Returns `e^(self)`.
+    fn exp(self) -> Self;
+    /// Returns `e^(self) - 1`, accurately even if `self` is close to zero.
+    fn exp_m1(self) -> Self;
+    /// Returns the natural logarithm of `self`.
+    fn ln(self) -> Self;
+    /// Returns `ln(1 + self)`, accurately even if `self` is close to zero.
+    fn ln_1p(self) -> Self;
+    /// Raises `self` to the floating-point power `n`.
+    fn powf(self, n: Self) -> Self;
+    /// Computes the tangent of `self` (in radians).
+    fn tan(self) -> Self;
+    /// Computes the cosine of `self` (in radians).
+    fn cos(self) -> Self;
+    /// Computes the arccosine of `self`, in the range `[0, π]`.
+    fn acos(self) -> Self;
+}
+
+#[cfg(feature="std")]
+macro_rules! float_trait_impl {
+    ($ty:ident) => {
+        impl Float for $ty {
+            #[inline(always)]
+            fn from_f64(x: f64) -> Self { x as $ty }
+            #[inline(always)]
+            fn zero() -> Self { 0.0 }
+            #[inline(always)]
+            fn one() -> Self { 1.0 }
+            #[inline(always)]
+            fn pi() -> Self { ::core::$ty::consts::PI }
+            #[inline(always)]
+            fn epsilon() -> Self { ::core::$ty::EPSILON }
+
+            #[inline(always)]
+            fn is_finite(self) -> bool { <$ty>::is_finite(self) }
+            #[inline(always)]
+            fn abs(self) -> Self { <$ty>::abs(self) }
+            #[inline(always)]
+            fn floor(self) -> Self { <$ty>::floor(self) }
+            #[inline(always)]
+            fn sqrt(self) -> Self { <$ty>::sqrt(self) }
+            #[inline(always)]
+            fn exp(self) -> Self { <$ty>::exp(self) }
+            #[inline(always)]
+            fn exp_m1(self) -> Self { <$ty>::exp_m1(self) }
+            #[inline(always)]
+            fn ln(self) -> Self { <$ty>::ln(self) }
+            #[inline(always)]
+            fn ln_1p(self) -> Self { <$ty>::ln_1p(self) }
+            #[inline(always)]
+            fn powf(self, n: Self) -> Self { <$ty>::powf(self, n) }
+            #[inline(always)]
+            fn tan(self) -> Self { <$ty>::tan(self) }
+            #[inline(always)]
+            fn cos(self) -> Self { <$ty>::cos(self) }
+            #[inline(always)]
+            fn acos(self) -> Self { <$ty>::acos(self) }
+        }
+    }
+}
+
+#[cfg(feature="std")] float_trait_impl! { f32 }
+#[cfg(feature="std")] float_trait_impl! { f64 }
+
+
 pub(crate) trait IntoFloat {
     type F;
 
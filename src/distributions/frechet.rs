@@ -0,0 +1,146 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Fréchet distribution.
+
+use Rng;
+use distributions::{Distribution, Float, Open01};
+use std::{error, fmt};
+
+/// The Fréchet distribution `Frechet(location, scale, shape)`, the type II
+/// extreme value distribution.
+///
+/// It models the distribution of the maximum of many samples from
+/// heavy-tailed distributions. It has cumulative distribution function
+/// `F(x) = exp(-((x - location) / scale)^(-shape))` for `x > location`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Frechet};
+///
+/// let frechet = Frechet::new(0.0, 1.0, 3.0).unwrap();
+/// let v = frechet.sample(&mut rand::thread_rng());
+/// println!("{} is from a Frechet(0, 1, 3) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Frechet<F> {
+    location: F,
+    scale: F,
+    neg_inv_shape: F,
+}
+
+/// Error type returned from `Frechet::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrechetError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+    /// `shape <= 0` or `nan`.
+    ShapeNotPositive,
+}
+
+impl FrechetError {
+    fn msg(&self) -> &str {
+        match *self {
+            FrechetError::ScaleNotPositive => "scale is not positive in Fréchet distribution",
+            FrechetError::ShapeNotPositive => "shape is not positive in Fréchet distribution",
+        }
+    }
+}
+
+impl fmt::Display for FrechetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for FrechetError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Frechet<F> {
+    /// Construct a new `Frechet` with the given `location` (the minimum),
+    /// `scale` and `shape`.
+    ///
+    /// Returns an error unless `scale` and `shape` are both positive.
+    pub fn new(location: F, scale: F, shape: F) -> Result<Frechet<F>, FrechetError> {
+        if !(scale > F::zero()) {
+            return Err(FrechetError::ScaleNotPositive);
+        }
+        if !(shape > F::zero()) {
+            return Err(FrechetError::ShapeNotPositive);
+        }
+        Ok(Frechet { location, scale, neg_inv_shape: -F::one() / shape })
+    }
+}
+
+impl<F: Float> Distribution<F> for Frechet<F> where Open01: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Open01);
+        self.location + self.scale * (-u.ln()).powf(self.neg_inv_shape)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{Frechet, FrechetError};
+
+    #[test]
+    fn test_frechet_median() {
+        let (location, scale, shape) = (1.0, 2.0, 3.0);
+        let d = Frechet::new(location, scale, shape).unwrap();
+        let mut rng = ::test::rng(807);
+        let mut numbers = [0.0f64; 1000];
+        for x in numbers.iter_mut() {
+            *x = d.sample(&mut rng);
+            assert!(*x > location);
+        }
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let expected_median = location + scale * ::std::f64::consts::LN_2.powf(-1.0 / shape);
+        assert!((numbers[500] - expected_median).abs() < 0.1, "median {}", numbers[500]);
+    }
+
+    #[test]
+    fn test_frechet_mean() {
+        // For shape > 1, the mean is `location + scale * Γ(1 - 1/shape)`,
+        // and for shape > 2 the variance is
+        // `scale^2 * (Γ(1 - 2/shape) - Γ(1 - 1/shape)^2)`.
+        const GAMMA_1_3: f64 = 2.6789385347077476;
+        const GAMMA_2_3: f64 = 1.3541179394264005;
+        let (location, scale) = (-1.0, 1.5);
+        let d = Frechet::new(location, scale, 3.0).unwrap();
+        let mut rng = ::test::rng(808);
+        let n = 10000;
+        let mean = (0..n).map(|_| d.sample(&mut rng)).sum::<f64>() / n as f64;
+        let expected_mean = location + scale * GAMMA_2_3;
+        let expected_variance = scale * scale * (GAMMA_1_3 - GAMMA_2_3 * GAMMA_2_3);
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_variance / n as f64).sqrt(),
+                "mean {}", mean);
+    }
+
+    #[test]
+    fn test_frechet_f32() {
+        let d = Frechet::new(0.0f32, 1.0, 5.0).unwrap();
+        let mut rng = ::test::rng(809);
+        for _ in 0..1000 {
+            let x = d.sample(&mut rng);
+            assert!(x > 0.0 && x.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_frechet_invalid() {
+        assert_eq!(Frechet::new(0.0, 0.0, 1.0).unwrap_err(), FrechetError::ScaleNotPositive);
+        assert_eq!(Frechet::new(0.0, 1.0, -1.0).unwrap_err(), FrechetError::ShapeNotPositive);
+        assert_eq!(Frechet::new(0.0, 1.0, ::std::f64::NAN).unwrap_err(),
+                   FrechetError::ShapeNotPositive);
+    }
+}
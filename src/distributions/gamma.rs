@@ -14,7 +14,8 @@ use self::ChiSquaredRepr::*;
 
 use Rng;
 use distributions::normal::StandardNormal;
-use distributions::{Distribution, Exp, Open01};
+use distributions::{Distribution, Exp, Exp1, Float, Open01};
+use std::{error, fmt};
 
 /// The Gamma distribution `Gamma(shape, scale)` distribution.
 ///
@@ -37,7 +38,7 @@ use distributions::{Distribution, Exp, Open01};
 /// ```
 /// use rand::distributions::{Distribution, Gamma};
 ///
-/// let gamma = Gamma::new(2.0, 5.0);
+/// let gamma = Gamma::new(2.0, 5.0).unwrap();
 /// let v = gamma.sample(&mut rand::thread_rng());
 /// println!("{} is from a Gamma(2, 5) distribution", v);
 /// ```
@@ -47,15 +48,15 @@ use distributions::{Distribution, Exp, Open01};
 ///       (September 2000), 363-372.
 ///       DOI:[10.1145/358407.358414](https://doi.acm.org/10.1145/358407.358414)
 #[derive(Clone, Copy, Debug)]
-pub struct Gamma {
-    repr: GammaRepr,
+pub struct Gamma<F> {
+    repr: GammaRepr<F>,
 }
 
 #[derive(Clone, Copy, Debug)]
-enum GammaRepr {
-    Large(GammaLargeShape),
-    One(Exp),
-    Small(GammaSmallShape)
+enum GammaRepr<F> {
+    Large(GammaLargeShape<F>),
+    One(Exp<F>),
+    Small(GammaSmallShape<F>)
 }
 
 // These two helpers could be made public, but saving the
@@ -73,9 +74,9 @@ enum GammaRepr {
 /// See `Gamma` for sampling from a Gamma distribution with general
 /// shape parameters.
 #[derive(Clone, Copy, Debug)]
-struct GammaSmallShape {
-    inv_shape: f64,
-    large_shape: GammaLargeShape
+struct GammaSmallShape<F> {
+    inv_shape: F,
+    large_shape: GammaLargeShape<F>
 }
 
 /// Gamma distribution where the shape parameter is larger than 1.
@@ -83,55 +84,96 @@ struct GammaSmallShape {
 /// See `Gamma` for sampling from a Gamma distribution with general
 /// shape parameters.
 #[derive(Clone, Copy, Debug)]
-struct GammaLargeShape {
-    scale: f64,
-    c: f64,
-    d: f64
+struct GammaLargeShape<F> {
+    scale: F,
+    c: F,
+    d: F
 }
 
-impl Gamma {
+/// Error type returned from `Gamma::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GammaError {
+    /// `shape <= 0` or `nan`.
+    ShapeNotPositive,
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+    /// `scale` is infinite.
+    ScaleTooLarge,
+}
+
+impl GammaError {
+    fn msg(&self) -> &str {
+        match *self {
+            GammaError::ShapeNotPositive => "shape is not positive in gamma distribution",
+            GammaError::ScaleNotPositive => "scale is not positive in gamma distribution",
+            GammaError::ScaleTooLarge => "scale is infinity in gamma distribution",
+        }
+    }
+}
+
+impl fmt::Display for GammaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for GammaError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Gamma<F> {
     /// Construct an object representing the `Gamma(shape, scale)`
     /// distribution.
     ///
-    /// Panics if `shape <= 0` or `scale <= 0`.
+    /// Returns an error if `shape <= 0`, `scale <= 0` or `scale` is infinite.
     #[inline]
-    pub fn new(shape: f64, scale: f64) -> Gamma {
-        assert!(shape > 0.0, "Gamma::new called with shape <= 0");
-        assert!(scale > 0.0, "Gamma::new called with scale <= 0");
+    pub fn new(shape: F, scale: F) -> Result<Gamma<F>, GammaError> {
+        if !(shape > F::zero()) {
+            return Err(GammaError::ShapeNotPositive);
+        } else if !(scale > F::zero()) {
+            return Err(GammaError::ScaleNotPositive);
+        } else if !scale.is_finite() {
+            return Err(GammaError::ScaleTooLarge);
+        }
 
-        let repr = if shape == 1.0 {
-            One(Exp::new(1.0 / scale))
-        } else if shape < 1.0 {
+        let repr = if shape == F::one() {
+            One(Exp::new(F::one() / scale).map_err(|_| GammaError::ScaleTooLarge)?)
+        } else if shape < F::one() {
             Small(GammaSmallShape::new_raw(shape, scale))
         } else {
             Large(GammaLargeShape::new_raw(shape, scale))
         };
-        Gamma { repr }
+        Ok(Gamma { repr })
     }
 }
 
-impl GammaSmallShape {
-    fn new_raw(shape: f64, scale: f64) -> GammaSmallShape {
+impl<F: Float> GammaSmallShape<F> {
+    fn new_raw(shape: F, scale: F) -> GammaSmallShape<F> {
         GammaSmallShape {
-            inv_shape: 1. / shape,
-            large_shape: GammaLargeShape::new_raw(shape + 1.0, scale)
+            inv_shape: F::one() / shape,
+            large_shape: GammaLargeShape::new_raw(shape + F::one(), scale)
         }
     }
 }
 
-impl GammaLargeShape {
-    fn new_raw(shape: f64, scale: f64) -> GammaLargeShape {
-        let d = shape - 1. / 3.;
+impl<F: Float> GammaLargeShape<F> {
+    fn new_raw(shape: F, scale: F) -> GammaLargeShape<F> {
+        let d = shape - F::from_f64(1. / 3.);
         GammaLargeShape {
             scale,
-            c: 1. / (9. * d).sqrt(),
+            c: F::one() / (F::from_f64(9.) * d).sqrt(),
             d
         }
     }
 }
 
-impl Distribution<f64> for Gamma {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for Gamma<F>
+    where StandardNormal: Distribution<F>, Exp1: Distribution<F>,
+          Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
         match self.repr {
             Small(ref g) => g.sample(rng),
             One(ref g) => g.sample(rng),
@@ -139,28 +181,32 @@ impl Distribution<f64> for Gamma {
         }
     }
 }
-impl Distribution<f64> for GammaSmallShape {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let u: f64 = rng.sample(Open01);
+impl<F: Float> Distribution<F> for GammaSmallShape<F>
+    where StandardNormal: Distribution<F>, Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Open01);
 
         self.large_shape.sample(rng) * u.powf(self.inv_shape)
     }
 }
-impl Distribution<f64> for GammaLargeShape {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for GammaLargeShape<F>
+    where StandardNormal: Distribution<F>, Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
         loop {
-            let x = rng.sample(StandardNormal);
-            let v_cbrt = 1.0 + self.c * x;
-            if v_cbrt <= 0.0 { // a^3 <= 0 iff a <= 0
+            let x: F = rng.sample(StandardNormal);
+            let v_cbrt = F::one() + self.c * x;
+            if v_cbrt <= F::zero() { // a^3 <= 0 iff a <= 0
                 continue
             }
 
             let v = v_cbrt * v_cbrt * v_cbrt;
-            let u: f64 = rng.sample(Open01);
+            let u: F = rng.sample(Open01);
 
             let x_sqr = x * x;
-            if u < 1.0 - 0.0331 * x_sqr * x_sqr ||
-                u.ln() < 0.5 * x_sqr + self.d * (1.0 - v + v.ln()) {
+            if u < F::one() - F::from_f64(0.0331) * x_sqr * x_sqr ||
+                u.ln() < F::from_f64(0.5) * x_sqr + self.d * (F::one() - v + v.ln()) {
                 return self.d * v * self.scale
             }
         }
@@ -180,43 +226,77 @@ impl Distribution<f64> for GammaLargeShape {
 /// ```
 /// use rand::distributions::{ChiSquared, Distribution};
 ///
-/// let chi = ChiSquared::new(11.0);
+/// let chi = ChiSquared::new(11.0).unwrap();
 /// let v = chi.sample(&mut rand::thread_rng());
 /// println!("{} is from a χ²(11) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct ChiSquared {
-    repr: ChiSquaredRepr,
+pub struct ChiSquared<F> {
+    repr: ChiSquaredRepr<F>,
 }
 
 #[derive(Clone, Copy, Debug)]
-enum ChiSquaredRepr {
+enum ChiSquaredRepr<F> {
     // k == 1, Gamma(alpha, ..) is particularly slow for alpha < 1,
     // e.g. when alpha = 1/2 as it would be for this case, so special-
     // casing and using the definition of N(0,1)^2 is faster.
     DoFExactlyOne,
-    DoFAnythingElse(Gamma),
+    DoFAnythingElse(Gamma<F>),
 }
 
-impl ChiSquared {
+/// Error type returned from `ChiSquared::new` and `StudentT::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChiSquaredError {
+    /// `k <= 0` or `nan`.
+    DoFNotPositive,
+}
+
+impl ChiSquaredError {
+    fn msg(&self) -> &str {
+        match *self {
+            ChiSquaredError::DoFNotPositive => "degrees-of-freedom k is not positive in chi-squared distribution",
+        }
+    }
+}
+
+impl fmt::Display for ChiSquaredError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for ChiSquaredError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> ChiSquared<F> {
     /// Create a new chi-squared distribution with degrees-of-freedom
-    /// `k`. Panics if `k < 0`.
-    pub fn new(k: f64) -> ChiSquared {
-        let repr = if k == 1.0 {
+    /// `k`. Returns an error if `k <= 0`.
+    pub fn new(k: F) -> Result<ChiSquared<F>, ChiSquaredError> {
+        let repr = if k == F::one() {
             DoFExactlyOne
         } else {
-            assert!(k > 0.0, "ChiSquared::new called with `k` < 0");
-            DoFAnythingElse(Gamma::new(0.5 * k, 2.0))
+            if !(k > F::zero()) {
+                return Err(ChiSquaredError::DoFNotPositive);
+            }
+            let half = F::from_f64(0.5);
+            DoFAnythingElse(Gamma::new(half * k, F::from_f64(2.0))
+                .map_err(|_| ChiSquaredError::DoFNotPositive)?)
         };
-        ChiSquared { repr }
+        Ok(ChiSquared { repr })
     }
 }
-impl Distribution<f64> for ChiSquared {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for ChiSquared<F>
+    where StandardNormal: Distribution<F>, Exp1: Distribution<F>,
+          Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
         match self.repr {
             DoFExactlyOne => {
                 // k == 1 => N(0,1)^2
-                let norm = rng.sample(StandardNormal);
+                let norm: F = rng.sample(StandardNormal);
                 norm * norm
             }
             DoFAnythingElse(ref g) => g.sample(rng)
@@ -235,35 +315,65 @@ impl Distribution<f64> for ChiSquared {
 /// ```
 /// use rand::distributions::{FisherF, Distribution};
 ///
-/// let f = FisherF::new(2.0, 32.0);
+/// let f = FisherF::new(2.0, 32.0).unwrap();
 /// let v = f.sample(&mut rand::thread_rng());
 /// println!("{} is from an F(2, 32) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct FisherF {
-    numer: ChiSquared,
-    denom: ChiSquared,
+pub struct FisherF<F> {
+    numer: ChiSquared<F>,
+    denom: ChiSquared<F>,
     // denom_dof / numer_dof so that this can just be a straight
     // multiplication, rather than a division.
-    dof_ratio: f64,
+    dof_ratio: F,
+}
+
+/// Error type returned from `FisherF::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FisherFError {
+    /// `m <= 0` or `nan`.
+    MNotPositive,
+    /// `n <= 0` or `nan`.
+    NNotPositive,
+}
+
+impl FisherFError {
+    fn msg(&self) -> &str {
+        match *self {
+            FisherFError::MNotPositive => "m is not positive in Fisher F distribution",
+            FisherFError::NNotPositive => "n is not positive in Fisher F distribution",
+        }
+    }
+}
+
+impl fmt::Display for FisherFError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for FisherFError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
 }
 
-impl FisherF {
+impl<F: Float> FisherF<F> {
     /// Create a new `FisherF` distribution, with the given
-    /// parameter. Panics if either `m` or `n` are not positive.
-    pub fn new(m: f64, n: f64) -> FisherF {
-        assert!(m > 0.0, "FisherF::new called with `m < 0`");
-        assert!(n > 0.0, "FisherF::new called with `n < 0`");
-
-        FisherF {
-            numer: ChiSquared::new(m),
-            denom: ChiSquared::new(n),
+    /// parameter. Returns an error if either `m` or `n` are not positive.
+    pub fn new(m: F, n: F) -> Result<FisherF<F>, FisherFError> {
+        Ok(FisherF {
+            numer: ChiSquared::new(m).map_err(|_| FisherFError::MNotPositive)?,
+            denom: ChiSquared::new(n).map_err(|_| FisherFError::NNotPositive)?,
             dof_ratio: n / m
-        }
+        })
     }
 }
-impl Distribution<f64> for FisherF {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for FisherF<F>
+    where StandardNormal: Distribution<F>, Exp1: Distribution<F>,
+          Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
         self.numer.sample(rng) / self.denom.sample(rng) * self.dof_ratio
     }
 }
@@ -276,30 +386,32 @@ impl Distribution<f64> for FisherF {
 /// ```
 /// use rand::distributions::{StudentT, Distribution};
 ///
-/// let t = StudentT::new(11.0);
+/// let t = StudentT::new(11.0).unwrap();
 /// let v = t.sample(&mut rand::thread_rng());
 /// println!("{} is from a t(11) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct StudentT {
-    chi: ChiSquared,
-    dof: f64
+pub struct StudentT<F> {
+    chi: ChiSquared<F>,
+    dof: F
 }
 
-impl StudentT {
+impl<F: Float> StudentT<F> {
     /// Create a new Student t distribution with `n` degrees of
-    /// freedom. Panics if `n <= 0`.
-    pub fn new(n: f64) -> StudentT {
-        assert!(n > 0.0, "StudentT::new called with `n <= 0`");
-        StudentT {
-            chi: ChiSquared::new(n),
+    /// freedom. Returns an error if `n <= 0`.
+    pub fn new(n: F) -> Result<StudentT<F>, ChiSquaredError> {
+        Ok(StudentT {
+            chi: ChiSquared::new(n)?,
             dof: n
-        }
+        })
     }
 }
-impl Distribution<f64> for StudentT {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let norm = rng.sample(StandardNormal);
+impl<F: Float> Distribution<F> for StudentT<F>
+    where StandardNormal: Distribution<F>, Exp1: Distribution<F>,
+          Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let norm: F = rng.sample(StandardNormal);
         norm * (self.dof / self.chi.sample(rng)).sqrt()
     }
 }
@@ -311,32 +423,64 @@ impl Distribution<f64> for StudentT {
 /// ```
 /// use rand::distributions::{Distribution, Beta};
 ///
-/// let beta = Beta::new(2.0, 5.0);
+/// let beta = Beta::new(2.0, 5.0).unwrap();
 /// let v = beta.sample(&mut rand::thread_rng());
 /// println!("{} is from a Beta(2, 5) distribution", v);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Beta {
-    gamma_a: Gamma,
-    gamma_b: Gamma,
+pub struct Beta<F> {
+    gamma_a: Gamma<F>,
+    gamma_b: Gamma<F>,
+}
+
+/// Error type returned from `Beta::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BetaError {
+    /// `alpha <= 0` or `nan`.
+    AlphaNotPositive,
+    /// `beta <= 0` or `nan`.
+    BetaNotPositive,
 }
 
-impl Beta {
+impl BetaError {
+    fn msg(&self) -> &str {
+        match *self {
+            BetaError::AlphaNotPositive => "alpha is not positive in beta distribution",
+            BetaError::BetaNotPositive => "beta is not positive in beta distribution",
+        }
+    }
+}
+
+impl fmt::Display for BetaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for BetaError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Beta<F> {
     /// Construct an object representing the `Beta(alpha, beta)`
     /// distribution.
     ///
-    /// Panics if `shape <= 0` or `scale <= 0`.
-    pub fn new(alpha: f64, beta: f64) -> Beta {
-        assert!((alpha > 0.) & (beta > 0.));
-        Beta {
-            gamma_a: Gamma::new(alpha, 1.),
-            gamma_b: Gamma::new(beta, 1.),
-        }
+    /// Returns an error if `alpha <= 0` or `beta <= 0`.
+    pub fn new(alpha: F, beta: F) -> Result<Beta<F>, BetaError> {
+        Ok(Beta {
+            gamma_a: Gamma::new(alpha, F::one()).map_err(|_| BetaError::AlphaNotPositive)?,
+            gamma_b: Gamma::new(beta, F::one()).map_err(|_| BetaError::BetaNotPositive)?,
+        })
     }
 }
 
-impl Distribution<f64> for Beta {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for Beta<F>
+    where StandardNormal: Distribution<F>, Exp1: Distribution<F>,
+          Open01: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
         let x = self.gamma_a.sample(rng);
         let y = self.gamma_b.sample(rng);
         x / (x + y)
@@ -346,11 +490,12 @@ impl Distribution<f64> for Beta {
 #[cfg(test)]
 mod test {
     use distributions::Distribution;
-    use super::{Beta, ChiSquared, StudentT, FisherF};
+    use super::{Beta, ChiSquared, StudentT, FisherF, Gamma};
+    use super::{BetaError, ChiSquaredError, FisherFError, GammaError};
 
     #[test]
     fn test_chi_squared_one() {
-        let chi = ChiSquared::new(1.0);
+        let chi = ChiSquared::new(1.0).unwrap();
         let mut rng = ::test::rng(201);
         for _ in 0..1000 {
             chi.sample(&mut rng);
@@ -358,7 +503,7 @@ mod test {
     }
     #[test]
     fn test_chi_squared_small() {
-        let chi = ChiSquared::new(0.5);
+        let chi = ChiSquared::new(0.5).unwrap();
         let mut rng = ::test::rng(202);
         for _ in 0..1000 {
             chi.sample(&mut rng);
@@ -366,21 +511,41 @@ mod test {
     }
     #[test]
     fn test_chi_squared_large() {
-        let chi = ChiSquared::new(30.0);
+        let chi = ChiSquared::new(30.0).unwrap();
         let mut rng = ::test::rng(203);
         for _ in 0..1000 {
             chi.sample(&mut rng);
         }
     }
     #[test]
-    #[should_panic]
     fn test_chi_squared_invalid_dof() {
-        ChiSquared::new(-1.0);
+        assert_eq!(ChiSquared::new(-1.0).unwrap_err(), ChiSquaredError::DoFNotPositive);
+        assert_eq!(StudentT::new(0.0).unwrap_err(), ChiSquaredError::DoFNotPositive);
+    }
+
+    #[test]
+    fn test_gamma_invalid_params() {
+        assert_eq!(Gamma::new(0.0, 1.0).unwrap_err(), GammaError::ShapeNotPositive);
+        assert_eq!(Gamma::new(1.0, -1.0).unwrap_err(), GammaError::ScaleNotPositive);
+        assert_eq!(Gamma::new(1.0, ::std::f64::INFINITY).unwrap_err(),
+                   GammaError::ScaleTooLarge);
+        assert_eq!(Gamma::new(0.5, ::std::f64::INFINITY).unwrap_err(),
+                   GammaError::ScaleTooLarge);
+        assert_eq!(Gamma::new(2.0, ::std::f64::INFINITY).unwrap_err(),
+                   GammaError::ScaleTooLarge);
+        assert_eq!(Gamma::new(2.0, ::std::f32::INFINITY).unwrap_err(),
+                   GammaError::ScaleTooLarge);
+    }
+
+    #[test]
+    fn test_f_invalid_dof() {
+        assert_eq!(FisherF::new(0.0, 1.0).unwrap_err(), FisherFError::MNotPositive);
+        assert_eq!(FisherF::new(1.0, -2.0).unwrap_err(), FisherFError::NNotPositive);
     }
 
     #[test]
     fn test_f() {
-        let f = FisherF::new(2.0, 32.0);
+        let f = FisherF::new(2.0, 32.0).unwrap();
         let mut rng = ::test::rng(204);
         for _ in 0..1000 {
             f.sample(&mut rng);
@@ -389,16 +554,37 @@ mod test {
 
     #[test]
     fn test_t() {
-        let t = StudentT::new(11.0);
+        let t = StudentT::new(11.0).unwrap();
         let mut rng = ::test::rng(205);
         for _ in 0..1000 {
             t.sample(&mut rng);
         }
     }
 
+    #[test]
+    fn test_gamma_f32() {
+        let mut rng = ::test::rng(206);
+        for &shape in &[0.5f32, 1.0, 3.0] {
+            let gamma = Gamma::new(shape, 2.0).unwrap();
+            let mut sum = 0.0f32;
+            for _ in 0..1000 {
+                let x: f32 = gamma.sample(&mut rng);
+                assert!(x >= 0.0);
+                sum += x;
+            }
+            // The mean of Gamma(k, θ) is k * θ.
+            assert!((sum / 1000.0 - shape * 2.0).abs() < 0.5);
+        }
+        let beta = Beta::new(1.0f32, 2.0).unwrap();
+        for _ in 0..1000 {
+            let x: f32 = beta.sample(&mut rng);
+            assert!(x >= 0.0 && x <= 1.0);
+        }
+    }
+
     #[test]
     fn test_beta() {
-        let beta = Beta::new(1.0, 2.0);
+        let beta = Beta::new(1.0, 2.0).unwrap();
         let mut rng = ::test::rng(201);
         for _ in 0..1000 {
             beta.sample(&mut rng);
@@ -406,8 +592,8 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
     fn test_beta_invalid_dof() {
-        Beta::new(0., 0.);
+        assert_eq!(Beta::new(0., 0.).unwrap_err(), BetaError::AlphaNotPositive);
+        assert_eq!(Beta::new(1., 0.).unwrap_err(), BetaError::BetaNotPositive);
     }
 }
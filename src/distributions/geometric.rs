@@ -0,0 +1,153 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The geometric distribution.
+
+use Rng;
+use distributions::{Distribution, OpenClosed01};
+use std::{error, fmt};
+
+/// The geometric distribution `Geometric(p)`.
+///
+/// This is the distribution of the number of failures before the first
+/// success in a sequence of independent [`Bernoulli`] trials, each with
+/// probability of success `p`.
+///
+/// This distribution has density function:
+/// `f(k) = (1 - p)^k p` for `k >= 0`.
+///
+/// Samples are generated by inversion, using a single random number and
+/// taking `O(1)` time regardless of `p`. Results which do not fit in a `u64`
+/// (possible only for tiny `p`) saturate to `u64::MAX`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Geometric, Distribution};
+///
+/// let geo = Geometric::new(0.25).unwrap();
+/// let v = geo.sample(&mut rand::thread_rng());
+/// println!("{} failures before the first success", v);
+/// ```
+///
+/// [`Bernoulli`]: crate::distributions::Bernoulli
+#[derive(Clone, Copy, Debug)]
+pub struct Geometric {
+    /// `ln(1 - p)`
+    ln_1_p: f64,
+}
+
+/// Error type returned from `Geometric::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeometricError {
+    /// `p <= 0`, `p > 1` or `nan`.
+    InvalidProbability,
+}
+
+impl GeometricError {
+    fn msg(&self) -> &str {
+        match *self {
+            GeometricError::InvalidProbability =>
+                "p is not in (0, 1] in geometric distribution",
+        }
+    }
+}
+
+impl fmt::Display for GeometricError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for GeometricError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl Geometric {
+    /// Construct a new `Geometric` with the given probability of success `p`.
+    ///
+    /// Returns an error if `p <= 0` or `p > 1`.
+    pub fn new(p: f64) -> Result<Geometric, GeometricError> {
+        if !(p > 0.0 && p <= 1.0) {
+            return Err(GeometricError::InvalidProbability);
+        }
+        // `ln_1p` keeps precision for small `p`
+        Ok(Geometric { ln_1_p: (-p).ln_1p() })
+    }
+}
+
+impl Distribution<u64> for Geometric {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        if self.ln_1_p == ::std::f64::NEG_INFINITY {
+            // p == 1
+            return 0;
+        }
+        // `P(X >= k) = (1 - p)^k`, so `X = floor(ln(u) / ln(1 - p))`.
+        let u: f64 = rng.sample(OpenClosed01);
+        let x = (u.ln() / self.ln_1_p).floor();
+        if x >= ::std::u64::MAX as f64 {
+            ::std::u64::MAX
+        } else {
+            x as u64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::Distribution;
+    use super::{Geometric, GeometricError};
+
+    fn test_geometric_mean_and_variance<R: Rng>(p: f64, rng: &mut R) {
+        let distr = Geometric::new(p).unwrap();
+
+        let expected_mean = (1.0 - p) / p;
+        let expected_variance = (1.0 - p) / (p * p);
+
+        let mut results = [0.0; 10000];
+        for i in results.iter_mut() { *i = distr.sample(rng) as f64; }
+
+        let mean = results.iter().sum::<f64>() / results.len() as f64;
+        let std_error = (expected_variance / results.len() as f64).sqrt();
+        assert!((mean - expected_mean).abs() < 4.0 * std_error);
+
+        let variance =
+            results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+            / results.len() as f64;
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0);
+    }
+
+    #[test]
+    fn test_geometric() {
+        let mut rng = ::test::rng(12345);
+        test_geometric_mean_and_variance(0.9, &mut rng);
+        test_geometric_mean_and_variance(0.5, &mut rng);
+        test_geometric_mean_and_variance(0.1, &mut rng);
+        test_geometric_mean_and_variance(0.001, &mut rng);
+    }
+
+    #[test]
+    fn test_geometric_edge_cases() {
+        let mut rng = ::test::rng(12346);
+        assert_eq!(rng.sample(Geometric::new(1.0).unwrap()), 0);
+        // Very small `p` saturates rather than overflowing
+        let distr = Geometric::new(1e-300).unwrap();
+        for _ in 0..10 {
+            assert!(rng.sample(distr) > 1 << 60);
+        }
+
+        assert_eq!(Geometric::new(0.0).unwrap_err(), GeometricError::InvalidProbability);
+        assert_eq!(Geometric::new(-0.5).unwrap_err(), GeometricError::InvalidProbability);
+        assert_eq!(Geometric::new(1.5).unwrap_err(), GeometricError::InvalidProbability);
+        assert_eq!(Geometric::new(::std::f64::NAN).unwrap_err(),
+                   GeometricError::InvalidProbability);
+    }
+}
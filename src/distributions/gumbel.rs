@@ -0,0 +1,126 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Gumbel distribution.
+
+use Rng;
+use distributions::{Distribution, Float, Open01};
+use std::{error, fmt};
+
+/// The Gumbel distribution `Gumbel(location, scale)`, the type I extreme
+/// value distribution.
+///
+/// It models the distribution of the maximum of many samples from
+/// distributions with exponential tails. It has cumulative distribution
+/// function `F(x) = exp(-exp(-(x - location) / scale))`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Gumbel};
+///
+/// let gumbel = Gumbel::new(0.0, 1.0).unwrap();
+/// let v = gumbel.sample(&mut rand::thread_rng());
+/// println!("{} is from a Gumbel(0, 1) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Gumbel<F> {
+    location: F,
+    scale: F,
+}
+
+/// Error type returned from `Gumbel::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GumbelError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+}
+
+impl GumbelError {
+    fn msg(&self) -> &str {
+        match *self {
+            GumbelError::ScaleNotPositive => "scale is not positive in Gumbel distribution",
+        }
+    }
+}
+
+impl fmt::Display for GumbelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for GumbelError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Gumbel<F> {
+    /// Construct a new `Gumbel` with the given `location` (the mode) and
+    /// `scale`.
+    ///
+    /// Returns an error if `scale <= 0`.
+    pub fn new(location: F, scale: F) -> Result<Gumbel<F>, GumbelError> {
+        if !(scale > F::zero()) {
+            return Err(GumbelError::ScaleNotPositive);
+        }
+        Ok(Gumbel { location, scale })
+    }
+}
+
+impl<F: Float> Distribution<F> for Gumbel<F> where Open01: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Open01);
+        self.location - self.scale * (-u.ln()).ln()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{Gumbel, GumbelError};
+
+    #[test]
+    fn test_gumbel_mean_and_variance() {
+        const EULER_GAMMA: f64 = 0.5772156649015329;
+        let (location, scale) = (5.0, 2.0);
+        let d = Gumbel::new(location, scale).unwrap();
+        let mut rng = ::test::rng(803);
+        let mut results = [0.0f64; 10000];
+        for x in results.iter_mut() {
+            *x = d.sample(&mut rng);
+        }
+        let n = results.len() as f64;
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        let expected_mean = location + EULER_GAMMA * scale;
+        let expected_variance = ::std::f64::consts::PI.powi(2) / 6.0 * scale * scale;
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_variance / n).sqrt(),
+                "mean {}", mean);
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0,
+                "variance {}", variance);
+    }
+
+    #[test]
+    fn test_gumbel_f32() {
+        let d = Gumbel::new(0.0f32, 1.0).unwrap();
+        let mut rng = ::test::rng(804);
+        for _ in 0..1000 {
+            assert!(d.sample(&mut rng).is_finite());
+        }
+    }
+
+    #[test]
+    fn test_gumbel_invalid() {
+        assert_eq!(Gumbel::new(0.0, 0.0).unwrap_err(), GumbelError::ScaleNotPositive);
+        assert_eq!(Gumbel::new(0.0, -1.0).unwrap_err(), GumbelError::ScaleNotPositive);
+        assert_eq!(Gumbel::new(0.0, ::std::f64::NAN).unwrap_err(),
+                   GumbelError::ScaleNotPositive);
+    }
+}
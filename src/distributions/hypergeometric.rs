@@ -0,0 +1,311 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The hypergeometric distribution.
+
+use Rng;
+use distributions::Distribution;
+use distributions::utils::log_gamma;
+use std::{error, fmt};
+
+/// The hypergeometric distribution `Hypergeometric(N, K, n)`.
+///
+/// This is the distribution of the number of successes in `n` draws *without*
+/// replacement from a population of size `N` containing `K` successes. It is
+/// the counterpart of the [`Binomial`] distribution, which describes draws
+/// with replacement.
+///
+/// This distribution has density function:
+/// `f(k) = binomial(K, k) * binomial(N-K, n-k) / binomial(N, n)`,
+/// for `max(0, n-(N-K)) <= k <= min(n, K)`.
+///
+/// When the smaller of `n` and `N-n` is small, draws are simulated directly.
+/// Otherwise the ratio-of-uniforms rejection method HRUA of Stadlober is
+/// used, whose expected running time is bounded independently of the
+/// parameters.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Hypergeometric};
+///
+/// // Draw 60 items from a lot of 5000, of which 120 are defective
+/// let hypergeo = Hypergeometric::new(5000, 120, 60).unwrap();
+/// let v = hypergeo.sample(&mut rand::thread_rng());
+/// println!("{} defective items were drawn", v);
+/// ```
+///
+/// [`Binomial`]: crate::distributions::Binomial
+#[derive(Clone, Copy, Debug)]
+pub struct Hypergeometric {
+    /// Number of successes in the population.
+    successes: u64,
+    /// Size of the population.
+    total_population: u64,
+    /// `min(draws, total_population - draws)`; if the second is smaller, we
+    /// sample the number of successes which are *not* drawn.
+    draws: u64,
+    complement: bool,
+    /// `min(successes, failures)`; if `failures` is smaller, we sample the
+    /// number of drawn failures.
+    min_group: u64,
+    swapped: bool,
+    method: Method,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Method {
+    /// Simulate each draw.
+    Direct,
+    /// Ratio-of-uniforms rejection with a table mountain hat function.
+    RatioOfUniforms {
+        /// Center of the hat.
+        center: f64,
+        /// Scale of the hat.
+        width: f64,
+        /// `ln f(mode)`, up to the normalizing constant.
+        ln_f_mode: f64,
+        /// Upper bound (exclusive) of sampled values.
+        bound: f64,
+    },
+}
+
+/// Error type returned from `Hypergeometric::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HypergeometricError {
+    /// `successes > total_population`.
+    SuccessesTooLarge,
+    /// `draws > total_population`.
+    DrawsTooLarge,
+}
+
+impl HypergeometricError {
+    fn msg(&self) -> &str {
+        match *self {
+            HypergeometricError::SuccessesTooLarge =>
+                "successes > total_population in hypergeometric distribution",
+            HypergeometricError::DrawsTooLarge =>
+                "draws > total_population in hypergeometric distribution",
+        }
+    }
+}
+
+impl fmt::Display for HypergeometricError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for HypergeometricError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+/// Below this number of (effective) draws, draws are simulated directly.
+const DIRECT_THRESHOLD: u64 = 10;
+
+/// `ln(x!)`.
+fn ln_factorial(x: u64) -> f64 {
+    log_gamma(x as f64 + 1.0)
+}
+
+impl Hypergeometric {
+    /// Construct a new `Hypergeometric` for a population of size
+    /// `total_population` containing `successes` successes, from which
+    /// `draws` items are drawn without replacement.
+    ///
+    /// Returns an error if `successes > total_population` or
+    /// `draws > total_population`.
+    pub fn new(total_population: u64, successes: u64, draws: u64)
+        -> Result<Hypergeometric, HypergeometricError>
+    {
+        if successes > total_population {
+            return Err(HypergeometricError::SuccessesTooLarge);
+        }
+        if draws > total_population {
+            return Err(HypergeometricError::DrawsTooLarge);
+        }
+
+        // The distribution is symmetric in exchanging successes and failures,
+        // and in exchanging drawn and not drawn items. We sample the count of
+        // the smaller group within the smaller number of draws.
+        let failures = total_population - successes;
+        let (min_group, max_group) = if successes > failures {
+            (failures, successes)
+        } else {
+            (successes, failures)
+        };
+        let n = if draws > total_population - draws {
+            total_population - draws
+        } else {
+            draws
+        };
+
+        let method = if n <= DIRECT_THRESHOLD {
+            Method::Direct
+        } else {
+            // Constants of the HRUA algorithm:
+            // D1 = 2 sqrt(2/e), D2 = 3 - 2 sqrt(3/e)
+            const D1: f64 = 1.7155277699214135;
+            const D2: f64 = 0.8989161620588988;
+
+            let pop = total_population as f64;
+            let p = min_group as f64 / pop;
+            let center = n as f64 * p + 0.5;
+            let sigma = ((pop - n as f64) * n as f64 * p * (1.0 - p) / (pop - 1.0)
+                + 0.5).sqrt();
+            let width = D1 * sigma + D2;
+            let mode = ((n + 1) as f64 * (min_group + 1) as f64 / (pop + 2.0))
+                .floor() as u64;
+            let ln_f_mode = ln_factorial(mode) + ln_factorial(min_group - mode)
+                + ln_factorial(n - mode) + ln_factorial(max_group - n + mode);
+            // Values further than 16 standard deviations from the center have
+            // negligible probability.
+            let max_value = if n < min_group { n } else { min_group };
+            let bound = (max_value as f64 + 1.0).min((center + 16.0 * sigma).floor());
+            Method::RatioOfUniforms { center, width, ln_f_mode, bound }
+        };
+
+        Ok(Hypergeometric {
+            successes,
+            total_population,
+            draws: n,
+            complement: n != draws,
+            min_group,
+            swapped: successes > failures,
+            method,
+        })
+    }
+}
+
+impl Distribution<u64> for Hypergeometric {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let n = self.draws;
+        let min_group = self.min_group;
+
+        // The number of items of the smaller group within `n` draws.
+        let x = match self.method {
+            Method::Direct => {
+                let mut remaining = min_group;
+                let mut population = self.total_population;
+                for _ in 0..n {
+                    if remaining == 0 {
+                        break;
+                    }
+                    if rng.gen_range(0, population) < remaining {
+                        remaining -= 1;
+                    }
+                    population -= 1;
+                }
+                min_group - remaining
+            }
+            Method::RatioOfUniforms { center, width, ln_f_mode, bound } => {
+                let max_group = self.total_population - min_group;
+                loop {
+                    let u: f64 = rng.gen();
+                    let v: f64 = rng.gen();
+                    let w = center + width * (v - 0.5) / u;
+                    // This also rejects NaN, for `u == 0`.
+                    if !(w >= 0.0 && w < bound) {
+                        continue;
+                    }
+
+                    let x = w.floor() as u64;
+                    let t = ln_f_mode - (ln_factorial(x) + ln_factorial(min_group - x)
+                        + ln_factorial(n - x) + ln_factorial(max_group - n + x));
+
+                    // fast acceptance
+                    if u * (4.0 - u) - 3.0 <= t {
+                        break x;
+                    }
+                    // fast rejection
+                    if u * (u - t) >= 1.0 {
+                        continue;
+                    }
+                    if 2.0 * u.ln() <= t {
+                        break x;
+                    }
+                }
+            }
+        };
+
+        let x = if self.swapped { n - x } else { x };
+        if self.complement { self.successes - x } else { x }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::Distribution;
+    use super::{Hypergeometric, HypergeometricError};
+
+    fn test_hypergeometric_mean_and_variance<R: Rng>(
+        total_population: u64, successes: u64, draws: u64, rng: &mut R)
+    {
+        let distr = Hypergeometric::new(total_population, successes, draws).unwrap();
+
+        let pop = total_population as f64;
+        let p = successes as f64 / pop;
+        let expected_mean = draws as f64 * p;
+        let expected_variance = draws as f64 * p * (1.0 - p)
+            * (pop - draws as f64) / (pop - 1.0);
+
+        let mut results = [0.0; 10000];
+        for i in results.iter_mut() {
+            let x = distr.sample(rng);
+            assert!(x <= successes && x <= draws);
+            assert!(draws - x <= total_population - successes);
+            *i = x as f64;
+        }
+
+        let mean = results.iter().sum::<f64>() / results.len() as f64;
+        assert!((mean - expected_mean).abs() < expected_mean / 50.0 + 0.01,
+                "mean {} expected {}", mean, expected_mean);
+
+        let variance =
+            results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+            / results.len() as f64;
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0 + 0.01,
+                "variance {} expected {}", variance, expected_variance);
+    }
+
+    #[test]
+    fn test_hypergeometric() {
+        let mut rng = ::test::rng(737);
+        // direct method
+        test_hypergeometric_mean_and_variance(20, 10, 5, &mut rng);
+        test_hypergeometric_mean_and_variance(500, 400, 10, &mut rng);
+        test_hypergeometric_mean_and_variance(500, 100, 495, &mut rng);
+        // ratio-of-uniforms method
+        test_hypergeometric_mean_and_variance(100, 50, 50, &mut rng);
+        test_hypergeometric_mean_and_variance(1000, 30, 200, &mut rng);
+        test_hypergeometric_mean_and_variance(1000, 970, 200, &mut rng);
+        test_hypergeometric_mean_and_variance(5000, 120, 4900, &mut rng);
+        test_hypergeometric_mean_and_variance(1 << 40, 1 << 38, 1 << 20, &mut rng);
+    }
+
+    #[test]
+    fn test_hypergeometric_end_points() {
+        let mut rng = ::test::rng(738);
+        assert_eq!(rng.sample(Hypergeometric::new(100, 7, 0).unwrap()), 0);
+        assert_eq!(rng.sample(Hypergeometric::new(100, 0, 50).unwrap()), 0);
+        assert_eq!(rng.sample(Hypergeometric::new(100, 7, 100).unwrap()), 7);
+        assert_eq!(rng.sample(Hypergeometric::new(100, 100, 50).unwrap()), 50);
+        assert_eq!(rng.sample(Hypergeometric::new(0, 0, 0).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_hypergeometric_invalid() {
+        assert_eq!(Hypergeometric::new(10, 11, 5).unwrap_err(),
+                   HypergeometricError::SuccessesTooLarge);
+        assert_eq!(Hypergeometric::new(10, 5, 11).unwrap_err(),
+                   HypergeometricError::DrawsTooLarge);
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sampling by inversion of a custom distribution function.
+
+use Rng;
+use distributions::{Distribution, Open01};
+
+/// A distribution given by its quantile function, the inverse of its
+/// cumulative distribution function, and sampled by inversion.
+///
+/// A sample is the quantile function applied to a value drawn from
+/// [`Open01`], i.e. uniformly from the open interval `(0, 1)`, so the
+/// quantile function need not be finite at `0` and `1`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, InverseCdf};
+///
+/// // The Kumaraswamy distribution with parameters a = 2, b = 5
+/// let (a, b) = (2.0, 5.0);
+/// let d = InverseCdf::new(|u: f64| (1.0 - (1.0 - u).powf(1.0 / b)).powf(1.0 / a));
+/// let v = d.sample(&mut rand::thread_rng());
+/// assert!(v > 0.0 && v < 1.0);
+/// ```
+///
+/// [`Open01`]: crate::distributions::Open01
+#[derive(Clone, Copy, Debug)]
+pub struct InverseCdf<Q> {
+    quantile: Q,
+}
+
+impl<Q: Fn(f64) -> f64> InverseCdf<Q> {
+    /// Construct a new `InverseCdf` distribution with the given quantile
+    /// function.
+    pub fn new(quantile: Q) -> InverseCdf<Q> {
+        InverseCdf { quantile }
+    }
+}
+
+impl<Q: Fn(f64) -> f64> Distribution<f64> for InverseCdf<Q> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        (self.quantile)(rng.sample(Open01))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::InverseCdf;
+
+    fn exp_quantile(u: f64) -> f64 {
+        -(1.0 - u).ln() / 2.0
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let mut rng = ::test::rng(880);
+        // Exponential distribution with rate 2, from a function pointer
+        let d = InverseCdf::new(exp_quantile as fn(f64) -> f64);
+        let n = 10000;
+        let mean = d.sample_iter(&mut rng).take(n).sum::<f64>() / n as f64;
+        assert!((mean - 0.5).abs() < 4.0 * (0.25 / n as f64).sqrt(), "{}", mean);
+
+        // Uniform distribution on (-1, 1), from a closure
+        let d = InverseCdf::new(|u| 2.0 * u - 1.0);
+        for _ in 0..100 {
+            let x = d.sample(&mut rng);
+            assert!(x > -1.0 && x < 1.0);
+        }
+    }
+}
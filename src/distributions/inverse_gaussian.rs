@@ -0,0 +1,169 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The inverse Gaussian distribution.
+
+use Rng;
+use distributions::{Distribution, Float, Standard, StandardNormal};
+use std::{error, fmt};
+
+/// The inverse Gaussian distribution `IG(mean, shape)`, also known as the
+/// Wald distribution.
+///
+/// This distribution has density function:
+/// `f(x) = sqrt(shape / (2 pi x^3)) * exp(-shape (x - mean)^2 / (2 mean^2 x))`
+/// for `x > 0`. Its variance is `mean^3 / shape`. It describes the first
+/// passage time of a Brownian motion with positive drift.
+///
+/// Samples are generated with the transformation method of Michael,
+/// Schucany and Haas (1976), which uses one normal and one uniform sample.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, InverseGaussian};
+///
+/// let ig = InverseGaussian::new(1.0, 2.0).unwrap();
+/// let v = ig.sample(&mut rand::thread_rng());
+/// println!("{} is from an inverse Gaussian(1, 2) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct InverseGaussian<F> {
+    mean: F,
+    shape: F,
+}
+
+/// Error type returned from `InverseGaussian::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InverseGaussianError {
+    /// `mean <= 0`, infinite or `nan`.
+    MeanNotPositive,
+    /// `shape <= 0`, infinite or `nan`.
+    ShapeNotPositive,
+}
+
+impl InverseGaussianError {
+    fn msg(&self) -> &str {
+        match *self {
+            InverseGaussianError::MeanNotPositive =>
+                "mean is not positive and finite in inverse Gaussian distribution",
+            InverseGaussianError::ShapeNotPositive =>
+                "shape is not positive and finite in inverse Gaussian distribution",
+        }
+    }
+}
+
+impl fmt::Display for InverseGaussianError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for InverseGaussianError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> InverseGaussian<F> {
+    /// Construct a new `InverseGaussian` with the given `mean` and `shape`.
+    ///
+    /// Returns an error unless `mean` and `shape` are both positive and
+    /// finite.
+    pub fn new(mean: F, shape: F) -> Result<InverseGaussian<F>, InverseGaussianError> {
+        if !(mean > F::zero() && mean.is_finite()) {
+            return Err(InverseGaussianError::MeanNotPositive);
+        }
+        if !(shape > F::zero() && shape.is_finite()) {
+            return Err(InverseGaussianError::ShapeNotPositive);
+        }
+        Ok(InverseGaussian { mean, shape })
+    }
+}
+
+impl<F: Float> Distribution<F> for InverseGaussian<F>
+    where StandardNormal: Distribution<F>, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let mu = self.mean;
+        let v: F = rng.sample(StandardNormal);
+        let y = mu * v * v;
+        // The smaller root `mu + mu / (2 shape) * (y - sqrt(y^2 + 4 shape y))`,
+        // rewritten as `4 mu shape / (sqrt(y) + sqrt(y + 4 shape))^2` to
+        // avoid cancellation.
+        let four_shape = F::from_f64(4.0) * self.shape;
+        let s = y.sqrt() + (y + four_shape).sqrt();
+        let x = mu * four_shape / (s * s);
+
+        // Choose between the two roots `x` and `mu^2 / x`.
+        let u: F = rng.gen();
+        if u <= mu / (mu + x) {
+            x
+        } else {
+            mu * mu / x
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::Distribution;
+    use super::{InverseGaussian, InverseGaussianError};
+
+    fn test_inverse_gaussian_mean_and_variance<R: Rng>(mean: f64, shape: f64, rng: &mut R) {
+        let d = InverseGaussian::new(mean, shape).unwrap();
+        let mut results = [0.0f64; 10000];
+        for x in results.iter_mut() {
+            *x = d.sample(rng);
+            assert!(*x > 0.0 && x.is_finite());
+        }
+        let n = results.len() as f64;
+        let sample_mean = results.iter().sum::<f64>() / n;
+        let sample_variance = results.iter()
+            .map(|x| (x - sample_mean) * (x - sample_mean)).sum::<f64>() / n;
+        let expected_variance = mean * mean * mean / shape;
+        assert!((sample_mean - mean).abs() < 4.0 * (expected_variance / n).sqrt(),
+                "mean {} expected {}", sample_mean, mean);
+        // The variance of the sample variance is large for small shapes, as
+        // the distribution is skewed.
+        assert!((sample_variance - expected_variance).abs() < expected_variance / 5.0,
+                "variance {} expected {}", sample_variance, expected_variance);
+    }
+
+    #[test]
+    fn test_inverse_gaussian() {
+        let mut rng = ::test::rng(820);
+        test_inverse_gaussian_mean_and_variance(1.0, 1.0, &mut rng);
+        test_inverse_gaussian_mean_and_variance(3.0, 10.0, &mut rng);
+        test_inverse_gaussian_mean_and_variance(0.5, 50.0, &mut rng);
+        test_inverse_gaussian_mean_and_variance(1e6, 1e8, &mut rng);
+    }
+
+    #[test]
+    fn test_inverse_gaussian_f32() {
+        let d = InverseGaussian::new(2.0f32, 0.5).unwrap();
+        let mut rng = ::test::rng(821);
+        for _ in 0..1000 {
+            let x = d.sample(&mut rng);
+            assert!(x > 0.0 && x.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_inverse_gaussian_invalid() {
+        assert_eq!(InverseGaussian::new(0.0, 1.0).unwrap_err(),
+                   InverseGaussianError::MeanNotPositive);
+        assert_eq!(InverseGaussian::new(::std::f64::INFINITY, 1.0).unwrap_err(),
+                   InverseGaussianError::MeanNotPositive);
+        assert_eq!(InverseGaussian::new(1.0, -1.0).unwrap_err(),
+                   InverseGaussianError::ShapeNotPositive);
+        assert_eq!(InverseGaussian::new(1.0, ::std::f64::NAN).unwrap_err(),
+                   InverseGaussianError::ShapeNotPositive);
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Laplace distribution.
+
+use Rng;
+use distributions::{Distribution, Float, Open01};
+use std::{error, fmt};
+
+/// The Laplace distribution `Laplace(location, scale)`, also known as the
+/// double exponential distribution.
+///
+/// This distribution has density function:
+/// `f(x) = exp(-|x - location| / scale) / (2 * scale)`
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Laplace};
+///
+/// let laplace = Laplace::new(2.0, 3.0).unwrap();
+/// let v = laplace.sample(&mut rand::thread_rng());
+/// println!("{} is from a Laplace(2, 3) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Laplace<F> {
+    location: F,
+    scale: F,
+}
+
+/// Error type returned from `Laplace::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaplaceError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+}
+
+impl LaplaceError {
+    fn msg(&self) -> &str {
+        match *self {
+            LaplaceError::ScaleNotPositive => "scale is not positive in Laplace distribution",
+        }
+    }
+}
+
+impl fmt::Display for LaplaceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for LaplaceError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Laplace<F> {
+    /// Construct a new `Laplace` with the given `location` (the mean and
+    /// median) and `scale`.
+    ///
+    /// Returns an error if `scale <= 0`.
+    pub fn new(location: F, scale: F) -> Result<Laplace<F>, LaplaceError> {
+        if !(scale > F::zero()) {
+            return Err(LaplaceError::ScaleNotPositive);
+        }
+        Ok(Laplace { location, scale })
+    }
+}
+
+impl<F: Float> Distribution<F> for Laplace<F> where Open01: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        // Inversion of the CDF, using each half of (0, 1) for one side.
+        let u: F = rng.sample(Open01);
+        let half = F::from_f64(0.5);
+        if u < half {
+            self.location + self.scale * (u + u).ln()
+        } else {
+            let v = F::one() - u;
+            self.location - self.scale * (v + v).ln()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{Laplace, LaplaceError};
+
+    #[test]
+    fn test_laplace_mean_and_variance() {
+        let (location, scale) = (-3.0, 2.0);
+        let d = Laplace::new(location, scale).unwrap();
+        let mut rng = ::test::rng(801);
+        let mut results = [0.0f64; 10000];
+        for x in results.iter_mut() {
+            *x = d.sample(&mut rng);
+        }
+        let n = results.len() as f64;
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        let expected_variance = 2.0 * scale * scale;
+        assert!((mean - location).abs() < 4.0 * (expected_variance / n).sqrt(),
+                "mean {}", mean);
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0,
+                "variance {}", variance);
+    }
+
+    #[test]
+    fn test_laplace_f32() {
+        let d = Laplace::new(10.0f32, 0.5).unwrap();
+        let mut rng = ::test::rng(802);
+        let mut numbers = [0.0f32; 1000];
+        for x in numbers.iter_mut() {
+            *x = d.sample(&mut rng);
+            assert!(x.is_finite());
+        }
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((numbers[500] - 10.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_laplace_invalid() {
+        assert_eq!(Laplace::new(0.0, 0.0).unwrap_err(), LaplaceError::ScaleNotPositive);
+        assert_eq!(Laplace::new(0.0, -1.0).unwrap_err(), LaplaceError::ScaleNotPositive);
+        assert_eq!(Laplace::new(0.0, ::std::f64::NAN).unwrap_err(),
+                   LaplaceError::ScaleNotPositive);
+    }
+}
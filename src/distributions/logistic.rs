@@ -0,0 +1,127 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The logistic distribution.
+
+use Rng;
+use distributions::{Distribution, Float, Open01};
+use std::{error, fmt};
+
+/// The logistic distribution `Logistic(location, scale)`.
+///
+/// This distribution has cumulative distribution function
+/// `F(x) = 1 / (1 + exp(-(x - location) / scale))`, the logistic function.
+/// It resembles the normal distribution, but has heavier tails.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Logistic};
+///
+/// let logistic = Logistic::new(2.0, 0.5).unwrap();
+/// let v = logistic.sample(&mut rand::thread_rng());
+/// println!("{} is from a Logistic(2, 0.5) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Logistic<F> {
+    location: F,
+    scale: F,
+}
+
+/// Error type returned from `Logistic::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogisticError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+}
+
+impl LogisticError {
+    fn msg(&self) -> &str {
+        match *self {
+            LogisticError::ScaleNotPositive => "scale is not positive in logistic distribution",
+        }
+    }
+}
+
+impl fmt::Display for LogisticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for LogisticError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Logistic<F> {
+    /// Construct a new `Logistic` with the given `location` (the mean and
+    /// median) and `scale`.
+    ///
+    /// Returns an error if `scale <= 0`.
+    pub fn new(location: F, scale: F) -> Result<Logistic<F>, LogisticError> {
+        if !(scale > F::zero()) {
+            return Err(LogisticError::ScaleNotPositive);
+        }
+        Ok(Logistic { location, scale })
+    }
+}
+
+impl<F: Float> Distribution<F> for Logistic<F> where Open01: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(Open01);
+        self.location + self.scale * (u / (F::one() - u)).ln()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{Logistic, LogisticError};
+
+    #[test]
+    fn test_logistic_mean_and_variance() {
+        let (location, scale) = (1.5, 0.5);
+        let d = Logistic::new(location, scale).unwrap();
+        let mut rng = ::test::rng(805);
+        let mut results = [0.0f64; 10000];
+        for x in results.iter_mut() {
+            *x = d.sample(&mut rng);
+        }
+        let n = results.len() as f64;
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        let expected_variance = ::std::f64::consts::PI.powi(2) / 3.0 * scale * scale;
+        assert!((mean - location).abs() < 4.0 * (expected_variance / n).sqrt(),
+                "mean {}", mean);
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0,
+                "variance {}", variance);
+    }
+
+    #[test]
+    fn test_logistic_f32() {
+        let d = Logistic::new(-4.0f32, 1.0).unwrap();
+        let mut rng = ::test::rng(806);
+        let mut numbers = [0.0f32; 1000];
+        for x in numbers.iter_mut() {
+            *x = d.sample(&mut rng);
+            assert!(x.is_finite());
+        }
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((numbers[500] + 4.0).abs() < 0.25);
+    }
+
+    #[test]
+    fn test_logistic_invalid() {
+        assert_eq!(Logistic::new(0.0, 0.0).unwrap_err(), LogisticError::ScaleNotPositive);
+        assert_eq!(Logistic::new(0.0, -1.0).unwrap_err(), LogisticError::ScaleNotPositive);
+        assert_eq!(Logistic::new(0.0, ::std::f64::NAN).unwrap_err(),
+                   LogisticError::ScaleNotPositive);
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Mixture distributions.
+
+use Rng;
+use distributions::{Distribution, WeightedError, WeightedIndex};
+use distributions::uniform::{SampleUniform, SampleBorrow};
+use core::fmt;
+#[cfg(not(feature="std"))] use alloc::vec::Vec;
+
+/// A mixture of several distributions, the components, with given weights.
+///
+/// Sampling picks a component with probability proportional to its weight,
+/// using [`WeightedIndex`], and returns a sample of that component.
+///
+/// All components must have the same type `D`. To mix distributions of
+/// different types, box them as [`DistributionObj`] trait objects: since
+/// `Box<DistributionObj<T>>` implements `Distribution<T>`, a
+/// `Vec<Box<DistributionObj<T>>>` can be used as the components.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(unknown_lints, bare_trait_objects)]
+/// use rand::distributions::{Distribution, DistributionObj, Exp, LogNormal, Mixture,
+///                           Normal};
+///
+/// let mut rng = rand::thread_rng();
+///
+/// // A bimodal distribution
+/// let bimodal = Mixture::new(
+///     vec![Normal::new(-2.0, 1.0).unwrap(), Normal::new(3.0, 0.5).unwrap()],
+///     &[0.3, 0.7]).unwrap();
+/// let v: f64 = bimodal.sample(&mut rng);
+///
+/// // Latencies: mostly fast, with a slow tail
+/// let components: Vec<Box<DistributionObj<f64>>> = vec![
+///     Box::new(LogNormal::new(0.0, 0.25).unwrap()),
+///     Box::new(Exp::new(0.01).unwrap()),
+/// ];
+/// let latency = Mixture::new(components, &[0.95, 0.05]).unwrap();
+/// let v: f64 = latency.sample(&mut rng);
+/// ```
+///
+/// [`WeightedIndex`]: crate::distributions::WeightedIndex
+/// [`DistributionObj`]: crate::distributions::DistributionObj
+pub struct Mixture<D, X: SampleUniform + PartialOrd> {
+    components: Vec<D>,
+    index: WeightedIndex<X>,
+}
+
+impl<D, X> fmt::Debug for Mixture<D, X>
+    where D: fmt::Debug, X: SampleUniform + PartialOrd, WeightedIndex<X>: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mixture")
+            .field("components", &self.components)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<D, X> Clone for Mixture<D, X>
+    where D: Clone, X: SampleUniform + PartialOrd, WeightedIndex<X>: Clone
+{
+    fn clone(&self) -> Self {
+        Mixture { components: self.components.clone(), index: self.index.clone() }
+    }
+}
+
+impl<D, X: SampleUniform + PartialOrd> Mixture<D, X> {
+    /// Creates a new `Mixture` of the given `components`, with the given
+    /// `weights`. The weights can use any type `X` accepted by
+    /// [`WeightedIndex`].
+    ///
+    /// Returns an error if the number of weights differs from the number of
+    /// components, and otherwise the errors of [`WeightedIndex::new`]: if
+    /// there are no components, if any weight is `< 0`, or if the total
+    /// weight is 0.
+    ///
+    /// [`WeightedIndex`]: crate::distributions::WeightedIndex
+    /// [`WeightedIndex::new`]: crate::distributions::WeightedIndex::new
+    pub fn new<I>(components: Vec<D>, weights: I) -> Result<Mixture<D, X>, WeightedError>
+        where I: IntoIterator,
+              I::Item: SampleBorrow<X>,
+              X: for<'a> ::core::ops::AddAssign<&'a X> + Clone + Default
+    {
+        let mut len = 0;
+        let index = WeightedIndex::new(weights.into_iter().inspect(|_| len += 1))?;
+        if len != components.len() {
+            return Err(WeightedError::LengthMismatch);
+        }
+        Ok(Mixture { components, index })
+    }
+
+    /// Returns the components of the mixture.
+    pub fn components(&self) -> &[D] {
+        &self.components
+    }
+}
+
+impl<D, X, T> Distribution<T> for Mixture<D, X>
+    where D: Distribution<T>, X: SampleUniform + PartialOrd
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let i = self.index.sample(rng);
+        self.components[i].sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::{Distribution, DistributionObj, Normal, Uniform, WeightedError};
+    use super::Mixture;
+
+    #[test]
+    fn test_mixture() {
+        let mut rng = ::test::rng(860);
+        let d = Mixture::new(
+            vec![Normal::new(-5.0, 1.0).unwrap(), Normal::new(5.0, 1.0).unwrap()],
+            &[1.0, 3.0]).unwrap();
+        assert_eq!(d.components().len(), 2);
+
+        const N: usize = 10000;
+        let mut right = 0;
+        let mut sum = 0.0;
+        for _ in 0..N {
+            let x: f64 = d.sample(&mut rng);
+            if x > 0.0 {
+                right += 1;
+            }
+            sum += x;
+        }
+        // The components hardly overlap.
+        let p = right as f64 / N as f64;
+        assert!((p - 0.75).abs() < 0.02, "{}", p);
+        let mean = sum / N as f64;
+        assert!((mean - 2.5).abs() < 0.15, "{}", mean);
+    }
+
+    #[test]
+    fn test_mixture_boxed() {
+        let mut rng = ::test::rng(861);
+        let components: Vec<Box<DistributionObj<f64>>> = vec![
+            Box::new(Uniform::new(0.0, 1.0)),
+            Box::new(Normal::new(100.0, 1.0).unwrap()),
+            Box::new(Uniform::new(-10.0, -9.0)),
+        ];
+        let d = Mixture::new(components, &[1u32, 0, 1]).unwrap();
+        let mut counts = [0; 2];
+        for _ in 0..1000 {
+            let x: f64 = d.sample(&mut rng);
+            if x >= 0.0 && x < 1.0 {
+                counts[0] += 1;
+            } else {
+                assert!(x >= -10.0 && x < -9.0);
+                counts[1] += 1;
+            }
+        }
+        assert!(counts[0] > 400 && counts[1] > 400);
+    }
+
+    #[test]
+    fn test_mixture_invalid() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        assert_eq!(Mixture::new(vec![normal, normal], &[1.0]).unwrap_err(),
+                   WeightedError::LengthMismatch);
+        assert_eq!(Mixture::new(vec![normal], &[1.0, 2.0]).unwrap_err(),
+                   WeightedError::LengthMismatch);
+        assert_eq!(Mixture::<Normal<f64>, f64>::new(vec![], &[]).unwrap_err(),
+                   WeightedError::NoItem);
+        assert_eq!(Mixture::new(vec![normal, normal], &[0.0, 0.0]).unwrap_err(),
+                   WeightedError::AllWeightsZero);
+        assert_eq!(Mixture::new(vec![normal, normal], &[1.0, -1.0]).unwrap_err(),
+                   WeightedError::NegativeWeight);
+    }
+}
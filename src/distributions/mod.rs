@@ -73,27 +73,47 @@
 //! `char`.
 //!
 //! [`WeightedIndex`] can be used to do weighted sampling from a set of items,
-//! such as from an array.
+//! such as from an array. [`WeightedAliasIndex`] does the same with `O(1)`
+//! sampling at the cost of a more expensive setup. [`WeightedTreeIndex`]
+//! supports adding, removing and reweighting items in `O(log N)` time.
 //!
 //! # Non-uniform probability distributions
 //!
-//! Rand currently provides the following probability distributions:
+//! Rand currently provides the following probability distributions. The
+//! continuous distributions are generic over the [`Float`] type `F` of their
+//! parameters and samples, which may be `f32` or `f64`:
 //!
 //! - Related to real-valued quantities that grow linearly
 //!   (e.g. errors, offsets):
 //!   - [`Normal`] distribution, and [`StandardNormal`] as a primitive
+//!   - [`SkewNormal`] distribution
+//!   - [`NormalInverseGaussian`] distribution, with heavy tails and skew
 //!   - [`Cauchy`] distribution
+//!   - [`Laplace`] distribution
+//!   - [`Logistic`] distribution
+//! - Related to extreme values (e.g. maximum floods, record temperatures):
+//!   - [`Gumbel`] distribution
+//!   - [`Frechet`] distribution
+//! - Related to angles and directions:
+//!   - [`VonMises`] distribution
 //! - Related to Bernoulli trials (yes/no events, with a given probability):
 //!   - [`Binomial`] distribution
 //!   - [`Bernoulli`] distribution, similar to [`Rng::gen_bool`].
+//!   - [`Hypergeometric`] distribution, for draws without replacement
+//!   - [`Geometric`] distribution, the number of failures before a success
+//!   - [`NegativeBinomial`] distribution
 //! - Related to positive real-valued quantities that grow exponentially
 //!   (e.g. prices, incomes, populations):
 //!   - [`LogNormal`] distribution
+//!   - [`InverseGaussian`] distribution
 //! - Related to the occurrence of independent events at a given rate:
 //!   - [`Pareto`] distribution
 //!   - [`Poisson`] distribution
 //!   - [`Exp`]onential distribution, and [`Exp1`] as a primitive
 //!   - [`Weibull`] distribution
+//! - Related to ranks and frequencies following a power law:
+//!   - [`Zipf`] distribution, over a finite number of items
+//!   - [`Zeta`] distribution, over all positive integers
 //! - Gamma and derived distributions:
 //!   - [`Gamma`] distribution
 //!   - [`ChiSquared`] distribution
@@ -104,8 +124,27 @@
 //!   - [`Triangular`] distribution
 //! - Multivariate probability distributions
 //!   - [`Dirichlet`] distribution
+//!   - [`MultivariateNormal`] distribution
+//!   - [`Multinomial`] distribution, counts of outcomes in several categories
 //!   - [`UnitSphereSurface`] distribution
 //!   - [`UnitCircle`] distribution
+//!   - [`UnitSphere`] distribution, on the sphere in `N` dimensions
+//!   - [`UnitBall`] distribution, inside the ball in `N` dimensions
+//!   - [`UnitDisc`] distribution
+//!   - [`UnitSimplex`] distribution, over probability vectors
+//! - Built from observed data:
+//!   - [`Empirical`] distribution of a sample, optionally interpolated
+//!   - [`PiecewiseConstant`] distribution, from a histogram
+//!   - [`PiecewiseLinear`] distribution
+//! - Custom distributions:
+//!   - [`InverseCdf`], sampled by inversion of a quantile function
+//!   - [`RejectionSampler`], sampled by rejection from a density
+//! - Combinations of distributions:
+//!   - [`Mixture`] of several distributions
+//! - Truncated distributions, restricted to an interval:
+//!   - [`Truncated`], an adapter for any distribution
+//!   - [`TruncatedNormal`] distribution
+//!   - [`TruncatedExp`]onential distribution
 //!
 //! # Examples
 //!
@@ -115,7 +154,7 @@
 //! use rand::{thread_rng, Rng};
 //! use rand::distributions::Exp;
 //!
-//! let exp = Exp::new(2.0);
+//! let exp = Exp::new(2.0).unwrap();
 //! let v = thread_rng().sample(exp);
 //! println!("{} is from an Exp(2) distribution", v);
 //! ```
@@ -151,66 +190,160 @@
 //! [`Cauchy`]: distributions::Cauchy
 //! [`ChiSquared`]: distributions::ChiSquared
 //! [`Dirichlet`]: distributions::Dirichlet
+//! [`Empirical`]: distributions::Empirical
 //! [`Exp`]: distributions::Exp
 //! [`Exp1`]: distributions::Exp1
 //! [`FisherF`]: distributions::FisherF
+//! [`Frechet`]: distributions::Frechet
+//! [`Float`]: distributions::Float
 //! [`Gamma`]: distributions::Gamma
+//! [`Geometric`]: distributions::Geometric
+//! [`Gumbel`]: distributions::Gumbel
+//! [`Hypergeometric`]: distributions::Hypergeometric
+//! [`InverseCdf`]: distributions::InverseCdf
+//! [`InverseGaussian`]: distributions::InverseGaussian
+//! [`Laplace`]: distributions::Laplace
 //! [`LogNormal`]: distributions::LogNormal
+//! [`Logistic`]: distributions::Logistic
+//! [`Mixture`]: distributions::Mixture
+//! [`Multinomial`]: distributions::Multinomial
+//! [`MultivariateNormal`]: distributions::MultivariateNormal
+//! [`NegativeBinomial`]: distributions::NegativeBinomial
 //! [`Normal`]: distributions::Normal
+//! [`NormalInverseGaussian`]: distributions::NormalInverseGaussian
 //! [`Open01`]: distributions::Open01
 //! [`OpenClosed01`]: distributions::OpenClosed01
 //! [`Pareto`]: distributions::Pareto
+//! [`PiecewiseConstant`]: distributions::PiecewiseConstant
+//! [`PiecewiseLinear`]: distributions::PiecewiseLinear
 //! [`Poisson`]: distributions::Poisson
+//! [`RejectionSampler`]: distributions::RejectionSampler
+//! [`SkewNormal`]: distributions::SkewNormal
 //! [`Standard`]: distributions::Standard
 //! [`StandardNormal`]: distributions::StandardNormal
 //! [`StudentT`]: distributions::StudentT
 //! [`Triangular`]: distributions::Triangular
+//! [`Truncated`]: distributions::Truncated
+//! [`TruncatedExp`]: distributions::TruncatedExp
+//! [`TruncatedNormal`]: distributions::TruncatedNormal
 //! [`Uniform`]: distributions::Uniform
 //! [`Uniform::new`]: distributions::Uniform::new
 //! [`Uniform::new_inclusive`]: distributions::Uniform::new_inclusive
 //! [`UnitSphereSurface`]: distributions::UnitSphereSurface
 //! [`UnitCircle`]: distributions::UnitCircle
+//! [`UnitSphere`]: distributions::UnitSphere
+//! [`UnitBall`]: distributions::UnitBall
+//! [`UnitDisc`]: distributions::UnitDisc
+//! [`UnitSimplex`]: distributions::UnitSimplex
+//! [`VonMises`]: distributions::VonMises
 //! [`Weibull`]: distributions::Weibull
 //! [`WeightedIndex`]: distributions::WeightedIndex
+//! [`WeightedAliasIndex`]: distributions::WeightedAliasIndex
+//! [`WeightedTreeIndex`]: distributions::WeightedTreeIndex
+//! [`Zeta`]: distributions::Zeta
+//! [`Zipf`]: distributions::Zipf
 
 #[cfg(any(rustc_1_26, features="nightly"))]
 use core::iter;
 use Rng;
+use RngCore;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::boxed::Box;
 
 pub use self::other::Alphanumeric;
 #[doc(inline)] pub use self::uniform::Uniform;
 pub use self::float::{OpenClosed01, Open01};
-pub use self::bernoulli::Bernoulli;
+#[cfg(feature="std")] pub use self::float::Float;
+pub use self::bernoulli::{Bernoulli, BernoulliError};
+pub use self::combinators::{DistMap, DistFilter, DistZip, DistFlatMap};
+pub use self::inverse_cdf::InverseCdf;
 #[cfg(feature="alloc")] pub use self::weighted::{WeightedIndex, WeightedError};
-#[cfg(feature="std")] pub use self::unit_sphere::UnitSphereSurface;
+#[cfg(feature="alloc")] pub use self::weighted_alias::{WeightedAliasIndex,
+    AliasableWeight};
+#[cfg(feature="alloc")] pub use self::weighted_tree::{WeightedTreeIndex, TreeWeight};
+#[cfg(feature="alloc")] pub use self::mixture::Mixture;
+#[cfg(feature="std")] pub use self::unit_sphere::{UnitSphereSurface, UnitSphere};
 #[cfg(feature="std")] pub use self::unit_circle::UnitCircle;
-#[cfg(feature="std")] pub use self::gamma::{Gamma, ChiSquared, FisherF,
-    StudentT, Beta};
-#[cfg(feature="std")] pub use self::normal::{Normal, LogNormal, StandardNormal};
-#[cfg(feature="std")] pub use self::exponential::{Exp, Exp1};
-#[cfg(feature="std")] pub use self::pareto::Pareto;
-#[cfg(feature="std")] pub use self::poisson::Poisson;
-#[cfg(feature="std")] pub use self::binomial::Binomial;
-#[cfg(feature="std")] pub use self::cauchy::Cauchy;
-#[cfg(feature="std")] pub use self::dirichlet::Dirichlet;
-#[cfg(feature="std")] pub use self::triangular::Triangular;
-#[cfg(feature="std")] pub use self::weibull::Weibull;
+#[cfg(feature="std")] pub use self::unit_ball::UnitBall;
+#[cfg(feature="std")] pub use self::unit_disc::UnitDisc;
+#[cfg(feature="std")] pub use self::unit_simplex::UnitSimplex;
+#[cfg(feature="std")] pub use self::gamma::{Gamma, GammaError, ChiSquared,
+    ChiSquaredError, FisherF, FisherFError, StudentT, Beta, BetaError};
+#[cfg(feature="std")] pub use self::normal::{Normal, LogNormal, NormalError,
+    StandardNormal};
+#[cfg(feature="std")] pub use self::skew_normal::{SkewNormal, SkewNormalError};
+#[cfg(feature="std")] pub use self::inverse_gaussian::{InverseGaussian,
+    InverseGaussianError};
+#[cfg(feature="std")] pub use self::normal_inverse_gaussian::{NormalInverseGaussian,
+    NormalInverseGaussianError};
+#[cfg(feature="std")] pub use self::exponential::{Exp, ExpError, Exp1};
+#[cfg(feature="std")] pub use self::pareto::{Pareto, ParetoError};
+#[cfg(feature="std")] pub use self::poisson::{Poisson, PoissonError};
+#[cfg(feature="std")] pub use self::binomial::{Binomial, BinomialError};
+#[cfg(feature="std")] pub use self::hypergeometric::{Hypergeometric, HypergeometricError};
+#[cfg(feature="std")] pub use self::multinomial::{Multinomial, MultinomialError};
+#[cfg(feature="std")] pub use self::geometric::{Geometric, GeometricError};
+#[cfg(feature="std")] pub use self::negative_binomial::{NegativeBinomial,
+    NegativeBinomialError};
+#[cfg(feature="std")] pub use self::cauchy::{Cauchy, CauchyError};
+#[cfg(feature="std")] pub use self::laplace::{Laplace, LaplaceError};
+#[cfg(feature="std")] pub use self::logistic::{Logistic, LogisticError};
+#[cfg(feature="std")] pub use self::gumbel::{Gumbel, GumbelError};
+#[cfg(feature="std")] pub use self::frechet::{Frechet, FrechetError};
+#[cfg(feature="std")] pub use self::von_mises::{VonMises, VonMisesError};
+#[cfg(feature="std")] pub use self::dirichlet::{Dirichlet, DirichletError};
+#[cfg(feature="std")] pub use self::multivariate_normal::{MultivariateNormal,
+    MultivariateNormalError};
+#[cfg(feature="std")] pub use self::triangular::{Triangular, TriangularError};
+#[cfg(feature="std")] pub use self::truncated::{Truncated, TruncatedError, TruncatedNormal,
+    TruncatedNormalError, TruncatedExp, TruncatedExpError};
+#[cfg(feature="std")] pub use self::weibull::{Weibull, WeibullError};
+#[cfg(feature="std")] pub use self::zipf::{Zipf, ZipfError, Zeta, ZetaError};
+#[cfg(feature="std")] pub use self::empirical::{Empirical, EmpiricalError};
+#[cfg(feature="std")] pub use self::piecewise::{PiecewiseConstant, PiecewiseLinear,
+    PiecewiseError};
+#[cfg(feature="std")] pub use self::rejection::{RejectionSampler, RejectionError};
 
 pub mod uniform;
 mod bernoulli;
+mod combinators;
+mod inverse_cdf;
 #[cfg(feature="alloc")] mod weighted;
+#[cfg(feature="alloc")] mod weighted_alias;
+#[cfg(feature="alloc")] mod weighted_tree;
+#[cfg(feature="alloc")] mod mixture;
 #[cfg(feature="std")] mod unit_sphere;
 #[cfg(feature="std")] mod unit_circle;
+#[cfg(feature="std")] mod unit_ball;
+#[cfg(feature="std")] mod unit_disc;
+#[cfg(feature="std")] mod unit_simplex;
 #[cfg(feature="std")] mod gamma;
 #[cfg(feature="std")] mod normal;
+#[cfg(feature="std")] mod skew_normal;
+#[cfg(feature="std")] mod inverse_gaussian;
+#[cfg(feature="std")] mod normal_inverse_gaussian;
 #[cfg(feature="std")] mod exponential;
 #[cfg(feature="std")] mod pareto;
 #[cfg(feature="std")] mod poisson;
 #[cfg(feature="std")] mod binomial;
+#[cfg(feature="std")] mod hypergeometric;
+#[cfg(feature="std")] mod multinomial;
+#[cfg(feature="std")] mod geometric;
+#[cfg(feature="std")] mod negative_binomial;
 #[cfg(feature="std")] mod cauchy;
+#[cfg(feature="std")] mod laplace;
+#[cfg(feature="std")] mod logistic;
+#[cfg(feature="std")] mod gumbel;
+#[cfg(feature="std")] mod frechet;
+#[cfg(feature="std")] mod von_mises;
 #[cfg(feature="std")] mod dirichlet;
+#[cfg(feature="std")] mod multivariate_normal;
 #[cfg(feature="std")] mod triangular;
+#[cfg(feature="std")] mod truncated;
 #[cfg(feature="std")] mod weibull;
+#[cfg(feature="std")] mod zipf;
+#[cfg(feature="std")] mod empirical;
+#[cfg(feature="std")] mod piecewise;
+#[cfg(feature="std")] mod rejection;
 
 mod float;
 mod integer;
@@ -267,6 +400,86 @@ pub trait Distribution<T> {
             phantom: ::core::marker::PhantomData,
         }
     }
+
+    /// Create a distribution of values of `S` by applying `func` to the
+    /// samples of this distribution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let even = Uniform::new(0u32, 50).map(|x| x * 2);
+    /// assert_eq!(even.sample(&mut thread_rng()) % 2, 0);
+    /// ```
+    fn map<F, S>(self, func: F) -> DistMap<Self, F, T, S>
+        where Self: Sized, F: Fn(T) -> S
+    {
+        DistMap::new(self, func)
+    }
+
+    /// Create a distribution of the samples of this distribution which
+    /// satisfy `predicate`, by rejection.
+    ///
+    /// Each sample of the new distribution takes at most `max_attempts`
+    /// samples of this distribution. If none of them is accepted,
+    /// [`DistFilter::try_sample`] returns `None` and [`Distribution::sample`]
+    /// panics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let odd = Uniform::new(0u32, 100).filter(|x| x % 2 == 1, 100);
+    /// assert_eq!(odd.try_sample(&mut thread_rng()).map(|x| x % 2), Some(1));
+    /// ```
+    fn filter<P>(self, predicate: P, max_attempts: usize) -> DistFilter<Self, P, T>
+        where Self: Sized, P: Fn(&T) -> bool
+    {
+        DistFilter::new(self, predicate, max_attempts)
+    }
+
+    /// Create a distribution of pairs of independent samples of this
+    /// distribution and `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Bernoulli, Uniform};
+    ///
+    /// let d = Uniform::new(0, 10).zip(Bernoulli::new(0.5).unwrap());
+    /// let (x, b): (i32, bool) = d.sample(&mut thread_rng());
+    /// println!("{} {}", x, b);
+    /// ```
+    fn zip<D>(self, other: D) -> DistZip<Self, D>
+        where Self: Sized
+    {
+        DistZip::new(self, other)
+    }
+
+    /// Create a distribution which samples from the distribution returned by
+    /// `func` for a sample of this distribution.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// // A uniform distribution over a range of random length
+    /// let d = Uniform::new(1u32, 10).flat_map(|n| Uniform::new(0, n));
+    /// let x: u32 = d.sample(&mut thread_rng());
+    /// assert!(x < 9);
+    /// ```
+    fn flat_map<F, D>(self, func: F) -> DistFlatMap<Self, F, T>
+        where Self: Sized, F: Fn(T) -> D
+    {
+        DistFlatMap::new(self, func)
+    }
 }
 
 impl<'a, T, D: Distribution<T>> Distribution<T> for &'a D {
@@ -275,6 +488,67 @@ impl<'a, T, D: Distribution<T>> Distribution<T> for &'a D {
     }
 }
 
+/// An object-safe counterpart of [`Distribution`], for use as a trait object.
+///
+/// [`Distribution::sample`] is generic over the type of the RNG, so a
+/// `Distribution` cannot be used as a trait object. This trait instead takes
+/// the RNG as `&mut RngCore`, and is implemented for every `Distribution`.
+/// `Box<DistributionObj<T>>` in turn implements `Distribution<T>`, so boxed
+/// distributions of different types can be used wherever a `Distribution`
+/// is expected, e.g. as the components of a [`Mixture`]. The same holds for
+/// `&DistributionObj<T>`, and for the `Send` and `Send + Sync` variants of
+/// the boxed trait object, which can be shared between threads.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(unknown_lints, bare_trait_objects)]
+/// use rand::distributions::{Distribution, DistributionObj, Normal, Uniform};
+///
+/// let distrs: Vec<Box<DistributionObj<f64>>> = vec![
+///     Box::new(Uniform::new(0.0, 1.0)),
+///     Box::new(Normal::new(0.0, 1.0).unwrap()),
+/// ];
+/// let mut rng = rand::thread_rng();
+/// for d in &distrs {
+///     println!("{}", d.sample(&mut rng));
+/// }
+/// ```
+///
+/// [`Mixture`]: distributions::Mixture
+pub trait DistributionObj<T> {
+    /// Generate a random value of `T`, using `rng` as the source of randomness.
+    fn sample_obj(&self, rng: &mut RngCore) -> T;
+}
+
+impl<T, D: Distribution<T>> DistributionObj<T> for D {
+    fn sample_obj(&self, rng: &mut RngCore) -> T {
+        self.sample(rng)
+    }
+}
+
+impl<'a, T> Distribution<T> for &'a DistributionObj<T> {
+    fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> T {
+        // `&mut R` is sized, unlike `R`, so it can be made a trait object.
+        (**self).sample_obj(&mut rng)
+    }
+}
+
+macro_rules! box_distribution_obj_impl {
+    ($($bounds:tt)*) => {
+        #[cfg(feature="alloc")]
+        impl<T> Distribution<T> for Box<DistributionObj<T> $($bounds)*> {
+            fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> T {
+                (**self).sample_obj(&mut rng)
+            }
+        }
+    }
+}
+
+box_distribution_obj_impl! {}
+box_distribution_obj_impl! { + Send }
+box_distribution_obj_impl! { + Send + Sync }
+
 
 /// An iterator that generates random values of `T` with distribution `D`,
 /// using `R` as the source of randomness.
@@ -596,12 +870,55 @@ mod tests {
                                   Weighted { weight: 1, item: 3 }]);
     }
 
+    #[cfg(feature="std")]
+    #[test]
+    fn test_distribution_obj() {
+        use distributions::{DistributionObj, Exp, Normal, Uniform, Bernoulli};
+        use std::sync::Arc;
+        use std::thread;
+
+        // Distributions chosen at runtime, e.g. from a configuration
+        fn parse(spec: &str) -> Box<DistributionObj<f64> + Send + Sync> {
+            let mut parts = spec.split(' ');
+            let name = parts.next().unwrap();
+            let params: Vec<f64> = parts.map(|p| p.parse().unwrap()).collect();
+            match name {
+                "uniform" => Box::new(Uniform::new(params[0], params[1])),
+                "normal" => Box::new(Normal::new(params[0], params[1]).unwrap()),
+                "exp" => Box::new(Exp::new(params[0]).unwrap()),
+                "coin" => Box::new(Bernoulli::new(params[0]).unwrap()
+                                   .map(|b| if b { 1.0 } else { 0.0 })),
+                _ => panic!("unknown distribution"),
+            }
+        }
+        let config = ["uniform 2 3", "normal 0 1", "exp 2", "coin 0.5"];
+        let distrs: Arc<Vec<_>> = Arc::new(config.iter().map(|s| parse(s)).collect());
+
+        let shared = distrs.clone();
+        let handle = thread::spawn(move || {
+            let mut rng = ::test::rng(212);
+            shared.iter().map(|d| d.sample(&mut rng)).collect::<Vec<f64>>()
+        });
+        let samples = handle.join().unwrap();
+        assert!(samples[0] >= 2.0 && samples[0] < 3.0);
+        assert!(samples[1].is_finite());
+        assert!(samples[2] >= 0.0);
+        assert!(samples[3] == 0.0 || samples[3] == 1.0);
+
+        let mut rng = ::test::rng(213);
+        let r: &DistributionObj<f64> = &Uniform::new(2.0, 3.0);
+        let sum: f64 = r.sample_iter(&mut rng).take(10).sum();
+        assert!(sum >= 20.0 && sum < 30.0);
+        let x = distrs[0].sample_obj(&mut rng);
+        assert!(x >= 2.0 && x < 3.0);
+    }
+
     #[cfg(feature="std")]
     #[test]
     fn test_distributions_iter() {
         use distributions::Normal;
         let mut rng = ::test::rng(210);
-        let distr = Normal::new(10.0, 10.0);
+        let distr = Normal::new(10.0, 10.0).unwrap();
         let results: Vec<_> = distr.sample_iter(&mut rng).take(100).collect();
         println!("{:?}", results);
     }
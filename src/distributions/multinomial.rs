@@ -0,0 +1,200 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The multinomial distribution.
+
+use Rng;
+use distributions::{Distribution, Binomial};
+use std::{error, fmt};
+
+/// The multinomial distribution `Multinomial(n, p)`.
+///
+/// This is the distribution of the number of outcomes in each of `k`
+/// categories after `n` independent trials, where each trial falls into
+/// category `i` with probability `p[i]`. It generalizes the [`Binomial`]
+/// distribution to more than two categories. Samples are `Vec<u64>` of
+/// length `k` whose elements sum to `n`.
+///
+/// The probabilities need not be normalized; they are divided by their sum.
+///
+/// Samples are generated from conditional binomial distributions: the count
+/// of category `i` is drawn from `Binomial(n', p[i] / (p[i] + ... + p[k-1]))`,
+/// where `n'` is the number of trials not assigned to earlier categories.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Multinomial};
+///
+/// let multi = Multinomial::new(100, vec![0.2, 0.5, 0.3]).unwrap();
+/// let counts = multi.sample(&mut rand::thread_rng());
+/// assert_eq!(counts.iter().sum::<u64>(), 100);
+/// println!("{:?} is from a multinomial distribution", counts);
+/// ```
+///
+/// [`Binomial`]: crate::distributions::Binomial
+#[derive(Clone, Debug)]
+pub struct Multinomial {
+    /// Number of trials.
+    n: u64,
+    /// Probability of each category, conditional on not being in any of the
+    /// previous categories.
+    conditional_p: Vec<f64>,
+}
+
+/// Error type returned from `Multinomial::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultinomialError {
+    /// No probabilities were given.
+    NoCategories,
+    /// A probability is negative, infinite or `nan`.
+    InvalidProbability,
+    /// All probabilities are zero.
+    SumNotPositive,
+}
+
+impl MultinomialError {
+    fn msg(&self) -> &str {
+        match *self {
+            MultinomialError::NoCategories =>
+                "no probabilities in multinomial distribution",
+            MultinomialError::InvalidProbability =>
+                "probability is negative or not finite in multinomial distribution",
+            MultinomialError::SumNotPositive =>
+                "probabilities sum to zero in multinomial distribution",
+        }
+    }
+}
+
+impl fmt::Display for MultinomialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for MultinomialError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl Multinomial {
+    /// Construct a new `Multinomial` with the given number of trials `n` and
+    /// relative probabilities of each category.
+    ///
+    /// Returns an error if `probabilities` is empty, if any probability is
+    /// negative or not finite, or if they sum to zero.
+    pub fn new(n: u64, probabilities: Vec<f64>) -> Result<Multinomial, MultinomialError> {
+        if probabilities.is_empty() {
+            return Err(MultinomialError::NoCategories);
+        }
+        if probabilities.iter().any(|&p| !(p >= 0.0 && p.is_finite())) {
+            return Err(MultinomialError::InvalidProbability);
+        }
+
+        // Divide each probability by the sum of the remaining ones, summing
+        // from the back.
+        let mut conditional_p = probabilities;
+        let mut remaining = 0.0;
+        for p in conditional_p.iter_mut().rev() {
+            remaining += *p;
+            *p = if remaining > 0.0 {
+                (*p / remaining).min(1.0)
+            } else {
+                0.0
+            };
+        }
+        if !(remaining > 0.0 && remaining.is_finite()) {
+            return Err(if remaining > 0.0 {
+                MultinomialError::InvalidProbability
+            } else {
+                MultinomialError::SumNotPositive
+            });
+        }
+
+        Ok(Multinomial { n, conditional_p })
+    }
+}
+
+impl Distribution<Vec<u64>> for Multinomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u64> {
+        let k = self.conditional_p.len();
+        let mut counts = vec![0; k];
+        let mut remaining = self.n;
+        for (count, &p) in counts[..(k - 1)].iter_mut().zip(self.conditional_p.iter()) {
+            if remaining == 0 {
+                break;
+            }
+            *count = rng.sample(Binomial::new(remaining, p).unwrap());
+            remaining -= *count;
+        }
+        // The last category takes the remaining trials. If its probability
+        // is zero, `remaining` is already zero here.
+        counts[k - 1] += remaining;
+        counts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{Multinomial, MultinomialError};
+
+    #[test]
+    fn test_multinomial() {
+        let mut rng = ::test::rng(225);
+        let p = [2.0, 0.0, 5.0, 3.0];
+        let d = Multinomial::new(1000, p.to_vec()).unwrap();
+
+        const N_REPS: usize = 2000;
+        let mut totals = [0u64; 4];
+        for _ in 0..N_REPS {
+            let counts = d.sample(&mut rng);
+            assert_eq!(counts.len(), 4);
+            assert_eq!(counts.iter().sum::<u64>(), 1000);
+            assert_eq!(counts[1], 0);
+            for (t, c) in totals.iter_mut().zip(counts.iter()) {
+                *t += c;
+            }
+        }
+        for (i, &t) in totals.iter().enumerate() {
+            let mean = t as f64 / N_REPS as f64;
+            let expected = 1000.0 * p[i] / 10.0;
+            assert!((mean - expected).abs() < 1.5, "category {}: {}", i, mean);
+        }
+    }
+
+    #[test]
+    fn test_multinomial_edge_cases() {
+        let mut rng = ::test::rng(226);
+        assert_eq!(Multinomial::new(0, vec![0.5, 0.5]).unwrap().sample(&mut rng),
+                   vec![0, 0]);
+        assert_eq!(Multinomial::new(7, vec![3.0]).unwrap().sample(&mut rng),
+                   vec![7]);
+        assert_eq!(Multinomial::new(7, vec![0.0, 1.0, 0.0]).unwrap().sample(&mut rng),
+                   vec![0, 7, 0]);
+        assert_eq!(Multinomial::new(7, vec![1.0, 0.0]).unwrap().sample(&mut rng),
+                   vec![7, 0]);
+    }
+
+    #[test]
+    fn test_multinomial_invalid() {
+        assert_eq!(Multinomial::new(5, vec![]).unwrap_err(),
+                   MultinomialError::NoCategories);
+        assert_eq!(Multinomial::new(5, vec![0.5, -0.1]).unwrap_err(),
+                   MultinomialError::InvalidProbability);
+        assert_eq!(Multinomial::new(5, vec![0.5, ::std::f64::NAN]).unwrap_err(),
+                   MultinomialError::InvalidProbability);
+        assert_eq!(Multinomial::new(5, vec![0.5, ::std::f64::INFINITY]).unwrap_err(),
+                   MultinomialError::InvalidProbability);
+        assert_eq!(Multinomial::new(5, vec![::std::f64::MAX, ::std::f64::MAX]).unwrap_err(),
+                   MultinomialError::InvalidProbability);
+        assert_eq!(Multinomial::new(5, vec![0.0, 0.0]).unwrap_err(),
+                   MultinomialError::SumNotPositive);
+    }
+}
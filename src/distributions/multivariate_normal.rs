@@ -0,0 +1,267 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The multivariate normal distribution.
+
+use Rng;
+use distributions::{Distribution, StandardNormal};
+use std::{error, fmt};
+
+/// The multivariate normal distribution `N(μ, Σ)` with mean vector `μ` and
+/// covariance matrix `Σ`.
+///
+/// The Cholesky factor `L` of the covariance matrix, with `Σ = L Lᵀ`, is
+/// computed on construction. Sampling draws a vector `z` of independent
+/// [`StandardNormal`] values and returns `μ + L z`, which takes `O(d²)` time
+/// for `d` dimensions.
+///
+/// Besides [`Distribution::sample`], which allocates a new `Vec<f64>`,
+/// [`sample_into`] writes a sample into an existing buffer.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, MultivariateNormal};
+///
+/// let mvn = MultivariateNormal::new(
+///     vec![1.0, -2.0],
+///     vec![vec![2.0, 0.6],
+///          vec![0.6, 1.0]]).unwrap();
+/// let mut rng = rand::thread_rng();
+/// let v = mvn.sample(&mut rng);
+/// println!("{:?} is from a bivariate normal distribution", v);
+///
+/// let mut buf = [0.0; 2];
+/// mvn.sample_into(&mut rng, &mut buf);
+/// ```
+///
+/// [`StandardNormal`]: crate::distributions::StandardNormal
+/// [`Distribution::sample`]: crate::distributions::Distribution::sample
+/// [`sample_into`]: MultivariateNormal::sample_into
+#[derive(Clone, Debug)]
+pub struct MultivariateNormal {
+    mean: Vec<f64>,
+    /// The lower triangle of the Cholesky factor, packed by row: row `i`
+    /// starts at `i * (i + 1) / 2` and has `i + 1` entries.
+    cholesky: Vec<f64>,
+}
+
+/// Error type returned from `MultivariateNormal::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultivariateNormalError {
+    /// The mean vector is empty.
+    MeanEmpty,
+    /// An element of the mean vector is infinite or `nan`.
+    MeanNotFinite,
+    /// The covariance matrix is not square, with the same dimension as the
+    /// mean vector.
+    CovarianceWrongShape,
+    /// The covariance matrix is not symmetric, or has `nan` elements above
+    /// the diagonal.
+    CovarianceNotSymmetric,
+    /// The covariance matrix is not positive-definite (this includes `nan`
+    /// elements).
+    CovarianceNotPositiveDefinite,
+}
+
+impl MultivariateNormalError {
+    fn msg(&self) -> &str {
+        match *self {
+            MultivariateNormalError::MeanEmpty =>
+                "mean is empty in multivariate normal distribution",
+            MultivariateNormalError::MeanNotFinite =>
+                "mean is not finite in multivariate normal distribution",
+            MultivariateNormalError::CovarianceWrongShape =>
+                "covariance has the wrong shape in multivariate normal distribution",
+            MultivariateNormalError::CovarianceNotSymmetric =>
+                "covariance is not symmetric in multivariate normal distribution",
+            MultivariateNormalError::CovarianceNotPositiveDefinite =>
+                "covariance is not positive-definite in multivariate normal distribution",
+        }
+    }
+}
+
+impl fmt::Display for MultivariateNormalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for MultivariateNormalError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl MultivariateNormal {
+    /// Construct a new `MultivariateNormal` with the given `mean` vector and
+    /// `covariance` matrix, given as a vector of rows.
+    ///
+    /// Returns an error if `mean` is empty or not finite, if `covariance` is
+    /// not a square matrix of the same dimension as `mean`, or if it is not
+    /// symmetric and positive-definite. Symmetry is checked up to a relative
+    /// tolerance of `1e-10`, after which only the lower triangle is used.
+    pub fn new(mean: Vec<f64>, covariance: Vec<Vec<f64>>)
+        -> Result<MultivariateNormal, MultivariateNormalError>
+    {
+        let n = mean.len();
+        if n == 0 {
+            return Err(MultivariateNormalError::MeanEmpty);
+        }
+        if mean.iter().any(|x| !x.is_finite()) {
+            return Err(MultivariateNormalError::MeanNotFinite);
+        }
+        if covariance.len() != n || covariance.iter().any(|row| row.len() != n) {
+            return Err(MultivariateNormalError::CovarianceWrongShape);
+        }
+        for i in 0..n {
+            for j in 0..i {
+                let (a, b) = (covariance[i][j], covariance[j][i]);
+                if !((a - b).abs() <= 1e-10 * (a.abs() + b.abs())) {
+                    return Err(MultivariateNormalError::CovarianceNotSymmetric);
+                }
+            }
+        }
+
+        // Cholesky–Banachiewicz algorithm, computing `L` row by row.
+        let row = |i: usize| i * (i + 1) / 2;
+        let mut cholesky = vec![0.0; row(n)];
+        for i in 0..n {
+            for j in 0..(i + 1) {
+                let mut sum = covariance[i][j];
+                for k in 0..j {
+                    sum -= cholesky[row(i) + k] * cholesky[row(j) + k];
+                }
+                if i == j {
+                    // This also rejects `nan`.
+                    if !(sum > 0.0) {
+                        return Err(MultivariateNormalError::CovarianceNotPositiveDefinite);
+                    }
+                    cholesky[row(i) + i] = sum.sqrt();
+                } else {
+                    cholesky[row(i) + j] = sum / cholesky[row(j) + j];
+                }
+            }
+        }
+
+        Ok(MultivariateNormal { mean, cholesky })
+    }
+
+    /// Returns the number of dimensions.
+    pub fn dim(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Generates a sample and writes it into `out`, without allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len()` differs from the number of dimensions.
+    pub fn sample_into<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [f64]) {
+        let n = self.dim();
+        assert_eq!(out.len(), n, "output has the wrong dimension");
+
+        for x in out.iter_mut() {
+            *x = rng.sample(StandardNormal);
+        }
+        // Compute `mean + L z` in place. Row `i` of `L` only uses `z[..=i]`,
+        // so going backwards never reads an element which was overwritten.
+        for i in (0..n).rev() {
+            let start = i * (i + 1) / 2;
+            let l_row = &self.cholesky[start..(start + i + 1)];
+            let mut x = self.mean[i];
+            for (l, z) in l_row.iter().zip(out.iter()) {
+                x += l * z;
+            }
+            out[i] = x;
+        }
+    }
+}
+
+impl Distribution<Vec<f64>> for MultivariateNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let mut out = vec![0.0; self.dim()];
+        self.sample_into(rng, &mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{MultivariateNormal, MultivariateNormalError};
+
+    #[test]
+    fn test_multivariate_normal_moments() {
+        let mut rng = ::test::rng(222);
+        let mean = vec![1.0, -2.0, 0.5];
+        let cov = vec![vec![4.0, 1.2, -0.8],
+                       vec![1.2, 1.0, 0.3],
+                       vec![-0.8, 0.3, 2.0]];
+        let d = MultivariateNormal::new(mean.clone(), cov.clone()).unwrap();
+        assert_eq!(d.dim(), 3);
+
+        const N: usize = 20000;
+        let samples: Vec<Vec<f64>> = (0..N).map(|_| d.sample(&mut rng)).collect();
+        for i in 0..3 {
+            let m = samples.iter().map(|x| x[i]).sum::<f64>() / N as f64;
+            assert!((m - mean[i]).abs() < 0.05, "mean {}: {}", i, m);
+        }
+        for i in 0..3 {
+            for j in 0..3 {
+                let c = samples.iter().map(|x| (x[i] - mean[i]) * (x[j] - mean[j]))
+                    .sum::<f64>() / N as f64;
+                assert!((c - cov[i][j]).abs() < 0.1, "cov {} {}: {}", i, j, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multivariate_normal_sample_into() {
+        let d = MultivariateNormal::new(vec![0.0, 10.0],
+            vec![vec![1.0, 0.5], vec![0.5, 2.0]]).unwrap();
+        let v = d.sample(&mut ::test::rng(223));
+        let mut buf = [0.0; 2];
+        d.sample_into(&mut ::test::rng(223), &mut buf);
+        assert_eq!(&v[..], &buf[..]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multivariate_normal_sample_into_wrong_len() {
+        let d = MultivariateNormal::new(vec![0.0, 0.0],
+            vec![vec![1.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        d.sample_into(&mut ::test::rng(224), &mut [0.0; 3]);
+    }
+
+    #[test]
+    fn test_multivariate_normal_invalid() {
+        let id = || vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        assert_eq!(MultivariateNormal::new(vec![], vec![]).unwrap_err(),
+                   MultivariateNormalError::MeanEmpty);
+        assert_eq!(MultivariateNormal::new(vec![0.0, ::std::f64::NAN], id()).unwrap_err(),
+                   MultivariateNormalError::MeanNotFinite);
+        assert_eq!(MultivariateNormal::new(vec![0.0], id()).unwrap_err(),
+                   MultivariateNormalError::CovarianceWrongShape);
+        assert_eq!(MultivariateNormal::new(vec![0.0, 0.0],
+                       vec![vec![1.0, 0.0], vec![0.0]]).unwrap_err(),
+                   MultivariateNormalError::CovarianceWrongShape);
+        assert_eq!(MultivariateNormal::new(vec![0.0, 0.0],
+                       vec![vec![1.0, 0.5], vec![0.4, 1.0]]).unwrap_err(),
+                   MultivariateNormalError::CovarianceNotSymmetric);
+        assert_eq!(MultivariateNormal::new(vec![0.0, 0.0],
+                       vec![vec![1.0, 2.0], vec![2.0, 1.0]]).unwrap_err(),
+                   MultivariateNormalError::CovarianceNotPositiveDefinite);
+        assert_eq!(MultivariateNormal::new(vec![0.0, 0.0],
+                       vec![vec![1.0, 1.0], vec![1.0, 1.0]]).unwrap_err(),
+                   MultivariateNormalError::CovarianceNotPositiveDefinite);
+        assert_eq!(MultivariateNormal::new(vec![0.0],
+                       vec![vec![::std::f64::NAN]]).unwrap_err(),
+                   MultivariateNormalError::CovarianceNotPositiveDefinite);
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The negative binomial distribution.
+
+use Rng;
+use distributions::{Distribution, Gamma, Poisson};
+use std::{error, fmt};
+
+/// The negative binomial distribution `NegativeBinomial(r, p)`.
+///
+/// This is the distribution of the number of failures before the `r`-th
+/// success in a sequence of independent Bernoulli trials, each with
+/// probability of success `p`. `r` need not be an integer.
+///
+/// This distribution has density function:
+/// `f(k) = Γ(k + r) / (k! Γ(r)) (1 - p)^k p^r` for `k >= 0`.
+///
+/// Samples are generated as a Gamma–Poisson mixture: `λ` is drawn from
+/// `Gamma(r, (1 - p) / p)` and the result from `Poisson(λ)`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{NegativeBinomial, Distribution};
+///
+/// let nb = NegativeBinomial::new(3.0, 0.4).unwrap();
+/// let v = nb.sample(&mut rand::thread_rng());
+/// println!("{} failures before the third success", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct NegativeBinomial {
+    /// `None` if `p == 1`, in which case there are never any failures.
+    gamma: Option<Gamma<f64>>,
+}
+
+/// Error type returned from `NegativeBinomial::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NegativeBinomialError {
+    /// `r <= 0` or `nan`.
+    RNotPositive,
+    /// `p <= 0`, `p > 1`, `nan`, or so small that `(1 - p) / p` overflows.
+    InvalidProbability,
+}
+
+impl NegativeBinomialError {
+    fn msg(&self) -> &str {
+        match *self {
+            NegativeBinomialError::RNotPositive =>
+                "r is not positive in negative binomial distribution",
+            NegativeBinomialError::InvalidProbability =>
+                "p is not in (0, 1] in negative binomial distribution",
+        }
+    }
+}
+
+impl fmt::Display for NegativeBinomialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for NegativeBinomialError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl NegativeBinomial {
+    /// Construct a new `NegativeBinomial` with the given number of successes
+    /// `r` and probability of success `p`.
+    ///
+    /// Returns an error if `r <= 0`, or if `p <= 0`, `p > 1` or `p` is so
+    /// small that `(1 - p) / p` is infinite.
+    pub fn new(r: f64, p: f64) -> Result<NegativeBinomial, NegativeBinomialError> {
+        if !(r > 0.0) {
+            return Err(NegativeBinomialError::RNotPositive);
+        }
+        if !(p > 0.0 && p <= 1.0) {
+            return Err(NegativeBinomialError::InvalidProbability);
+        }
+        let scale = (1.0 - p) / p;
+        if !scale.is_finite() {
+            return Err(NegativeBinomialError::InvalidProbability);
+        }
+        let gamma = if p == 1.0 {
+            None
+        } else {
+            Some(Gamma::new(r, scale)
+                .map_err(|_| NegativeBinomialError::InvalidProbability)?)
+        };
+        Ok(NegativeBinomial { gamma })
+    }
+}
+
+impl Distribution<u64> for NegativeBinomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let gamma = match self.gamma {
+            Some(gamma) => gamma,
+            None => return 0,
+        };
+        let lambda = rng.sample(gamma);
+        // `lambda` may underflow to zero for small `r`. For tiny `p` it may
+        // be so large that the Poisson sample is all but certain to exceed
+        // `u64::MAX`, to which it saturates, and `Poisson` does not handle
+        // an infinite `lambda`.
+        if !(lambda > 0.0) {
+            0
+        } else if lambda >= 1e20 {
+            ::std::u64::MAX
+        } else {
+            rng.sample(Poisson::new(lambda).unwrap())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::Distribution;
+    use super::{NegativeBinomial, NegativeBinomialError};
+
+    fn test_negative_binomial_mean_and_variance<R: Rng>(r: f64, p: f64, rng: &mut R) {
+        let distr = NegativeBinomial::new(r, p).unwrap();
+
+        let expected_mean = r * (1.0 - p) / p;
+        let expected_variance = r * (1.0 - p) / (p * p);
+
+        let mut results = [0.0; 10000];
+        for i in results.iter_mut() { *i = distr.sample(rng) as f64; }
+
+        let mean = results.iter().sum::<f64>() / results.len() as f64;
+        let std_error = (expected_variance / results.len() as f64).sqrt();
+        assert!((mean - expected_mean).abs() < 4.0 * std_error);
+
+        let variance =
+            results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+            / results.len() as f64;
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0);
+    }
+
+    #[test]
+    fn test_negative_binomial() {
+        let mut rng = ::test::rng(12347);
+        test_negative_binomial_mean_and_variance(1.0, 0.5, &mut rng);
+        test_negative_binomial_mean_and_variance(3.0, 0.4, &mut rng);
+        test_negative_binomial_mean_and_variance(0.5, 0.2, &mut rng);
+        test_negative_binomial_mean_and_variance(20.0, 0.9, &mut rng);
+        test_negative_binomial_mean_and_variance(100.0, 0.05, &mut rng);
+    }
+
+    #[test]
+    fn test_negative_binomial_edge_cases() {
+        let mut rng = ::test::rng(12348);
+        assert_eq!(rng.sample(NegativeBinomial::new(5.0, 1.0).unwrap()), 0);
+
+        assert_eq!(NegativeBinomial::new(0.0, 0.5).unwrap_err(),
+                   NegativeBinomialError::RNotPositive);
+        assert_eq!(NegativeBinomial::new(::std::f64::NAN, 0.5).unwrap_err(),
+                   NegativeBinomialError::RNotPositive);
+        assert_eq!(NegativeBinomial::new(1.0, 0.0).unwrap_err(),
+                   NegativeBinomialError::InvalidProbability);
+        assert_eq!(NegativeBinomial::new(1.0, 1.5).unwrap_err(),
+                   NegativeBinomialError::InvalidProbability);
+        // `(1 - p) / p` overflows for a subnormal `p`
+        assert_eq!(NegativeBinomial::new(2.0, 1e-320).unwrap_err(),
+                   NegativeBinomialError::InvalidProbability);
+        assert_eq!(NegativeBinomial::new(0.5, 1e-320).unwrap_err(),
+                   NegativeBinomialError::InvalidProbability);
+
+        // The scale is finite, but `lambda` is too large for `Poisson`
+        let distr = NegativeBinomial::new(2.0, 1e-308).unwrap();
+        for _ in 0..100 {
+            assert_eq!(distr.sample(&mut rng), ::std::u64::MAX);
+        }
+    }
+}
@@ -10,8 +10,9 @@
 //! The normal and derived distributions.
 
 use Rng;
-use distributions::{ziggurat_tables, Distribution, Open01};
+use distributions::{ziggurat_tables, Distribution, Float, Open01};
 use distributions::utils::ziggurat;
+use std::{error, fmt};
 
 /// Samples floating-point numbers according to the normal distribution
 /// `N(0, 1)` (a.k.a. a standard normal, or Gaussian). This is equivalent to
@@ -19,7 +20,8 @@ use distributions::utils::ziggurat;
 ///
 /// See `Normal` for the general normal distribution.
 ///
-/// Implemented via the ZIGNOR variant[^1] of the Ziggurat method.
+/// Implemented via the ZIGNOR variant[^1] of the Ziggurat method, for both
+/// `f32` and `f64` (each using its own precision of ziggurat tables).
 ///
 /// [^1]: Jurgen A. Doornik (2005). [*An Improved Ziggurat Method to
 ///       Generate Normal Random Samples*](
@@ -37,41 +39,50 @@ use distributions::utils::ziggurat;
 #[derive(Clone, Copy, Debug)]
 pub struct StandardNormal;
 
-impl Distribution<f64> for StandardNormal {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        #[inline]
-        fn pdf(x: f64) -> f64 {
-            (-x*x/2.0).exp()
-        }
-        #[inline]
-        fn zero_case<R: Rng + ?Sized>(rng: &mut R, u: f64) -> f64 {
-            // compute a random number in the tail by hand
-
-            // strange initial conditions, because the loop is not
-            // do-while, so the condition should be true on the first
-            // run, they get overwritten anyway (0 < 1, so these are
-            // good).
-            let mut x = 1.0f64;
-            let mut y = 0.0f64;
-
-            while -2.0 * y < x * x {
-                let x_: f64 = rng.sample(Open01);
-                let y_: f64 = rng.sample(Open01);
-
-                x = x_.ln() / ziggurat_tables::ZIG_NORM_R;
-                y = y_.ln();
-            }
+macro_rules! standard_normal_impl {
+    ($ty:ident, $zig_r:expr, $zig_x:expr, $zig_f:expr) => {
+        impl Distribution<$ty> for StandardNormal {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                #[inline]
+                fn pdf(x: $ty) -> $ty {
+                    (-x*x/2.0).exp()
+                }
+                #[inline]
+                fn zero_case<R: Rng + ?Sized>(rng: &mut R, u: $ty) -> $ty {
+                    // compute a random number in the tail by hand
 
-            if u < 0.0 { x - ziggurat_tables::ZIG_NORM_R } else { ziggurat_tables::ZIG_NORM_R - x }
-        }
+                    // strange initial conditions, because the loop is not
+                    // do-while, so the condition should be true on the first
+                    // run, they get overwritten anyway (0 < 1, so these are
+                    // good).
+                    let mut x: $ty = 1.0;
+                    let mut y: $ty = 0.0;
+
+                    while -2.0 * y < x * x {
+                        let x_: $ty = rng.sample(Open01);
+                        let y_: $ty = rng.sample(Open01);
 
-        ziggurat(rng, true, // this is symmetric
-                 &ziggurat_tables::ZIG_NORM_X,
-                 &ziggurat_tables::ZIG_NORM_F,
-                 pdf, zero_case)
+                        x = x_.ln() / $zig_r;
+                        y = y_.ln();
+                    }
+
+                    if u < 0.0 { x - $zig_r } else { $zig_r - x }
+                }
+
+                ziggurat(rng, true, // this is symmetric
+                         &$zig_x,
+                         &$zig_f,
+                         pdf, zero_case)
+            }
+        }
     }
 }
 
+standard_normal_impl! { f32, ziggurat_tables::ZIG_NORM_R_F32,
+    ziggurat_tables::ZIG_NORM_X_F32, ziggurat_tables::ZIG_NORM_F_F32 }
+standard_normal_impl! { f64, ziggurat_tables::ZIG_NORM_R,
+    ziggurat_tables::ZIG_NORM_X, ziggurat_tables::ZIG_NORM_F }
+
 /// The normal distribution `N(mean, std_dev**2)`.
 ///
 /// This uses the ZIGNOR variant of the Ziggurat method, see [`StandardNormal`]
@@ -86,37 +97,64 @@ impl Distribution<f64> for StandardNormal {
 /// use rand::distributions::{Normal, Distribution};
 ///
 /// // mean 2, standard deviation 3
-/// let normal = Normal::new(2.0, 3.0);
+/// let normal = Normal::new(2.0, 3.0).unwrap();
 /// let v = normal.sample(&mut rand::thread_rng());
 /// println!("{} is from a N(2, 9) distribution", v)
 /// ```
 ///
 /// [`StandardNormal`]: crate::distributions::StandardNormal
 #[derive(Clone, Copy, Debug)]
-pub struct Normal {
-    mean: f64,
-    std_dev: f64,
+pub struct Normal<F> {
+    mean: F,
+    std_dev: F,
+}
+
+/// Error type returned from `Normal::new` and `LogNormal::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NormalError {
+    /// `std_dev < 0` or `nan`.
+    StdDevNegative,
+}
+
+impl NormalError {
+    fn msg(&self) -> &str {
+        match *self {
+            NormalError::StdDevNegative => "std_dev is negative in normal distribution",
+        }
+    }
+}
+
+impl fmt::Display for NormalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for NormalError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
 }
 
-impl Normal {
+impl<F: Float> Normal<F> {
     /// Construct a new `Normal` distribution with the given mean and
     /// standard deviation.
     ///
-    /// # Panics
-    ///
-    /// Panics if `std_dev < 0`.
+    /// Returns an error if `std_dev < 0` or `std_dev` is NaN.
     #[inline]
-    pub fn new(mean: f64, std_dev: f64) -> Normal {
-        assert!(std_dev >= 0.0, "Normal::new called with `std_dev` < 0");
-        Normal {
+    pub fn new(mean: F, std_dev: F) -> Result<Normal<F>, NormalError> {
+        if !(std_dev >= F::zero()) {
+            return Err(NormalError::StdDevNegative);
+        }
+        Ok(Normal {
             mean,
             std_dev
-        }
+        })
     }
 }
-impl Distribution<f64> for Normal {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let n = rng.sample(StandardNormal);
+impl<F: Float> Distribution<F> for Normal<F> where StandardNormal: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let n: F = rng.sample(StandardNormal);
         self.mean + self.std_dev * n
     }
 }
@@ -133,30 +171,27 @@ impl Distribution<f64> for Normal {
 /// use rand::distributions::{LogNormal, Distribution};
 ///
 /// // mean 2, standard deviation 3
-/// let log_normal = LogNormal::new(2.0, 3.0);
+/// let log_normal = LogNormal::new(2.0, 3.0).unwrap();
 /// let v = log_normal.sample(&mut rand::thread_rng());
 /// println!("{} is from an ln N(2, 9) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct LogNormal {
-    norm: Normal
+pub struct LogNormal<F> {
+    norm: Normal<F>
 }
 
-impl LogNormal {
+impl<F: Float> LogNormal<F> {
     /// Construct a new `LogNormal` distribution with the given mean
     /// and standard deviation.
     ///
-    /// # Panics
-    ///
-    /// Panics if `std_dev < 0`.
+    /// Returns an error if `std_dev < 0` or `std_dev` is NaN.
     #[inline]
-    pub fn new(mean: f64, std_dev: f64) -> LogNormal {
-        assert!(std_dev >= 0.0, "LogNormal::new called with `std_dev` < 0");
-        LogNormal { norm: Normal::new(mean, std_dev) }
+    pub fn new(mean: F, std_dev: F) -> Result<LogNormal<F>, NormalError> {
+        Ok(LogNormal { norm: Normal::new(mean, std_dev)? })
     }
 }
-impl Distribution<f64> for LogNormal {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for LogNormal<F> where StandardNormal: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
         self.norm.sample(rng).exp()
     }
 }
@@ -164,34 +199,60 @@ impl Distribution<f64> for LogNormal {
 #[cfg(test)]
 mod tests {
     use distributions::Distribution;
-    use super::{Normal, LogNormal};
+    use super::{Normal, LogNormal, NormalError, StandardNormal};
 
     #[test]
     fn test_normal() {
-        let norm = Normal::new(10.0, 10.0);
+        let norm = Normal::new(10.0, 10.0).unwrap();
         let mut rng = ::test::rng(210);
         for _ in 0..1000 {
             norm.sample(&mut rng);
         }
     }
     #[test]
-    #[should_panic]
+    fn test_normal_f32() {
+        let norm = Normal::new(10.0f32, 10.0).unwrap();
+        let mut rng = ::test::rng(212);
+        let mut sum = 0.0f32;
+        for _ in 0..1000 {
+            let x: f32 = norm.sample(&mut rng);
+            assert!(x.is_finite());
+            sum += x;
+        }
+        assert!((sum / 1000.0 - 10.0).abs() < 1.0);
+    }
+    #[test]
+    fn test_standard_normal_f32_moments() {
+        let mut rng = ::test::rng(213);
+        let n = 10000;
+        let (mut sum, mut sum_sq) = (0.0f64, 0.0f64);
+        for _ in 0..n {
+            let x: f32 = StandardNormal.sample(&mut rng);
+            sum += x as f64;
+            sum_sq += (x * x) as f64;
+        }
+        let mean = sum / n as f64;
+        let var = sum_sq / n as f64 - mean * mean;
+        assert!(mean.abs() < 0.05);
+        assert!((var - 1.0).abs() < 0.05);
+    }
+    #[test]
     fn test_normal_invalid_sd() {
-        Normal::new(10.0, -1.0);
+        assert_eq!(Normal::new(10.0, -1.0).unwrap_err(), NormalError::StdDevNegative);
+        assert!(Normal::new(10.0, ::std::f64::NAN).is_err());
     }
 
 
     #[test]
     fn test_log_normal() {
-        let lnorm = LogNormal::new(10.0, 10.0);
+        let lnorm = LogNormal::new(10.0, 10.0).unwrap();
         let mut rng = ::test::rng(211);
         for _ in 0..1000 {
             lnorm.sample(&mut rng);
         }
     }
     #[test]
-    #[should_panic]
     fn test_log_normal_invalid_sd() {
-        LogNormal::new(10.0, -1.0);
+        assert_eq!(LogNormal::new(10.0, -1.0).unwrap_err(), NormalError::StdDevNegative);
     }
 }
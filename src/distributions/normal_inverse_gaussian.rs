@@ -0,0 +1,165 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The normal-inverse Gaussian distribution.
+
+use Rng;
+use distributions::{Distribution, Float, InverseGaussian, Standard, StandardNormal};
+use std::{error, fmt};
+
+/// The normal-inverse Gaussian distribution `NIG(alpha, beta)`.
+///
+/// This is a normal variance-mean mixture: a sample is `beta * z + sqrt(z) * x`
+/// where `x` is a standard normal and `z` is drawn from the
+/// [`InverseGaussian`] distribution with mean `1 / gamma` and shape `1`,
+/// with `gamma = sqrt(alpha^2 - beta^2)`. The parameter `alpha` controls the
+/// tail heaviness and `beta` the asymmetry. The mean is `beta / gamma` and
+/// the variance is `alpha^2 / gamma^3`.
+///
+/// This is the standardized form of the distribution with location `0` and
+/// scale `1`; for location `mu` and scale `delta`, use
+/// `mu + delta * x` where `x` is drawn from `NIG(alpha * delta, beta * delta)`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, NormalInverseGaussian};
+///
+/// let nig = NormalInverseGaussian::new(2.0, 1.0).unwrap();
+/// let v = nig.sample(&mut rand::thread_rng());
+/// println!("{} is from a NIG(2, 1) distribution", v);
+/// ```
+///
+/// [`InverseGaussian`]: crate::distributions::InverseGaussian
+#[derive(Clone, Copy, Debug)]
+pub struct NormalInverseGaussian<F> {
+    beta: F,
+    inverse_gaussian: InverseGaussian<F>,
+}
+
+/// Error type returned from `NormalInverseGaussian::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NormalInverseGaussianError {
+    /// `alpha <= 0`, infinite or `nan`.
+    AlphaNotPositive,
+    /// `|beta| >= alpha` or `nan`.
+    AbsBetaNotLessThanAlpha,
+}
+
+impl NormalInverseGaussianError {
+    fn msg(&self) -> &str {
+        match *self {
+            NormalInverseGaussianError::AlphaNotPositive =>
+                "alpha is not positive and finite in normal-inverse Gaussian distribution",
+            NormalInverseGaussianError::AbsBetaNotLessThanAlpha =>
+                "|beta| is not less than alpha in normal-inverse Gaussian distribution",
+        }
+    }
+}
+
+impl fmt::Display for NormalInverseGaussianError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for NormalInverseGaussianError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> NormalInverseGaussian<F> {
+    /// Construct a new `NormalInverseGaussian` with the given tail heaviness
+    /// `alpha` and asymmetry `beta`.
+    ///
+    /// Returns an error unless `alpha` is positive and finite and
+    /// `|beta| < alpha`.
+    pub fn new(alpha: F, beta: F)
+        -> Result<NormalInverseGaussian<F>, NormalInverseGaussianError>
+    {
+        if !(alpha > F::zero() && alpha.is_finite()) {
+            return Err(NormalInverseGaussianError::AlphaNotPositive);
+        }
+        if !(beta.abs() < alpha) {
+            return Err(NormalInverseGaussianError::AbsBetaNotLessThanAlpha);
+        }
+        let gamma = (alpha - beta).sqrt() * (alpha + beta).sqrt();
+        let inverse_gaussian = InverseGaussian::new(F::one() / gamma, F::one())
+            .map_err(|_| NormalInverseGaussianError::AbsBetaNotLessThanAlpha)?;
+        Ok(NormalInverseGaussian { beta, inverse_gaussian })
+    }
+}
+
+impl<F: Float> Distribution<F> for NormalInverseGaussian<F>
+    where StandardNormal: Distribution<F>, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let z = self.inverse_gaussian.sample(rng);
+        let x: F = rng.sample(StandardNormal);
+        self.beta * z + z.sqrt() * x
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::Distribution;
+    use super::{NormalInverseGaussian, NormalInverseGaussianError};
+
+    fn test_nig_mean_and_variance<R: Rng>(alpha: f64, beta: f64, rng: &mut R) {
+        let d = NormalInverseGaussian::new(alpha, beta).unwrap();
+        let gamma = (alpha * alpha - beta * beta).sqrt();
+        let expected_mean = beta / gamma;
+        let expected_variance = alpha * alpha / (gamma * gamma * gamma);
+
+        let mut results = [0.0f64; 10000];
+        for x in results.iter_mut() {
+            *x = d.sample(rng);
+        }
+        let n = results.len() as f64;
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_variance / n).sqrt(),
+                "mean {} expected {}", mean, expected_mean);
+        assert!((variance - expected_variance).abs() < expected_variance / 5.0,
+                "variance {} expected {}", variance, expected_variance);
+    }
+
+    #[test]
+    fn test_nig() {
+        let mut rng = ::test::rng(822);
+        test_nig_mean_and_variance(1.0, 0.0, &mut rng);
+        test_nig_mean_and_variance(2.0, 1.0, &mut rng);
+        test_nig_mean_and_variance(5.0, -3.0, &mut rng);
+        test_nig_mean_and_variance(100.0, 10.0, &mut rng);
+    }
+
+    #[test]
+    fn test_nig_f32() {
+        let d = NormalInverseGaussian::new(3.0f32, 2.0).unwrap();
+        let mut rng = ::test::rng(823);
+        for _ in 0..1000 {
+            assert!(d.sample(&mut rng).is_finite());
+        }
+    }
+
+    #[test]
+    fn test_nig_invalid() {
+        assert_eq!(NormalInverseGaussian::new(0.0, 0.0).unwrap_err(),
+                   NormalInverseGaussianError::AlphaNotPositive);
+        assert_eq!(NormalInverseGaussian::new(::std::f64::INFINITY, 0.0).unwrap_err(),
+                   NormalInverseGaussianError::AlphaNotPositive);
+        assert_eq!(NormalInverseGaussian::new(1.0, 1.0).unwrap_err(),
+                   NormalInverseGaussianError::AbsBetaNotLessThanAlpha);
+        assert_eq!(NormalInverseGaussian::new(1.0, -2.0).unwrap_err(),
+                   NormalInverseGaussianError::AbsBetaNotLessThanAlpha);
+        assert_eq!(NormalInverseGaussian::new(1.0, ::std::f64::NAN).unwrap_err(),
+                   NormalInverseGaussianError::AbsBetaNotLessThanAlpha);
+    }
+}
@@ -9,7 +9,8 @@
 //! The Pareto distribution.
 
 use Rng;
-use distributions::{Distribution, OpenClosed01};
+use distributions::{Distribution, Float, OpenClosed01};
+use std::{error, fmt};
 
 /// Samples floating-point numbers according to the Pareto distribution
 ///
@@ -18,33 +19,66 @@ use distributions::{Distribution, OpenClosed01};
 /// use rand::prelude::*;
 /// use rand::distributions::Pareto;
 ///
-/// let val: f64 = SmallRng::from_entropy().sample(Pareto::new(1., 2.));
+/// let val: f64 = SmallRng::from_entropy().sample(Pareto::new(1., 2.).unwrap());
 /// println!("{}", val);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Pareto {
-    scale: f64,
-    inv_neg_shape: f64,
+pub struct Pareto<F> {
+    scale: F,
+    inv_neg_shape: F,
 }
 
-impl Pareto {
+/// Error type returned from `Pareto::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParetoError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+    /// `shape <= 0` or `nan`.
+    ShapeNotPositive,
+}
+
+impl ParetoError {
+    fn msg(&self) -> &str {
+        match *self {
+            ParetoError::ScaleNotPositive => "scale is not positive in Pareto distribution",
+            ParetoError::ShapeNotPositive => "shape is not positive in Pareto distribution",
+        }
+    }
+}
+
+impl fmt::Display for ParetoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for ParetoError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Pareto<F> {
     /// Construct a new Pareto distribution with given `scale` and `shape`.
     ///
     /// In the literature, `scale` is commonly written as x<sub>m</sub> or k and
     /// `shape` is often written as α.
     ///
-    /// # Panics
-    ///
-    /// `scale` and `shape` have to be non-zero and positive.
-    pub fn new(scale: f64, shape: f64) -> Pareto {
-        assert!((scale > 0.) & (shape > 0.));
-        Pareto { scale, inv_neg_shape: -1.0 / shape }
+    /// Returns an error unless `scale` and `shape` are both positive.
+    pub fn new(scale: F, shape: F) -> Result<Pareto<F>, ParetoError> {
+        if !(scale > F::zero()) {
+            return Err(ParetoError::ScaleNotPositive);
+        }
+        if !(shape > F::zero()) {
+            return Err(ParetoError::ShapeNotPositive);
+        }
+        Ok(Pareto { scale, inv_neg_shape: -F::one() / shape })
     }
 }
 
-impl Distribution<f64> for Pareto {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let u: f64 = rng.sample(OpenClosed01);
+impl<F: Float> Distribution<F> for Pareto<F> where OpenClosed01: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.sample(OpenClosed01);
         self.scale * u.powf(self.inv_neg_shape)
     }
 }
@@ -52,19 +86,19 @@ impl Distribution<f64> for Pareto {
 #[cfg(test)]
 mod tests {
     use distributions::Distribution;
-    use super::Pareto;
+    use super::{Pareto, ParetoError};
 
     #[test]
-    #[should_panic]
     fn invalid() {
-        Pareto::new(0., 0.);
+        assert_eq!(Pareto::new(0., 0.).unwrap_err(), ParetoError::ScaleNotPositive);
+        assert_eq!(Pareto::new(1., -1.).unwrap_err(), ParetoError::ShapeNotPositive);
     }
 
     #[test]
     fn sample() {
         let scale = 1.0;
         let shape = 2.0;
-        let d = Pareto::new(scale, shape);
+        let d = Pareto::new(scale, shape).unwrap();
         let mut rng = ::test::rng(1);
         for _ in 0..1000 {
             let r = d.sample(&mut rng);
@@ -0,0 +1,316 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Piecewise constant and piecewise linear distributions.
+
+use Rng;
+use distributions::{Distribution, Standard, Uniform, WeightedIndex};
+use std::{error, fmt};
+
+/// A distribution with a density which is constant on each of a sequence
+/// of intervals, i.e. a histogram.
+///
+/// The intervals are given by their `n + 1` edges, and the density on each
+/// of the `n` intervals by a non-negative weight. The density need not be
+/// normalized: the probability of an interval is proportional to its density
+/// times its width. Within an interval, values are uniformly distributed.
+///
+/// This is similar to `std::piecewise_constant_distribution` in C++.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, PiecewiseConstant};
+///
+/// // A histogram of latencies, with bins [0, 10), [10, 20) and [20, 100)
+/// let d = PiecewiseConstant::new(&[0.0, 10.0, 20.0, 100.0], &[5.0, 3.0, 0.1]).unwrap();
+/// let v = d.sample(&mut rand::thread_rng());
+/// assert!(v >= 0.0 && v < 100.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PiecewiseConstant {
+    edges: Vec<f64>,
+    densities: Vec<f64>,
+    index: WeightedIndex<f64>,
+    intervals: Vec<Uniform<f64>>,
+}
+
+/// A distribution with a density which is linear on each of a sequence of
+/// intervals.
+///
+/// The intervals are given by their `n + 1` edges, and the density by its
+/// non-negative value at each of the edges, between which it is linearly
+/// interpolated. The density need not be normalized.
+///
+/// This is similar to `std::piecewise_linear_distribution` in C++.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, PiecewiseLinear};
+///
+/// // A triangular density on [0, 2] with its peak at 1
+/// let d = PiecewiseLinear::new(&[0.0, 1.0, 2.0], &[0.0, 1.0, 0.0]).unwrap();
+/// let v = d.sample(&mut rand::thread_rng());
+/// assert!(v >= 0.0 && v <= 2.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PiecewiseLinear {
+    edges: Vec<f64>,
+    densities: Vec<f64>,
+    index: WeightedIndex<f64>,
+}
+
+/// Error type returned from `PiecewiseConstant::new` and
+/// `PiecewiseLinear::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PiecewiseError {
+    /// There are fewer than two edges.
+    TooFewEdges,
+    /// The edges are not finite and strictly increasing.
+    InvalidEdges,
+    /// The number of densities does not match the number of edges.
+    LengthMismatch,
+    /// A density is negative, infinite or `nan`.
+    InvalidDensity,
+    /// The density is zero everywhere.
+    ZeroMass,
+}
+
+impl PiecewiseError {
+    fn msg(&self) -> &str {
+        match *self {
+            PiecewiseError::TooFewEdges =>
+                "fewer than two edges in piecewise distribution",
+            PiecewiseError::InvalidEdges =>
+                "edges are not finite and strictly increasing in piecewise distribution",
+            PiecewiseError::LengthMismatch =>
+                "number of densities does not match number of edges in piecewise distribution",
+            PiecewiseError::InvalidDensity =>
+                "density is negative or not finite in piecewise distribution",
+            PiecewiseError::ZeroMass =>
+                "density is zero everywhere in piecewise distribution",
+        }
+    }
+}
+
+impl fmt::Display for PiecewiseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for PiecewiseError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+/// Check the edges and densities, and compute the probability weight of
+/// each interval with the given function of its width and the densities at
+/// its edges or in its interior.
+fn interval_weights<F>(edges: &[f64], densities: &[f64], num_densities: usize, weight: F)
+    -> Result<WeightedIndex<f64>, PiecewiseError>
+    where F: Fn(usize, f64) -> f64
+{
+    if edges.len() < 2 {
+        return Err(PiecewiseError::TooFewEdges);
+    }
+    if edges.windows(2).any(|w| !(w[0] < w[1] && (w[1] - w[0]).is_finite())) {
+        return Err(PiecewiseError::InvalidEdges);
+    }
+    if densities.len() != num_densities {
+        return Err(PiecewiseError::LengthMismatch);
+    }
+    if !densities.iter().all(|&d| d >= 0.0 && d.is_finite()) {
+        return Err(PiecewiseError::InvalidDensity);
+    }
+    let mut weights = Vec::with_capacity(edges.len() - 1);
+    for i in 0..edges.len() - 1 {
+        let w = weight(i, edges[i + 1] - edges[i]);
+        if !w.is_finite() {
+            return Err(PiecewiseError::InvalidDensity);
+        }
+        weights.push(w);
+    }
+    WeightedIndex::new(&weights).map_err(|_| PiecewiseError::ZeroMass)
+}
+
+impl PiecewiseConstant {
+    /// Construct a new `PiecewiseConstant` distribution with the given
+    /// interval `edges`, and the density on each interval.
+    ///
+    /// Returns an error unless there are at least two edges, the edges are
+    /// finite and strictly increasing, there is one density less than there
+    /// are edges, and the densities are non-negative and finite and not all
+    /// zero.
+    pub fn new(edges: &[f64], densities: &[f64]) -> Result<PiecewiseConstant, PiecewiseError> {
+        let index = interval_weights(edges, densities, edges.len().saturating_sub(1),
+                                     |i, width| densities[i] * width)?;
+        let intervals = edges.windows(2).map(|w| Uniform::new(w[0], w[1])).collect();
+        Ok(PiecewiseConstant {
+            edges: edges.to_vec(),
+            densities: densities.to_vec(),
+            index,
+            intervals,
+        })
+    }
+
+    /// Returns the interval edges.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Returns the density on each interval, as given to `new`.
+    pub fn densities(&self) -> &[f64] {
+        &self.densities
+    }
+}
+
+impl Distribution<f64> for PiecewiseConstant {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let i = self.index.sample(rng);
+        self.intervals[i].sample(rng)
+    }
+}
+
+impl PiecewiseLinear {
+    /// Construct a new `PiecewiseLinear` distribution with the given
+    /// interval `edges`, and the density at each edge.
+    ///
+    /// Returns an error unless there are at least two edges, the edges are
+    /// finite and strictly increasing, there are as many densities as edges,
+    /// and the densities are non-negative and finite and not all zero.
+    pub fn new(edges: &[f64], densities: &[f64]) -> Result<PiecewiseLinear, PiecewiseError> {
+        let index = interval_weights(edges, densities, edges.len(),
+                                     |i, width| 0.5 * (densities[i] + densities[i + 1]) * width)?;
+        Ok(PiecewiseLinear {
+            edges: edges.to_vec(),
+            densities: densities.to_vec(),
+            index,
+        })
+    }
+
+    /// Returns the interval edges.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Returns the density at each edge, as given to `new`.
+    pub fn densities(&self) -> &[f64] {
+        &self.densities
+    }
+}
+
+impl Distribution<f64> for PiecewiseLinear {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let i = self.index.sample(rng);
+        let (low, high) = (self.edges[i], self.edges[i + 1]);
+        let (d0, d1) = (self.densities[i], self.densities[i + 1]);
+        let u: f64 = rng.sample(Standard);
+        // Invert the CDF within the interval, scaled to `[0, 1]`:
+        // `d0 * t + (d1 - d0) * t^2 / 2 = u * (d0 + d1) / 2`. The root is
+        // written in a form without cancellation, which also covers `d0 == d1`.
+        let t = if d0 == 0.0 {
+            u.sqrt()
+        } else {
+            u * (d0 + d1) / (d0 + (d0 * d0 + u * (d1 * d1 - d0 * d0)).sqrt())
+        };
+        (low + t * (high - low)).min(high)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{PiecewiseConstant, PiecewiseLinear, PiecewiseError};
+
+    #[test]
+    fn test_piecewise_constant() {
+        let mut rng = ::test::rng(872);
+        // The intervals have probabilities 0.2, 0.0 and 0.8.
+        let d = PiecewiseConstant::new(&[0.0, 1.0, 2.0, 4.0], &[1.0, 0.0, 2.0]).unwrap();
+        assert_eq!(d.edges(), &[0.0, 1.0, 2.0, 4.0]);
+        assert_eq!(d.densities(), &[1.0, 0.0, 2.0]);
+        const N: usize = 10000;
+        let mut counts = [0; 4];
+        for _ in 0..N {
+            let x = d.sample(&mut rng);
+            assert!(x >= 0.0 && x < 4.0);
+            assert!(x < 1.0 || x >= 2.0);
+            counts[x as usize] += 1;
+        }
+        assert!((counts[0] as f64 / N as f64 - 0.2).abs() < 0.02, "{:?}", counts);
+        assert!((counts[2] as f64 / N as f64 - 0.4).abs() < 0.02, "{:?}", counts);
+        assert!((counts[3] as f64 / N as f64 - 0.4).abs() < 0.02, "{:?}", counts);
+    }
+
+    #[test]
+    fn test_piecewise_linear() {
+        let mut rng = ::test::rng(873);
+        // Triangular density on [0, 2] with its peak at 1, and a density
+        // increasing linearly on [0, 1].
+        let triangle = PiecewiseLinear::new(&[0.0, 1.0, 2.0], &[0.0, 1.0, 0.0]).unwrap();
+        let ramp = PiecewiseLinear::new(&[0.0, 1.0], &[0.0, 3.0]).unwrap();
+        let flat = PiecewiseLinear::new(&[-1.0, 1.0], &[2.0, 2.0]).unwrap();
+        const N: usize = 10000;
+        let (mut sum_triangle, mut sum_ramp, mut sum_flat) = (0.0, 0.0, 0.0);
+        let mut below_half = 0;
+        for _ in 0..N {
+            let x = triangle.sample(&mut rng);
+            assert!(x >= 0.0 && x <= 2.0);
+            if x < 0.5 {
+                below_half += 1;
+            }
+            sum_triangle += x;
+            let y = ramp.sample(&mut rng);
+            assert!(y >= 0.0 && y <= 1.0);
+            sum_ramp += y;
+            let z = flat.sample(&mut rng);
+            assert!(z >= -1.0 && z <= 1.0);
+            sum_flat += z;
+        }
+        let n = N as f64;
+        // P(x < 0.5) = 0.125 for the triangle
+        assert!((below_half as f64 / n - 0.125).abs() < 0.015, "{}", below_half);
+        // Mean 1 and variance 1/6
+        assert!((sum_triangle / n - 1.0).abs() < 4.0 * (1.0 / 6.0 / n).sqrt());
+        // Mean 2/3 and variance 1/18
+        assert!((sum_ramp / n - 2.0 / 3.0).abs() < 4.0 * (1.0 / 18.0 / n).sqrt());
+        // Mean 0 and variance 1/3
+        assert!((sum_flat / n).abs() < 4.0 * (1.0 / 3.0 / n).sqrt());
+    }
+
+    #[test]
+    fn test_piecewise_invalid() {
+        assert_eq!(PiecewiseConstant::new(&[1.0], &[]).unwrap_err(),
+                   PiecewiseError::TooFewEdges);
+        assert_eq!(PiecewiseLinear::new(&[], &[]).unwrap_err(),
+                   PiecewiseError::TooFewEdges);
+        assert_eq!(PiecewiseConstant::new(&[0.0, 2.0, 1.0], &[1.0, 1.0]).unwrap_err(),
+                   PiecewiseError::InvalidEdges);
+        assert_eq!(PiecewiseLinear::new(&[0.0, 0.0], &[1.0, 1.0]).unwrap_err(),
+                   PiecewiseError::InvalidEdges);
+        assert_eq!(PiecewiseLinear::new(&[0.0, ::std::f64::NAN], &[1.0, 1.0]).unwrap_err(),
+                   PiecewiseError::InvalidEdges);
+        assert_eq!(PiecewiseConstant::new(&[0.0, 1.0], &[1.0, 1.0]).unwrap_err(),
+                   PiecewiseError::LengthMismatch);
+        assert_eq!(PiecewiseLinear::new(&[0.0, 1.0], &[1.0]).unwrap_err(),
+                   PiecewiseError::LengthMismatch);
+        assert_eq!(PiecewiseConstant::new(&[0.0, 1.0], &[-1.0]).unwrap_err(),
+                   PiecewiseError::InvalidDensity);
+        assert_eq!(PiecewiseLinear::new(&[0.0, 1.0], &[1.0, ::std::f64::NAN]).unwrap_err(),
+                   PiecewiseError::InvalidDensity);
+        assert_eq!(PiecewiseConstant::new(&[-1e300, 1e300], &[1e300]).unwrap_err(),
+                   PiecewiseError::InvalidDensity);
+        assert_eq!(PiecewiseConstant::new(&[0.0, 1.0, 2.0], &[0.0, 0.0]).unwrap_err(),
+                   PiecewiseError::ZeroMass);
+        assert_eq!(PiecewiseLinear::new(&[0.0, 1.0], &[0.0, 0.0]).unwrap_err(),
+                   PiecewiseError::ZeroMass);
+    }
+}
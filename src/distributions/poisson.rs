@@ -12,6 +12,7 @@
 use Rng;
 use distributions::{Distribution, Cauchy};
 use distributions::utils::log_gamma;
+use std::{error, fmt};
 
 /// The Poisson distribution `Poisson(lambda)`.
 ///
@@ -23,7 +24,7 @@ use distributions::utils::log_gamma;
 /// ```
 /// use rand::distributions::{Poisson, Distribution};
 ///
-/// let poi = Poisson::new(2.0);
+/// let poi = Poisson::new(2.0).unwrap();
 /// let v = poi.sample(&mut rand::thread_rng());
 /// println!("{} is from a Poisson(2) distribution", v);
 /// ```
@@ -37,19 +38,48 @@ pub struct Poisson {
     magic_val: f64,
 }
 
+/// Error type returned from `Poisson::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoissonError {
+    /// `lambda <= 0` or `nan`.
+    LambdaNotPositive,
+}
+
+impl PoissonError {
+    fn msg(&self) -> &str {
+        match *self {
+            PoissonError::LambdaNotPositive => "lambda is not positive in Poisson distribution",
+        }
+    }
+}
+
+impl fmt::Display for PoissonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for PoissonError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
 impl Poisson {
     /// Construct a new `Poisson` with the given shape parameter
-    /// `lambda`. Panics if `lambda <= 0`.
-    pub fn new(lambda: f64) -> Poisson {
-        assert!(lambda > 0.0, "Poisson::new called with lambda <= 0");
+    /// `lambda`. Returns an error if `lambda <= 0`.
+    pub fn new(lambda: f64) -> Result<Poisson, PoissonError> {
+        if !(lambda > 0.0) {
+            return Err(PoissonError::LambdaNotPositive);
+        }
         let log_lambda = lambda.ln();
-        Poisson {
+        Ok(Poisson {
             lambda,
             exp_lambda: (-lambda).exp(),
             log_lambda,
             sqrt_2lambda: (2.0 * lambda).sqrt(),
             magic_val: lambda * log_lambda - log_gamma(1.0 + lambda),
-        }
+        })
     }
 }
 
@@ -73,7 +103,7 @@ impl Distribution<u64> for Poisson {
 
             // we use the Cauchy distribution as the comparison distribution
             // f(x) ~ 1/(1+x^2)
-            let cauchy = Cauchy::new(0.0, 1.0);
+            let cauchy = Cauchy::new(0.0, 1.0).unwrap();
 
             loop {
                 let mut result;
@@ -114,11 +144,11 @@ impl Distribution<u64> for Poisson {
 #[cfg(test)]
 mod test {
     use distributions::Distribution;
-    use super::Poisson;
+    use super::{Poisson, PoissonError};
 
     #[test]
     fn test_poisson_10() {
-        let poisson = Poisson::new(10.0);
+        let poisson = Poisson::new(10.0).unwrap();
         let mut rng = ::test::rng(123);
         let mut sum = 0;
         for _ in 0..1000 {
@@ -132,7 +162,7 @@ mod test {
     #[test]
     fn test_poisson_15() {
         // Take the 'high expected values' path
-        let poisson = Poisson::new(15.0);
+        let poisson = Poisson::new(15.0).unwrap();
         let mut rng = ::test::rng(123);
         let mut sum = 0;
         for _ in 0..1000 {
@@ -144,14 +174,12 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
     fn test_poisson_invalid_lambda_zero() {
-        Poisson::new(0.0);
+        assert_eq!(Poisson::new(0.0).unwrap_err(), PoissonError::LambdaNotPositive);
     }
 
     #[test]
-    #[should_panic]
     fn test_poisson_invalid_lambda_neg() {
-        Poisson::new(-10.0);
+        assert_eq!(Poisson::new(-10.0).unwrap_err(), PoissonError::LambdaNotPositive);
     }
 }
@@ -0,0 +1,303 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rejection sampling from a custom density.
+
+use Rng;
+use distributions::{Distribution, Standard, Uniform};
+use std::{error, fmt};
+
+/// The minimum default number of proposals tried for a sample by a
+/// `RejectionSampler`.
+const MIN_DEFAULT_ATTEMPTS: usize = 1000;
+/// The default number of proposals tried for a sample, relative to `bound`.
+const ATTEMPTS_PER_BOUND: f64 = 50.0;
+
+/// The default maximum number of proposals tried for a sample, where `bound`
+/// bounds the ratio of the target and proposal densities: enough that a
+/// normalized target density fails with probability below `exp(-50)`.
+fn default_max_attempts(bound: f64) -> usize {
+    let attempts = ATTEMPTS_PER_BOUND * bound;
+    if attempts >= ::std::usize::MAX as f64 {
+        ::std::usize::MAX
+    } else if attempts > MIN_DEFAULT_ATTEMPTS as f64 {
+        attempts.ceil() as usize
+    } else {
+        MIN_DEFAULT_ATTEMPTS
+    }
+}
+
+/// A distribution given by a (not necessarily normalized) density, and
+/// sampled by rejection from a proposal distribution.
+///
+/// A value `x` is drawn from the proposal distribution and accepted with
+/// probability `target_pdf(x) / (bound * proposal_pdf(x))`; otherwise a new
+/// value is drawn. This requires `target_pdf(x) <= bound * proposal_pdf(x)`
+/// for all `x`, which is not checked, and takes `bound` attempts on
+/// average for normalized densities.
+///
+/// [`RejectionSampler::new`] uses a uniform proposal distribution over the
+/// support `[low, high)` of the target density, and `bound` is then an upper
+/// bound of the target density. Use [`RejectionSampler::with_proposal_pdf`]
+/// to specify a non-uniform proposal distribution and its density.
+///
+/// Sampling gives up after a maximum number of attempts, which can be
+/// changed with [`with_max_attempts`]. [`try_sample`] then returns an error,
+/// while [`Distribution::sample`] panics. The default is `50 * bound`
+/// attempts, and at least 1000: for a normalized target density the
+/// acceptance rate is `1 / bound`, so a sample then fails with probability
+/// below `exp(-50)`. With [`RejectionSampler::new`], the density of the
+/// proposal distribution is `1 / (high - low)`, so the acceptance rate and
+/// default are computed with `bound * (high - low)` in place of `bound`. If
+/// the target density integrates to `m` instead of `1`, the acceptance rate
+/// is `m` times as large, and the limit may need to be raised for small
+/// `m`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, RejectionSampler};
+///
+/// // The semicircle distribution on [-1, 1]
+/// let d = RejectionSampler::new(|x: f64| (1.0 - x * x).sqrt(), -1.0, 1.0, 1.0)
+///     .unwrap();
+/// let v = d.sample(&mut rand::thread_rng());
+/// assert!(v >= -1.0 && v < 1.0);
+/// ```
+///
+/// [`RejectionSampler::new`]: RejectionSampler::new
+/// [`RejectionSampler::with_proposal_pdf`]: RejectionSampler::with_proposal_pdf
+/// [`with_max_attempts`]: RejectionSampler::with_max_attempts
+/// [`try_sample`]: RejectionSampler::try_sample
+/// [`Distribution::sample`]: Distribution::sample
+#[derive(Clone, Copy, Debug)]
+pub struct RejectionSampler<P, D, G> {
+    target_pdf: P,
+    proposal: D,
+    proposal_pdf: G,
+    bound: f64,
+    max_attempts: usize,
+}
+
+/// Error type returned from `RejectionSampler`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectionError {
+    /// `bound <= 0`, infinite or `nan`.
+    BoundNotPositive,
+    /// `low >= high`, `high - low` is infinite, or either is `nan`.
+    InvalidSupport,
+    /// No proposal was accepted within the maximum number of attempts.
+    AttemptsExhausted,
+}
+
+impl RejectionError {
+    fn msg(&self) -> &str {
+        match *self {
+            RejectionError::BoundNotPositive =>
+                "bound is not positive and finite in rejection sampler",
+            RejectionError::InvalidSupport =>
+                "support is empty or not finite in rejection sampler",
+            RejectionError::AttemptsExhausted =>
+                "no proposal accepted within maximum attempts in rejection sampler",
+        }
+    }
+}
+
+impl fmt::Display for RejectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for RejectionError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+fn unit_pdf(_: f64) -> f64 {
+    1.0
+}
+
+impl<P> RejectionSampler<P, Uniform<f64>, fn(f64) -> f64>
+    where P: Fn(f64) -> f64
+{
+    /// Construct a new `RejectionSampler` for the density `target_pdf` with
+    /// support `[low, high)`, and an upper `bound` of the density.
+    ///
+    /// Returns an error unless `bound` is positive and finite, and `low <
+    /// high` with `high - low` finite.
+    pub fn new(target_pdf: P, low: f64, high: f64, bound: f64)
+        -> Result<Self, RejectionError>
+    {
+        if !(low < high && (high - low).is_finite()) {
+            return Err(RejectionError::InvalidSupport);
+        }
+        // Comparing `target_pdf(x)` with `bound` rather than with the ratio
+        // `bound * (high - low)` of the densities accepts the same values.
+        let sampler = RejectionSampler::with_proposal_pdf(
+            target_pdf, Uniform::new(low, high), unit_pdf as fn(f64) -> f64, bound)?;
+        Ok(sampler.with_max_attempts(default_max_attempts(bound * (high - low))))
+    }
+}
+
+impl<P, D, G> RejectionSampler<P, D, G>
+    where P: Fn(f64) -> f64, D: Distribution<f64>, G: Fn(f64) -> f64
+{
+    /// Construct a new `RejectionSampler` for the density `target_pdf`, with
+    /// the given `proposal` distribution with density `proposal_pdf`, such
+    /// that `target_pdf(x) <= bound * proposal_pdf(x)` for all `x`.
+    ///
+    /// Returns an error unless `bound` is positive and finite.
+    pub fn with_proposal_pdf(target_pdf: P, proposal: D, proposal_pdf: G, bound: f64)
+        -> Result<RejectionSampler<P, D, G>, RejectionError>
+    {
+        if !(bound > 0.0 && bound.is_finite()) {
+            return Err(RejectionError::BoundNotPositive);
+        }
+        Ok(RejectionSampler {
+            target_pdf,
+            proposal,
+            proposal_pdf,
+            bound,
+            max_attempts: default_max_attempts(bound),
+        })
+    }
+
+    /// Set the maximum number of proposals tried for a sample.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sample by rejection, giving up after the maximum number of attempts.
+    ///
+    /// Returns an error if no proposal was accepted.
+    pub fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<f64, RejectionError> {
+        for _ in 0..self.max_attempts {
+            let x = self.proposal.sample(rng);
+            let u: f64 = rng.sample(Standard);
+            if u * self.bound * (self.proposal_pdf)(x) < (self.target_pdf)(x) {
+                return Ok(x);
+            }
+        }
+        Err(RejectionError::AttemptsExhausted)
+    }
+}
+
+impl<P, D, G> Distribution<f64> for RejectionSampler<P, D, G>
+    where P: Fn(f64) -> f64, D: Distribution<f64>, G: Fn(f64) -> f64
+{
+    /// Sample by rejection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_sample`] returns an error.
+    ///
+    /// [`try_sample`]: RejectionSampler::try_sample
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self.try_sample(rng) {
+            Ok(x) => x,
+            Err(e) => panic!("RejectionSampler: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::{Distribution, Exp};
+    use super::{RejectionSampler, RejectionError};
+
+    #[test]
+    fn test_rejection_uniform_proposal() {
+        let mut rng = ::test::rng(881);
+        // Density 2x on [0, 1]: mean 2/3 and variance 1/18
+        let d = RejectionSampler::new(|x| 2.0 * x, 0.0, 1.0, 2.0).unwrap();
+        let n = 10000;
+        let mean = d.sample_iter(&mut rng).take(n).sum::<f64>() / n as f64;
+        assert!((mean - 2.0 / 3.0).abs() < 4.0 * (1.0 / 18.0 / n as f64).sqrt(), "{}", mean);
+    }
+
+    #[test]
+    fn test_rejection_proposal_pdf() {
+        let mut rng = ::test::rng(882);
+        // Half-normal density from an exponential proposal with rate 1:
+        // f(x) / g(x) = sqrt(2 / pi) exp(x - x^2 / 2) <= sqrt(2 e / pi)
+        let half_normal = |x: f64| (2.0 / ::std::f64::consts::PI).sqrt() * (-0.5 * x * x).exp();
+        let bound = (2.0 * ::std::f64::consts::E / ::std::f64::consts::PI).sqrt();
+        let d = RejectionSampler::with_proposal_pdf(
+            half_normal, Exp::new(1.0).unwrap(), |x: f64| (-x).exp(), bound).unwrap();
+        let n = 10000;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let x = d.try_sample(&mut rng).unwrap();
+            assert!(x >= 0.0);
+            sum += x;
+        }
+        // Mean sqrt(2 / pi) and variance 1 - 2 / pi
+        let expected_mean = (2.0 / ::std::f64::consts::PI).sqrt();
+        let expected_variance = 1.0 - 2.0 / ::std::f64::consts::PI;
+        let mean = sum / n as f64;
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_variance / n as f64).sqrt(),
+                "{}", mean);
+    }
+
+    #[test]
+    fn test_rejection_low_acceptance() {
+        let mut rng = ::test::rng(885);
+        // A peak of width 1e-4 and height 1e4 on [0, 1]: acceptance rate 1e-4
+        let peak = |x: f64| if x >= 0.5 && x < 0.5001 { 1e4 } else { 0.0 };
+        let d = RejectionSampler::new(peak, 0.0, 1.0, 1e4).unwrap();
+        for _ in 0..20 {
+            let x = d.try_sample(&mut rng).unwrap();
+            assert!(x >= 0.5 && x < 0.5001);
+        }
+    }
+
+    #[test]
+    fn test_rejection_wide_support() {
+        let mut rng = ::test::rng(886);
+        // The standard normal density on [-1e4, 1e4]: acceptance rate 1.25e-4
+        let normal = |x: f64| (-0.5 * x * x).exp() / (2.0 * ::std::f64::consts::PI).sqrt();
+        let d = RejectionSampler::new(normal, -1e4, 1e4, 0.4).unwrap();
+        let n = 100;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let x = d.try_sample(&mut rng).unwrap();
+            assert!(x.abs() < 10.0);
+            sum += x;
+        }
+        assert!((sum / n as f64).abs() < 0.4);
+    }
+
+    #[test]
+    fn test_rejection_errors() {
+        let mut rng = ::test::rng(883);
+        assert_eq!(RejectionSampler::new(|x| x, 0.0, 1.0, 0.0).err(),
+                   Some(RejectionError::BoundNotPositive));
+        assert_eq!(RejectionSampler::new(|x| x, 0.0, 1.0, ::std::f64::NAN).err(),
+                   Some(RejectionError::BoundNotPositive));
+        assert_eq!(RejectionSampler::new(|x| x, 1.0, 1.0, 1.0).err(),
+                   Some(RejectionError::InvalidSupport));
+        assert_eq!(RejectionSampler::new(|x| x, 0.0, ::std::f64::NAN, 1.0).err(),
+                   Some(RejectionError::InvalidSupport));
+        assert_eq!(RejectionSampler::new(|x| x, -1e308, 1e308, 1.0).err(),
+                   Some(RejectionError::InvalidSupport));
+
+        let never = RejectionSampler::new(|_| 0.0, 0.0, 1.0, 1.0).unwrap()
+            .with_max_attempts(10);
+        assert_eq!(never.try_sample(&mut rng), Err(RejectionError::AttemptsExhausted));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rejection_exhausted() {
+        let d = RejectionSampler::new(|_| 0.0, 0.0, 1.0, 1.0).unwrap();
+        d.sample(&mut ::test::rng(884));
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The skew-normal distribution.
+
+use Rng;
+use distributions::{Distribution, Float, StandardNormal};
+use std::{error, fmt};
+
+/// The skew-normal distribution `SN(location, scale, shape)`.
+///
+/// This distribution has density function:
+/// `f(x) = 2 / scale * phi(z) * Phi(shape * z)`, with
+/// `z = (x - location) / scale`, where `phi` and `Phi` are the density and
+/// cumulative distribution functions of the standard normal distribution.
+/// Positive `shape` skews the distribution to the right and negative `shape`
+/// to the left; for `shape = 0` this is the [`Normal`] distribution.
+///
+/// With `delta = shape / sqrt(1 + shape^2)`, the mean is
+/// `location + scale * delta * sqrt(2 / pi)` and the variance is
+/// `scale^2 * (1 - 2 delta^2 / pi)`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, SkewNormal};
+///
+/// let sn = SkewNormal::new(0.0, 1.0, 4.0).unwrap();
+/// let v = sn.sample(&mut rand::thread_rng());
+/// println!("{} is from a SN(0, 1, 4) distribution", v);
+/// ```
+///
+/// [`Normal`]: crate::distributions::Normal
+#[derive(Clone, Copy, Debug)]
+pub struct SkewNormal<F> {
+    location: F,
+    scale: F,
+    /// `shape / sqrt(1 + shape^2)`.
+    delta: F,
+    /// `sqrt(1 - delta^2)`.
+    delta_complement: F,
+}
+
+/// Error type returned from `SkewNormal::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkewNormalError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+    /// `shape` is infinite or `nan`.
+    ShapeNotFinite,
+}
+
+impl SkewNormalError {
+    fn msg(&self) -> &str {
+        match *self {
+            SkewNormalError::ScaleNotPositive =>
+                "scale is not positive in skew-normal distribution",
+            SkewNormalError::ShapeNotFinite =>
+                "shape is not finite in skew-normal distribution",
+        }
+    }
+}
+
+impl fmt::Display for SkewNormalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for SkewNormalError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> SkewNormal<F> {
+    /// Construct a new `SkewNormal` with the given `location`, `scale` and
+    /// `shape`.
+    ///
+    /// Returns an error if `scale <= 0` or `shape` is not finite.
+    pub fn new(location: F, scale: F, shape: F) -> Result<SkewNormal<F>, SkewNormalError> {
+        if !(scale > F::zero()) {
+            return Err(SkewNormalError::ScaleNotPositive);
+        }
+        if !shape.is_finite() {
+            return Err(SkewNormalError::ShapeNotFinite);
+        }
+        // `sqrt(1 + shape^2)`, computed without overflow for large `shape`.
+        let norm = if shape.abs() > F::one() {
+            let t = F::one() / shape;
+            shape.abs() * (F::one() + t * t).sqrt()
+        } else {
+            (F::one() + shape * shape).sqrt()
+        };
+        Ok(SkewNormal {
+            location,
+            scale,
+            delta: shape / norm,
+            delta_complement: F::one() / norm,
+        })
+    }
+}
+
+impl<F: Float> Distribution<F> for SkewNormal<F> where StandardNormal: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        // If `(u, v)` are independent standard normals, `delta * |u| +
+        // sqrt(1 - delta^2) * v` has the standard skew-normal distribution.
+        // Flipping the sign of `v` as well as `u` keeps `v` symmetric.
+        let u: F = rng.sample(StandardNormal);
+        let v: F = rng.sample(StandardNormal);
+        let z = self.delta * u + self.delta_complement * v;
+        let z = if u >= F::zero() { z } else { -z };
+        self.location + self.scale * z
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::Distribution;
+    use super::{SkewNormal, SkewNormalError};
+
+    fn test_skew_normal_mean_and_variance<R: Rng>(
+        location: f64, scale: f64, shape: f64, rng: &mut R)
+    {
+        let d = SkewNormal::new(location, scale, shape).unwrap();
+        let delta = shape.signum() / (1.0 + 1.0 / (shape * shape)).sqrt();
+        let expected_mean = location
+            + scale * delta * (2.0 / ::std::f64::consts::PI).sqrt();
+        let expected_variance = scale * scale
+            * (1.0 - 2.0 * delta * delta / ::std::f64::consts::PI);
+
+        let mut results = [0.0f64; 10000];
+        for x in results.iter_mut() {
+            *x = d.sample(rng);
+        }
+        let n = results.len() as f64;
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_variance / n).sqrt(),
+                "mean {} expected {}", mean, expected_mean);
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0,
+                "variance {} expected {}", variance, expected_variance);
+    }
+
+    #[test]
+    fn test_skew_normal() {
+        let mut rng = ::test::rng(824);
+        test_skew_normal_mean_and_variance(0.0, 1.0, 0.0, &mut rng);
+        test_skew_normal_mean_and_variance(2.0, 3.0, 4.0, &mut rng);
+        test_skew_normal_mean_and_variance(-1.0, 0.5, -2.0, &mut rng);
+        test_skew_normal_mean_and_variance(10.0, 2.0, 1e200, &mut rng);
+    }
+
+    #[test]
+    fn test_skew_normal_large_shape() {
+        // The distribution approaches a half-normal distribution.
+        let d = SkewNormal::new(1.0, 2.0, 1e200).unwrap();
+        let mut rng = ::test::rng(825);
+        for _ in 0..1000 {
+            assert!(d.sample(&mut rng) >= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_skew_normal_f32() {
+        let d = SkewNormal::new(0.0f32, 1.0, -3.0).unwrap();
+        let mut rng = ::test::rng(826);
+        for _ in 0..1000 {
+            assert!(d.sample(&mut rng).is_finite());
+        }
+    }
+
+    #[test]
+    fn test_skew_normal_invalid() {
+        assert_eq!(SkewNormal::new(0.0, 0.0, 1.0).unwrap_err(),
+                   SkewNormalError::ScaleNotPositive);
+        assert_eq!(SkewNormal::new(0.0, ::std::f64::NAN, 1.0).unwrap_err(),
+                   SkewNormalError::ScaleNotPositive);
+        assert_eq!(SkewNormal::new(0.0, 1.0, ::std::f64::INFINITY).unwrap_err(),
+                   SkewNormalError::ShapeNotFinite);
+        assert_eq!(SkewNormal::new(0.0, 1.0, ::std::f64::NAN).unwrap_err(),
+                   SkewNormalError::ShapeNotFinite);
+    }
+}
@@ -8,7 +8,8 @@
 //! The triangular distribution.
 
 use Rng;
-use distributions::{Distribution, Standard};
+use distributions::{Distribution, Float, Standard};
+use std::{error, fmt};
 
 /// The triangular distribution.
 ///
@@ -17,44 +18,75 @@ use distributions::{Distribution, Standard};
 /// ```rust
 /// use rand::distributions::{Triangular, Distribution};
 ///
-/// let d = Triangular::new(0., 5., 2.5);
+/// let d = Triangular::new(0., 5., 2.5).unwrap();
 /// let v = d.sample(&mut rand::thread_rng());
 /// println!("{} is from a triangular distribution", v);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Triangular {
-    min: f64,
-    max: f64,
-    mode: f64,
+pub struct Triangular<F> {
+    min: F,
+    max: F,
+    mode: F,
 }
 
-impl Triangular {
+/// Error type returned from `Triangular::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriangularError {
+    /// `max <= min` or `nan`.
+    RangeTooSmall,
+    /// `mode < min` or `mode > max` or `nan`.
+    ModeRange,
+}
+
+impl TriangularError {
+    fn msg(&self) -> &str {
+        match *self {
+            TriangularError::RangeTooSmall => "max <= min in triangular distribution",
+            TriangularError::ModeRange => "mode is outside [min, max] in triangular distribution",
+        }
+    }
+}
+
+impl fmt::Display for TriangularError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for TriangularError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Triangular<F> {
     /// Construct a new `Triangular` with minimum `min`, maximum `max` and mode
     /// `mode`.
     ///
-    /// # Panics
-    ///
-    /// If `max < mode`, `mode < max` or `max == min`.
+    /// Returns an error if `max <= min`, `mode < min` or `mode > max`.
     ///
     #[inline]
-    pub fn new(min: f64, max: f64, mode: f64) -> Triangular {
-        assert!(max >= mode);
-        assert!(mode >= min);
-        assert!(max != min);
-        Triangular { min, max, mode }
+    pub fn new(min: F, max: F, mode: F) -> Result<Triangular<F>, TriangularError> {
+        if !(max > min) {
+            return Err(TriangularError::RangeTooSmall);
+        }
+        if !(mode >= min && max >= mode) {
+            return Err(TriangularError::ModeRange);
+        }
+        Ok(Triangular { min, max, mode })
     }
 }
 
-impl Distribution<f64> for Triangular {
+impl<F: Float> Distribution<F> for Triangular<F> where Standard: Distribution<F> {
     #[inline]
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let f: f64 = rng.sample(Standard);
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let f: F = rng.sample(Standard);
         let diff_mode_min = self.mode - self.min;
         let diff_max_min = self.max - self.min;
         if f * diff_max_min < diff_mode_min {
             self.min + (f * diff_max_min * diff_mode_min).sqrt()
         } else {
-            self.max - ((1. - f) * diff_max_min * (self.max - self.mode)).sqrt()
+            self.max - ((F::one() - f) * diff_max_min * (self.max - self.mode)).sqrt()
         }
     }
 }
@@ -62,7 +94,7 @@ impl Distribution<f64> for Triangular {
 #[cfg(test)]
 mod test {
     use distributions::Distribution;
-    use super::Triangular;
+    use super::{Triangular, TriangularError};
 
     #[test]
     fn test_new() {
@@ -71,13 +103,21 @@ mod test {
             (0., 1., 0.9), (-4., -0.5, -2.), (-13.039, 8.41, 1.17),
         ] {
             println!("{} {} {}", min, max, mode);
-            let _ = Triangular::new(min, max, mode);
+            let _ = Triangular::new(min, max, mode).unwrap();
         }
     }
 
+    #[test]
+    fn test_new_invalid() {
+        assert_eq!(Triangular::new(1., 1., 1.).unwrap_err(), TriangularError::RangeTooSmall);
+        assert_eq!(Triangular::new(2., 1., 1.5).unwrap_err(), TriangularError::RangeTooSmall);
+        assert_eq!(Triangular::new(0., 1., 2.).unwrap_err(), TriangularError::ModeRange);
+        assert_eq!(Triangular::new(0., 1., -1.).unwrap_err(), TriangularError::ModeRange);
+    }
+
     #[test]
     fn test_sample() {
-        let norm = Triangular::new(0., 1., 0.5);
+        let norm = Triangular::new(0., 1., 0.5).unwrap();
         let mut rng = ::test::rng(1);
         for _ in 0..1000 {
             norm.sample(&mut rng);
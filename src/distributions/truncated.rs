@@ -0,0 +1,623 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncated distributions.
+
+use Rng;
+use distributions::{Distribution, Exp1, Float, Standard, StandardNormal};
+use std::{error, fmt};
+
+/// A distribution restricted to the interval `[low, high]`.
+///
+/// This adapter works with any distribution, by sampling from it until the
+/// result falls into the interval. The expected number of samples is the
+/// inverse of the probability of the interval, so this is only efficient if
+/// that probability is not small.
+///
+/// The adapter cannot detect an interval with zero probability, so sampling
+/// gives up after a maximum number of attempts, one million by default,
+/// which can be changed with [`with_max_attempts`]. [`try_sample`] then
+/// returns an error, while [`Distribution::sample`] panics. With the default,
+/// sampling an interval with probability `p` fails with probability about
+/// `exp(-1e6 * p)`, which is negligible unless `p` is below about `1e-5`.
+///
+/// [`TruncatedNormal`] and [`TruncatedExp`] are specialized samplers for
+/// truncated normal and exponential distributions, which are efficient for
+/// any interval.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Gamma, Truncated};
+///
+/// let gamma = Gamma::new(2.0, 5.0).unwrap();
+/// let truncated = Truncated::new(gamma, 1.0, 20.0).unwrap();
+/// let v = truncated.sample(&mut rand::thread_rng());
+/// assert!(v >= 1.0 && v <= 20.0);
+/// ```
+///
+/// [`TruncatedNormal`]: crate::distributions::TruncatedNormal
+/// [`TruncatedExp`]: crate::distributions::TruncatedExp
+/// [`with_max_attempts`]: Truncated::with_max_attempts
+/// [`try_sample`]: Truncated::try_sample
+/// [`Distribution::sample`]: Distribution::sample
+#[derive(Clone, Copy, Debug)]
+pub struct Truncated<D, T> {
+    distr: D,
+    low: T,
+    high: T,
+    max_attempts: usize,
+}
+
+/// Error type returned from `Truncated`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncatedError {
+    /// `low > high`, or a bound is `nan`.
+    InvalidBounds,
+    /// No sample fell into the interval within the maximum number of
+    /// attempts.
+    AttemptsExhausted,
+}
+
+impl TruncatedError {
+    fn msg(&self) -> &str {
+        match *self {
+            TruncatedError::InvalidBounds => "low > high in truncated distribution",
+            TruncatedError::AttemptsExhausted =>
+                "no sample within interval within maximum attempts in truncated distribution",
+        }
+    }
+}
+
+impl fmt::Display for TruncatedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for TruncatedError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+/// The default maximum number of samples tried by `Truncated`.
+const DEFAULT_MAX_ATTEMPTS: usize = 1_000_000;
+
+impl<D, T: PartialOrd> Truncated<D, T> {
+    /// Construct a new `Truncated` restricting `distr` to the inclusive
+    /// interval `[low, high]`.
+    ///
+    /// Returns an error if `low > high`, or if they cannot be compared.
+    pub fn new(distr: D, low: T, high: T) -> Result<Truncated<D, T>, TruncatedError> {
+        if !(low <= high) {
+            return Err(TruncatedError::InvalidBounds);
+        }
+        Ok(Truncated { distr, low, high, max_attempts: DEFAULT_MAX_ATTEMPTS })
+    }
+
+    /// Set the maximum number of samples tried for a sample in the interval.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sample until the result falls into the interval, giving up after the
+    /// maximum number of attempts.
+    ///
+    /// Returns an error if no sample fell into the interval.
+    pub fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, TruncatedError>
+        where D: Distribution<T>
+    {
+        for _ in 0..self.max_attempts {
+            let x = self.distr.sample(rng);
+            if x >= self.low && x <= self.high {
+                return Ok(x);
+            }
+        }
+        Err(TruncatedError::AttemptsExhausted)
+    }
+}
+
+impl<D: Distribution<T>, T: PartialOrd> Distribution<T> for Truncated<D, T> {
+    /// Sample until the result falls into the interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_sample`] returns an error.
+    ///
+    /// [`try_sample`]: Truncated::try_sample
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        match self.try_sample(rng) {
+            Ok(x) => x,
+            Err(e) => panic!("Truncated: {}", e),
+        }
+    }
+}
+
+/// The normal distribution `N(mean, std_dev**2)` restricted to the interval
+/// `[low, high]`.
+///
+/// The bounds may be infinite. Depending on the interval, samples are
+/// generated by the algorithms of Robert (1995), which have a high
+/// acceptance rate for any interval, including those far in the tails:
+///
+/// - rejection from the normal distribution, for wide intervals around the
+///   mean;
+/// - rejection from a uniform distribution, for narrow intervals;
+/// - rejection from a shifted exponential distribution, for intervals in
+///   one tail.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, TruncatedNormal};
+///
+/// // The upper tail of a standard normal, far beyond 10 standard deviations
+/// let tail = TruncatedNormal::new(0.0, 1.0, 10.0, std::f64::INFINITY).unwrap();
+/// let v = tail.sample(&mut rand::thread_rng());
+/// assert!(v >= 10.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TruncatedNormal<F> {
+    mean: F,
+    std_dev: F,
+    low: F,
+    high: F,
+    /// Bounds of the standardized interval, mirrored if `flip` such that
+    /// `a > 0` for the tail methods.
+    a: F,
+    b: F,
+    flip: bool,
+    method: NormalMethod<F>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum NormalMethod<F> {
+    /// `std_dev == 0`, so the only value is the mean.
+    Point,
+    /// Rejection from the standard normal distribution.
+    Normal,
+    /// Rejection from the uniform distribution on `[a, b]`. The acceptance
+    /// probability is `exp((c^2 - z^2) / 2)`, where `c` is the point of the
+    /// interval closest to zero.
+    Uniform { c: F },
+    /// Rejection from `a + Exp(alpha)`.
+    Exponential { alpha: F },
+}
+
+/// Error type returned from `TruncatedNormal::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncatedNormalError {
+    /// `mean` is infinite or `nan`.
+    MeanNotFinite,
+    /// `std_dev < 0`, infinite or `nan`.
+    InvalidStdDev,
+    /// `low > high`, or a bound is `nan`.
+    InvalidBounds,
+    /// The interval has zero probability mass.
+    ZeroMass,
+}
+
+impl TruncatedNormalError {
+    fn msg(&self) -> &str {
+        match *self {
+            TruncatedNormalError::MeanNotFinite =>
+                "mean is not finite in truncated normal distribution",
+            TruncatedNormalError::InvalidStdDev =>
+                "std_dev is negative or not finite in truncated normal distribution",
+            TruncatedNormalError::InvalidBounds =>
+                "low > high in truncated normal distribution",
+            TruncatedNormalError::ZeroMass =>
+                "interval has zero probability in truncated normal distribution",
+        }
+    }
+}
+
+impl fmt::Display for TruncatedNormalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for TruncatedNormalError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> TruncatedNormal<F> {
+    /// Construct a new `TruncatedNormal` from the normal distribution with
+    /// the given mean and standard deviation, restricted to the inclusive
+    /// interval `[low, high]`.
+    ///
+    /// Returns an error if `mean` is not finite, if `std_dev` is negative or
+    /// not finite, if `low > high`, or if the interval has zero probability
+    /// mass: if `low == high`, or if `std_dev == 0` and `mean` is not in the
+    /// interval.
+    pub fn new(mean: F, std_dev: F, low: F, high: F)
+        -> Result<TruncatedNormal<F>, TruncatedNormalError>
+    {
+        if !mean.is_finite() {
+            return Err(TruncatedNormalError::MeanNotFinite);
+        }
+        if !(std_dev >= F::zero() && std_dev.is_finite()) {
+            return Err(TruncatedNormalError::InvalidStdDev);
+        }
+        if !(low <= high) {
+            return Err(TruncatedNormalError::InvalidBounds);
+        }
+
+        if std_dev == F::zero() {
+            if !(mean >= low && mean <= high) {
+                return Err(TruncatedNormalError::ZeroMass);
+            }
+            let zero = F::zero();
+            return Ok(TruncatedNormal {
+                mean, std_dev, low, high,
+                a: zero, b: zero, flip: false,
+                method: NormalMethod::Point,
+            });
+        }
+        if low == high {
+            return Err(TruncatedNormalError::ZeroMass);
+        }
+
+        let a = (low - mean) / std_dev;
+        let b = (high - mean) / std_dev;
+        // Use the upper tail for intervals entirely below the mean.
+        let flip = b <= F::zero();
+        let (a, b) = if flip { (-b, -a) } else { (a, b) };
+        if a == b {
+            // The standardized bounds may coincide after rounding.
+            return Err(TruncatedNormalError::ZeroMass);
+        }
+
+        let two = F::from_f64(2.0);
+        let method = if a <= F::zero() {
+            // The interval contains zero: use the uniform proposal only if
+            // the interval is narrower than `sqrt(2 pi)`.
+            if b - a < (two * F::pi()).sqrt() {
+                NormalMethod::Uniform { c: F::zero() }
+            } else {
+                NormalMethod::Normal
+            }
+        } else {
+            // `sqrt(a^2 + 4)`, without overflow for large `a`.
+            let four = F::from_f64(4.0);
+            let root = if a > F::one() {
+                a * (F::one() + four / (a * a)).sqrt()
+            } else {
+                (a * a + four).sqrt()
+            };
+            let alpha = (a + root) / two;
+            // Robert's bound on the width below which the uniform proposal
+            // is more efficient than the exponential one,
+            // `2 sqrt(e) / (a + root) * exp((a^2 - a root) / 4)`.
+            let e = F::one().exp();
+            let width = two * e.sqrt() / (a + root) * (-a / (a + root)).exp();
+            if b - a < width {
+                NormalMethod::Uniform { c: a }
+            } else {
+                NormalMethod::Exponential { alpha }
+            }
+        };
+
+        Ok(TruncatedNormal { mean, std_dev, low, high, a, b, flip, method })
+    }
+}
+
+impl<F: Float> Distribution<F> for TruncatedNormal<F>
+    where StandardNormal: Distribution<F>, Exp1: Distribution<F>, Standard: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let (a, b) = (self.a, self.b);
+        let half = F::from_f64(0.5);
+        let z = match self.method {
+            NormalMethod::Point => return self.mean,
+            NormalMethod::Normal => loop {
+                let z: F = rng.sample(StandardNormal);
+                // The interval is not mirrored in this case.
+                if z >= a && z <= b {
+                    break z;
+                }
+            },
+            NormalMethod::Uniform { c } => loop {
+                let u: F = rng.gen();
+                let z = a + (b - a) * u;
+                let v: F = rng.gen();
+                if v <= ((c - z) * (c + z) * half).exp() {
+                    break z;
+                }
+            },
+            NormalMethod::Exponential { alpha } => loop {
+                let e: F = rng.sample(Exp1);
+                let z = a + e / alpha;
+                if z > b {
+                    continue;
+                }
+                let v: F = rng.gen();
+                let d = z - alpha;
+                if v <= (-d * d * half).exp() {
+                    break z;
+                }
+            },
+        };
+        let z = if self.flip { -z } else { z };
+        let x = self.mean + self.std_dev * z;
+        // Rounding may move `x` slightly outside of the interval.
+        if x < self.low {
+            self.low
+        } else if x > self.high {
+            self.high
+        } else {
+            x
+        }
+    }
+}
+
+/// The exponential distribution `Exp(lambda)` restricted to the interval
+/// `[low, high]`.
+///
+/// The bounds may lie outside of the support `[0, ∞)` of the exponential
+/// distribution, and `high` may be infinite. Samples are generated by
+/// inversion of the cumulative distribution function, which needs a single
+/// uniform sample.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, TruncatedExp};
+///
+/// // Exponential waiting times, capped at 10
+/// let capped = TruncatedExp::new(0.5, 0.0, 10.0).unwrap();
+/// let v = capped.sample(&mut rand::thread_rng());
+/// assert!(v >= 0.0 && v <= 10.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TruncatedExp<F> {
+    /// `max(low, 0)`.
+    low: F,
+    high: F,
+    lambda_inverse: F,
+    /// `exp(-lambda * (high - low)) - 1`, the negated probability of the
+    /// interval relative to `[low, ∞)`.
+    neg_mass: F,
+}
+
+/// Error type returned from `TruncatedExp::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncatedExpError {
+    /// `lambda <= 0` or `nan`.
+    LambdaNotPositive,
+    /// `low > high`, or a bound is `nan`.
+    InvalidBounds,
+    /// The interval has zero probability mass.
+    ZeroMass,
+}
+
+impl TruncatedExpError {
+    fn msg(&self) -> &str {
+        match *self {
+            TruncatedExpError::LambdaNotPositive =>
+                "lambda is not positive in truncated exponential distribution",
+            TruncatedExpError::InvalidBounds =>
+                "low > high in truncated exponential distribution",
+            TruncatedExpError::ZeroMass =>
+                "interval has zero probability in truncated exponential distribution",
+        }
+    }
+}
+
+impl fmt::Display for TruncatedExpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for TruncatedExpError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> TruncatedExp<F> {
+    /// Construct a new `TruncatedExp` from the exponential distribution with
+    /// rate `lambda`, restricted to the inclusive interval `[low, high]`.
+    ///
+    /// Returns an error if `lambda <= 0`, if `low > high`, or if the interval
+    /// has zero probability mass: if `high <= 0` or `low == high`.
+    pub fn new(lambda: F, low: F, high: F) -> Result<TruncatedExp<F>, TruncatedExpError> {
+        if !(lambda > F::zero()) {
+            return Err(TruncatedExpError::LambdaNotPositive);
+        }
+        if !(low <= high) {
+            return Err(TruncatedExpError::InvalidBounds);
+        }
+        let low = if low > F::zero() { low } else { F::zero() };
+        if !(high > low) {
+            return Err(TruncatedExpError::ZeroMass);
+        }
+        // By memorylessness, this is `low + Exp(lambda)` truncated to
+        // `[0, high - low]`.
+        let neg_mass = (-lambda * (high - low)).exp_m1();
+        if neg_mass == F::zero() {
+            // The interval is too narrow relative to `1 / lambda`.
+            return Err(TruncatedExpError::ZeroMass);
+        }
+        Ok(TruncatedExp { low, high, lambda_inverse: F::one() / lambda, neg_mass })
+    }
+}
+
+impl<F: Float> Distribution<F> for TruncatedExp<F> where Standard: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let u: F = rng.gen();
+        let x = self.low - (u * self.neg_mass).ln_1p() * self.lambda_inverse;
+        // Rounding may move `x` slightly above `high`.
+        if x > self.high { self.high } else { x }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::{Binomial, Distribution, Exp, Normal};
+    use super::{Truncated, TruncatedError, TruncatedNormal, TruncatedNormalError,
+                TruncatedExp, TruncatedExpError};
+    use std::f64::INFINITY;
+
+    fn check_mean_and_variance<D: Distribution<f64>, R: Rng>(
+        d: D, low: f64, high: f64, expected_mean: f64, expected_variance: f64, rng: &mut R)
+    {
+        let mut results = [0.0f64; 10000];
+        for x in results.iter_mut() {
+            *x = d.sample(rng);
+            assert!(*x >= low && *x <= high, "{} not in [{}, {}]", x, low, high);
+        }
+        let n = results.len() as f64;
+        let mean = results.iter().sum::<f64>() / n;
+        let variance = results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_variance / n).sqrt(),
+                "[{}, {}]: mean {} expected {}", low, high, mean, expected_mean);
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0,
+                "[{}, {}]: variance {} expected {}", low, high, variance, expected_variance);
+    }
+
+    #[test]
+    fn test_truncated_normal() {
+        let mut rng = ::test::rng(840);
+        let cases = [
+            // (mean, std_dev, low, high, expected mean, expected variance)
+            (0.0, 1.0, -INFINITY, INFINITY, 0.0, 1.0),
+            (0.0, 1.0, 0.0, INFINITY, 0.7978845608028654, 0.3633802276324186),
+            (0.0, 1.0, 5.0, INFINITY, 5.18650396712583, 0.03269643461717564),
+            (0.0, 1.0, -1.0, 1.0, 0.0, 0.29112509477279314),
+            (0.0, 1.0, -3.0, 4.0, 0.004303964411157657, 0.9861314750158282),
+            (0.0, 1.0, 2.0, 2.1, 2.048293430255099, 0.0008313091254814609),
+            (0.0, 1.0, -INFINITY, -3.0, -3.283098654930434, 0.0705591867852764),
+            (10.0, 2.0, 9.0, 20.0, 11.018316989608353, 1.9446662166536772),
+            (1.0, 0.5, -20.0, -10.0, -10.022634314018683, 0.0005102336234017457),
+        ];
+        for &(mean, std_dev, low, high, expected_mean, expected_variance) in cases.iter() {
+            let d = TruncatedNormal::new(mean, std_dev, low, high).unwrap();
+            check_mean_and_variance(d, low, high, expected_mean, expected_variance, &mut rng);
+        }
+    }
+
+    #[test]
+    fn test_truncated_normal_edge_cases() {
+        let mut rng = ::test::rng(841);
+        let d = TruncatedNormal::new(3.0, 0.0, 1.0, 5.0).unwrap();
+        assert_eq!(d.sample(&mut rng), 3.0);
+
+        let d = TruncatedNormal::new(0.0f32, 1.0, 50.0, 50.001).unwrap();
+        for _ in 0..100 {
+            let x = d.sample(&mut rng);
+            assert!(x >= 50.0 && x <= 50.001);
+        }
+
+        let d = TruncatedNormal::new(0.0, 1.0, -INFINITY, -1e200).unwrap();
+        for _ in 0..100 {
+            assert!(d.sample(&mut rng) <= -1e200);
+        }
+    }
+
+    #[test]
+    fn test_truncated_normal_invalid() {
+        assert_eq!(TruncatedNormal::new(::std::f64::NAN, 1.0, 0.0, 1.0).unwrap_err(),
+                   TruncatedNormalError::MeanNotFinite);
+        assert_eq!(TruncatedNormal::new(0.0, -1.0, 0.0, 1.0).unwrap_err(),
+                   TruncatedNormalError::InvalidStdDev);
+        assert_eq!(TruncatedNormal::new(0.0, INFINITY, 0.0, 1.0).unwrap_err(),
+                   TruncatedNormalError::InvalidStdDev);
+        assert_eq!(TruncatedNormal::new(0.0, 1.0, 1.0, 0.0).unwrap_err(),
+                   TruncatedNormalError::InvalidBounds);
+        assert_eq!(TruncatedNormal::new(0.0, 1.0, ::std::f64::NAN, 0.0).unwrap_err(),
+                   TruncatedNormalError::InvalidBounds);
+        assert_eq!(TruncatedNormal::new(0.0, 1.0, 1.0, 1.0).unwrap_err(),
+                   TruncatedNormalError::ZeroMass);
+        assert_eq!(TruncatedNormal::new(0.0, 1.0, INFINITY, INFINITY).unwrap_err(),
+                   TruncatedNormalError::ZeroMass);
+        assert_eq!(TruncatedNormal::new(0.0, 0.0, 1.0, 2.0).unwrap_err(),
+                   TruncatedNormalError::ZeroMass);
+    }
+
+    #[test]
+    fn test_truncated_exp() {
+        let mut rng = ::test::rng(842);
+        let cases = [
+            // (lambda, low, high)
+            (1.0, 0.0, INFINITY),
+            (2.0, -5.0, 1.0),
+            (0.5, 3.0, 4.0),
+            (1.0, 100.0, 101.0),
+            (1e-3, 0.0, 1.0),
+        ];
+        for &(lambda, low, high) in cases.iter() {
+            let d = TruncatedExp::new(lambda, low, high).unwrap();
+            // Moments of `Exp(lambda)` truncated to `[0, w]`, shifted by `low`.
+            let low = if low > 0.0 { low } else { 0.0 };
+            let w = high - low;
+            let (mean, variance) = if w.is_infinite() {
+                (1.0 / lambda, 1.0 / (lambda * lambda))
+            } else {
+                let q = (-lambda * w).exp();
+                let m = 1.0 / lambda - w * q / (1.0 - q);
+                (m, 1.0 / (lambda * lambda) - w * w * q / ((1.0 - q) * (1.0 - q)))
+            };
+            check_mean_and_variance(d, low, high, low + mean, variance, &mut rng);
+        }
+    }
+
+    #[test]
+    fn test_truncated_exp_invalid() {
+        assert_eq!(TruncatedExp::new(0.0, 0.0, 1.0).unwrap_err(),
+                   TruncatedExpError::LambdaNotPositive);
+        assert_eq!(TruncatedExp::new(1.0, 2.0, 1.0).unwrap_err(),
+                   TruncatedExpError::InvalidBounds);
+        assert_eq!(TruncatedExp::new(1.0, -2.0, 0.0).unwrap_err(),
+                   TruncatedExpError::ZeroMass);
+        assert_eq!(TruncatedExp::new(1.0, 1.0, 1.0).unwrap_err(),
+                   TruncatedExpError::ZeroMass);
+        assert_eq!(TruncatedExp::new(1.0, INFINITY, INFINITY).unwrap_err(),
+                   TruncatedExpError::ZeroMass);
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut rng = ::test::rng(843);
+        let d = Truncated::new(Normal::new(0.0, 1.0).unwrap(), -1.0, 1.0).unwrap();
+        check_mean_and_variance(d, -1.0, 1.0, 0.0, 0.29112509477279314, &mut rng);
+
+        let d = Truncated::new(Binomial::new(10, 0.5).unwrap(), 3, 5).unwrap();
+        for _ in 0..100 {
+            let x = d.sample(&mut rng);
+            assert!(x >= 3 && x <= 5);
+        }
+
+        assert_eq!(Truncated::new(Normal::new(0.0, 1.0).unwrap(), 1.0, -1.0).unwrap_err(),
+                   TruncatedError::InvalidBounds);
+    }
+
+    #[test]
+    fn test_truncated_zero_mass() {
+        let mut rng = ::test::rng(844);
+        let d = Truncated::new(Exp::new(1.0).unwrap(), -2.0, -1.0).unwrap();
+        assert_eq!(d.try_sample(&mut rng), Err(TruncatedError::AttemptsExhausted));
+        let d = Truncated::new(Normal::new(0.0, 1.0).unwrap(), 0.5, 0.5).unwrap()
+            .with_max_attempts(100);
+        assert_eq!(d.try_sample(&mut rng), Err(TruncatedError::AttemptsExhausted));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_truncated_zero_mass_panics() {
+        let d = Truncated::new(Exp::new(1.0).unwrap(), -2.0, -1.0).unwrap();
+        d.sample(&mut ::test::rng(845));
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use Rng;
+use distributions::{Distribution, Exp1, StandardNormal};
+
+/// Samples uniformly from the unit ball in `N` dimensions.
+///
+/// The ball consists of the points `x` in `N`-dimensional space with
+/// `x[0]^2 + ... + x[N-1]^2 <= 1`. `UnitBall` implements
+/// `Distribution<[f64; N]>` for `N` from 1 to 32. For other dimensions, or a
+/// dimension only known at runtime, use [`UnitBall::fill`].
+///
+/// Implemented via a method by Barthe et al.[^1]: with `z` a vector of `N`
+/// independent [`StandardNormal`] values and `e` drawn from [`Exp1`], the
+/// point `z / sqrt(|z|^2 + 2 e)` is uniformly distributed in the ball.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitBall, Distribution};
+///
+/// let ball = UnitBall::new();
+/// let mut rng = rand::thread_rng();
+/// let v: [f64; 3] = ball.sample(&mut rng);
+/// println!("{:?} is from the unit ball in 3 dimensions.", v);
+///
+/// let mut w = vec![0.0; 100];
+/// ball.fill(&mut rng, &mut w);
+/// ```
+///
+/// [^1]: Barthe, F., Guédon, O., Mendelson, S. and Naor, A. (2005).
+///       [*A probabilistic approach to the geometry of the ℓp-ball.*](
+///       https://doi.org/10.1214/009117904000000874)
+///       Ann. Probab. 33, no. 2, 480--513.
+///
+/// [`StandardNormal`]: crate::distributions::StandardNormal
+/// [`Exp1`]: crate::distributions::Exp1
+/// [`UnitBall::fill`]: UnitBall::fill
+#[derive(Clone, Copy, Debug)]
+pub struct UnitBall;
+
+impl UnitBall {
+    /// Construct a new `UnitBall` distribution.
+    #[inline]
+    pub fn new() -> UnitBall {
+        UnitBall
+    }
+
+    /// Fill `dest` with a point sampled uniformly from the unit ball in
+    /// `dest.len()` dimensions.
+    pub fn fill<R: Rng + ?Sized>(&self, rng: &mut R, dest: &mut [f64]) {
+        if dest.is_empty() {
+            return;
+        }
+        loop {
+            let mut norm2 = 0.0;
+            for x in dest.iter_mut() {
+                *x = rng.sample(StandardNormal);
+                norm2 += *x * *x;
+            }
+            let e: f64 = rng.sample(Exp1);
+            // The denominator is 0 with negligible probability.
+            let denom = norm2 + 2.0 * e;
+            if denom > 0.0 {
+                let scale = 1.0 / denom.sqrt();
+                for x in dest.iter_mut() {
+                    *x *= scale;
+                }
+                return;
+            }
+        }
+    }
+}
+
+macro_rules! unit_ball_array_impls {
+    ($($n:expr),*) => {
+        $(
+            impl Distribution<[f64; $n]> for UnitBall {
+                #[inline]
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; $n] {
+                    let mut x = [0.0; $n];
+                    self.fill(rng, &mut x);
+                    x
+                }
+            }
+        )*
+    }
+}
+
+unit_ball_array_impls!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+                       17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32);
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitBall;
+
+    #[test]
+    fn unit_ball() {
+        let mut rng = ::test::rng(892);
+        // The n-th power of the norm is uniformly distributed on [0, 1].
+        const N: usize = 10000;
+        let mut sum = 0.0;
+        let mut sum_x0 = 0.0;
+        for _ in 0..N {
+            let x: [f64; 3] = UnitBall::new().sample(&mut rng);
+            let norm2: f64 = x.iter().map(|x| x * x).sum();
+            assert!(norm2 <= 1.0);
+            sum += norm2.powf(1.5);
+            sum_x0 += x[0];
+        }
+        let mean = sum / N as f64;
+        assert!((mean - 0.5).abs() < 4.0 * (1.0 / 12.0 / N as f64).sqrt(), "{}", mean);
+        // Each coordinate has mean 0 and variance 1/5 in 3 dimensions.
+        assert!((sum_x0 / N as f64).abs() < 4.0 * (0.2 / N as f64).sqrt());
+
+        let mut v = vec![0.0; 40];
+        let mut sum = 0.0;
+        for _ in 0..N {
+            UnitBall::new().fill(&mut rng, &mut v);
+            let norm2: f64 = v.iter().map(|x| x * x).sum();
+            assert!(norm2 <= 1.0);
+            sum += norm2.powf(20.0);
+        }
+        let mean = sum / N as f64;
+        assert!((mean - 0.5).abs() < 4.0 * (1.0 / 12.0 / N as f64).sqrt(), "{}", mean);
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+
+/// Samples uniformly from the unit disc in two dimensions, the interior of
+/// the [`UnitCircle`].
+///
+/// Implemented by rejection sampling from the enclosing square.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitDisc, Distribution};
+///
+/// let disc = UnitDisc::new();
+/// let v = disc.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit disc.", v)
+/// ```
+///
+/// [`UnitCircle`]: crate::distributions::UnitCircle
+#[derive(Clone, Copy, Debug)]
+pub struct UnitDisc;
+
+impl UnitDisc {
+    /// Construct a new `UnitDisc` distribution.
+    #[inline]
+    pub fn new() -> UnitDisc {
+        UnitDisc
+    }
+}
+
+impl Distribution<[f64; 2]> for UnitDisc {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        let uniform = Uniform::new(-1., 1.);
+        loop {
+            let (x1, x2) = (uniform.sample(rng), uniform.sample(rng));
+            if x1*x1 + x2*x2 <= 1. {
+                return [x1, x2];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitDisc;
+
+    #[test]
+    fn unit_disc() {
+        let mut rng = ::test::rng(893);
+        let dist = UnitDisc::new();
+        // The squared norm is uniformly distributed on [0, 1].
+        const N: usize = 10000;
+        let mut inner = 0;
+        for _ in 0..N {
+            let x = dist.sample(&mut rng);
+            let norm2 = x[0]*x[0] + x[1]*x[1];
+            assert!(norm2 <= 1.);
+            if norm2 < 0.25 {
+                inner += 1;
+            }
+        }
+        let p = inner as f64 / N as f64;
+        assert!((p - 0.25).abs() < 0.02, "{}", p);
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use Rng;
+use distributions::{Distribution, Exp1};
+
+/// Samples uniformly from the probability simplex in `N` dimensions.
+///
+/// The simplex consists of the points `x` in `N`-dimensional space with
+/// non-negative coordinates and `x[0] + ... + x[N-1] = 1`, i.e. the
+/// probability vectors over `N` outcomes. This is the [`Dirichlet`]
+/// distribution with all parameters equal to `1`.
+///
+/// `UnitSimplex` implements `Distribution<[f64; N]>` for `N` from 1 to 32.
+/// For other dimensions, or a dimension only known at runtime, use
+/// [`UnitSimplex::fill`].
+///
+/// A sample is a vector of independent [`Exp1`] values divided by their sum.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitSimplex, Distribution};
+///
+/// let simplex = UnitSimplex::new();
+/// let mut rng = rand::thread_rng();
+/// let p: [f64; 3] = simplex.sample(&mut rng);
+/// println!("{:?} is a random probability vector.", p);
+///
+/// let mut w = vec![0.0; 100];
+/// simplex.fill(&mut rng, &mut w);
+/// ```
+///
+/// [`Dirichlet`]: crate::distributions::Dirichlet
+/// [`Exp1`]: crate::distributions::Exp1
+/// [`UnitSimplex::fill`]: UnitSimplex::fill
+#[derive(Clone, Copy, Debug)]
+pub struct UnitSimplex;
+
+impl UnitSimplex {
+    /// Construct a new `UnitSimplex` distribution.
+    #[inline]
+    pub fn new() -> UnitSimplex {
+        UnitSimplex
+    }
+
+    /// Fill `dest` with a point sampled uniformly from the probability
+    /// simplex in `dest.len()` dimensions.
+    pub fn fill<R: Rng + ?Sized>(&self, rng: &mut R, dest: &mut [f64]) {
+        if dest.is_empty() {
+            return;
+        }
+        loop {
+            let mut sum = 0.0;
+            for x in dest.iter_mut() {
+                *x = rng.sample(Exp1);
+                sum += *x;
+            }
+            // The sum is 0 with negligible probability.
+            if sum > 0.0 {
+                let inv_sum = 1.0 / sum;
+                for x in dest.iter_mut() {
+                    *x *= inv_sum;
+                }
+                return;
+            }
+        }
+    }
+}
+
+macro_rules! unit_simplex_array_impls {
+    ($($n:expr),*) => {
+        $(
+            impl Distribution<[f64; $n]> for UnitSimplex {
+                #[inline]
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; $n] {
+                    let mut x = [0.0; $n];
+                    self.fill(rng, &mut x);
+                    x
+                }
+            }
+        )*
+    }
+}
+
+unit_simplex_array_impls!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+                          17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32);
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitSimplex;
+
+    #[test]
+    fn unit_simplex() {
+        let mut rng = ::test::rng(894);
+        // Each coordinate has a Beta(1, n - 1) distribution, with mean 1/n
+        // and variance (n - 1) / (n^2 (n + 1)).
+        const N: usize = 10000;
+        let mut sum = [0.0; 4];
+        for _ in 0..N {
+            let x: [f64; 4] = UnitSimplex::new().sample(&mut rng);
+            assert!(x.iter().all(|&x| x >= 0.0));
+            assert!((x.iter().sum::<f64>() - 1.0).abs() < 1e-14);
+            for i in 0..4 {
+                sum[i] += x[i];
+            }
+        }
+        let variance = 3.0 / (16.0 * 5.0);
+        for i in 0..4 {
+            let mean = sum[i] / N as f64;
+            assert!((mean - 0.25).abs() < 4.0 * (variance / N as f64).sqrt(), "{}", mean);
+        }
+
+        let x: [f64; 1] = UnitSimplex::new().sample(&mut rng);
+        assert_eq!(x, [1.0]);
+        let mut v = vec![0.0; 100];
+        UnitSimplex::new().fill(&mut rng, &mut v);
+        assert!((v.iter().sum::<f64>() - 1.0).abs() < 1e-13);
+    }
+}
@@ -7,7 +7,7 @@
 // except according to those terms.
 
 use Rng;
-use distributions::{Distribution, Uniform};
+use distributions::{Distribution, Uniform, StandardNormal};
 
 /// Samples uniformly from the surface of the unit sphere in three dimensions.
 ///
@@ -54,10 +54,91 @@ impl Distribution<[f64; 3]> for UnitSphereSurface {
     }
 }
 
+/// Samples uniformly from the surface of the unit sphere in `N` dimensions.
+///
+/// The sphere consists of the points `x` in `N`-dimensional space with
+/// `x[0]^2 + ... + x[N-1]^2 = 1`. `UnitSphere` implements
+/// `Distribution<[f64; N]>` for `N` from 1 to 32. For other dimensions, or a
+/// dimension only known at runtime, use [`UnitSphere::fill`].
+///
+/// A sample is a vector of independent [`StandardNormal`] values divided by
+/// its norm, which is uniformly distributed because the standard normal
+/// distribution in `N` dimensions is rotationally symmetric. In three
+/// dimensions, [`UnitSphereSurface`] is faster.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitSphere, Distribution};
+///
+/// let sphere = UnitSphere::new();
+/// let mut rng = rand::thread_rng();
+/// let v: [f64; 4] = sphere.sample(&mut rng);
+/// println!("{:?} is from the unit sphere surface in 4 dimensions.", v);
+///
+/// let mut w = vec![0.0; 100];
+/// sphere.fill(&mut rng, &mut w);
+/// ```
+///
+/// [`StandardNormal`]: crate::distributions::StandardNormal
+/// [`UnitSphereSurface`]: crate::distributions::UnitSphereSurface
+/// [`UnitSphere::fill`]: UnitSphere::fill
+#[derive(Clone, Copy, Debug)]
+pub struct UnitSphere;
+
+impl UnitSphere {
+    /// Construct a new `UnitSphere` distribution.
+    #[inline]
+    pub fn new() -> UnitSphere {
+        UnitSphere
+    }
+
+    /// Fill `dest` with a point sampled uniformly from the surface of the
+    /// unit sphere in `dest.len()` dimensions.
+    pub fn fill<R: Rng + ?Sized>(&self, rng: &mut R, dest: &mut [f64]) {
+        if dest.is_empty() {
+            return;
+        }
+        loop {
+            let mut norm2 = 0.0;
+            for x in dest.iter_mut() {
+                *x = rng.sample(StandardNormal);
+                norm2 += *x * *x;
+            }
+            // The norm is 0 with negligible probability.
+            if norm2 > 0.0 {
+                let inv_norm = 1.0 / norm2.sqrt();
+                for x in dest.iter_mut() {
+                    *x *= inv_norm;
+                }
+                return;
+            }
+        }
+    }
+}
+
+macro_rules! unit_sphere_array_impls {
+    ($($n:expr),*) => {
+        $(
+            impl Distribution<[f64; $n]> for UnitSphere {
+                #[inline]
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; $n] {
+                    let mut x = [0.0; $n];
+                    self.fill(rng, &mut x);
+                    x
+                }
+            }
+        )*
+    }
+}
+
+unit_sphere_array_impls!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+                         17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32);
+
 #[cfg(test)]
 mod tests {
     use distributions::Distribution;
-    use super::UnitSphereSurface;
+    use super::{UnitSphereSurface, UnitSphere};
 
     /// Assert that two numbers are almost equal to each other.
     ///
@@ -96,4 +177,40 @@ mod tests {
         assert_eq!(dist.sample(&mut rng),
                    [0.9795722330927367, 0.18692349236651176, 0.07414747571708524]);
     }
+
+    #[test]
+    fn unit_sphere_norm() {
+        let mut rng = ::test::rng(890);
+        for _ in 0..1000 {
+            let x: [f64; 5] = UnitSphere::new().sample(&mut rng);
+            let norm2: f64 = x.iter().map(|x| x * x).sum();
+            assert_almost_eq!(norm2, 1., 1e-14);
+        }
+        let mut v = vec![0.0; 50];
+        UnitSphere::new().fill(&mut rng, &mut v);
+        let norm2: f64 = v.iter().map(|x| x * x).sum();
+        assert_almost_eq!(norm2, 1., 1e-14);
+        let x: [f64; 1] = UnitSphere::new().sample(&mut rng);
+        assert!(x == [1.0] || x == [-1.0]);
+    }
+
+    #[test]
+    fn unit_sphere_moments() {
+        let mut rng = ::test::rng(891);
+        // Each coordinate has mean 0 and variance 1/4 in 4 dimensions.
+        const N: usize = 10000;
+        let mut sum = [0.0; 4];
+        let mut sum2 = [0.0; 4];
+        for _ in 0..N {
+            let x: [f64; 4] = UnitSphere::new().sample(&mut rng);
+            for i in 0..4 {
+                sum[i] += x[i];
+                sum2[i] += x[i] * x[i];
+            }
+        }
+        for i in 0..4 {
+            assert!((sum[i] / N as f64).abs() < 4.0 * (0.25 / N as f64).sqrt());
+            assert!((sum2[i] / N as f64 - 0.25).abs() < 0.025);
+        }
+    }
 }
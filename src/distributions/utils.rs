@@ -11,7 +11,7 @@
 #[cfg(feature="simd_support")]
 use packed_simd::*;
 #[cfg(feature="std")]
-use distributions::ziggurat_tables;
+use distributions::{ziggurat_tables, Distribution, Standard};
 #[cfg(feature="std")]
 use Rng;
 
@@ -439,6 +439,51 @@ pub fn log_gamma(x: f64) -> f64 {
     log + (2.5066282746310005 * a / x).ln()
 }
 
+/// Floating-point types which can be sampled with [`ziggurat`].
+#[cfg(feature="std")]
+pub(crate) trait ZigguratFloat: ::distributions::Float {
+    /// Draw the layer index `i` and the uniform value `u` used by a single
+    /// round of the ziggurat method. `u` is in `[-1, 1)` if `symmetric`, and
+    /// in `(0, 1)` otherwise.
+    fn zig_sample<R: Rng + ?Sized>(rng: &mut R, symmetric: bool) -> (usize, Self);
+}
+
+#[cfg(feature="std")]
+macro_rules! ziggurat_float_impl {
+    ($ty:ident, $next_u:ident, $fraction_shift:expr) => {
+        impl ZigguratFloat for $ty {
+            #[inline(always)]
+            fn zig_sample<R: Rng + ?Sized>(rng: &mut R, symmetric: bool) -> (usize, $ty) {
+                use distributions::float::IntoFloat;
+                // As an optimisation we re-implement the conversion to a float.
+                // From the remaining most significant bits not used by the
+                // fraction we use 8 to construct `i`. This saves us generating
+                // a whole extra random number, while the added precision of
+                // using all bits for the fraction does not buy us much.
+                let bits = rng.$next_u();
+                let i = bits as usize & 0xff;
+
+                let u = if symmetric {
+                    // Convert to a value in the range [2,4) and substract to get [-1,1)
+                    // We can't convert to an open range directly, that would require
+                    // substracting `3.0 - EPSILON`, which is not representable.
+                    // It is possible with an extra step, but an open range does not
+                    // seem neccesary for the ziggurat algorithm anyway.
+                    (bits >> $fraction_shift).into_float_with_exponent(1) - 3.0
+                } else {
+                    // Convert to a value in the range [1,2) and substract to get (0,1)
+                    (bits >> $fraction_shift).into_float_with_exponent(0)
+                    - (1.0 - ::core::$ty::EPSILON / 2.0)
+                };
+                (i, u)
+            }
+        }
+    }
+}
+
+#[cfg(feature="std")] ziggurat_float_impl! { f32, next_u32, 9 }
+#[cfg(feature="std")] ziggurat_float_impl! { f64, next_u64, 12 }
+
 /// Sample a random number using the Ziggurat method (specifically the
 /// ZIGNOR variant from Doornik 2005). Most of the arguments are
 /// directly from the paper:
@@ -456,35 +501,17 @@ pub fn log_gamma(x: f64) -> f64 {
 // size from force-inlining.
 #[cfg(feature="std")]
 #[inline(always)]
-pub fn ziggurat<R: Rng + ?Sized, P, Z>(
+pub fn ziggurat<R: Rng + ?Sized, F, P, Z>(
             rng: &mut R,
             symmetric: bool,
-            x_tab: ziggurat_tables::ZigTable,
-            f_tab: ziggurat_tables::ZigTable,
+            x_tab: ziggurat_tables::ZigTable<F>,
+            f_tab: ziggurat_tables::ZigTable<F>,
             mut pdf: P,
             mut zero_case: Z)
-            -> f64 where P: FnMut(f64) -> f64, Z: FnMut(&mut R, f64) -> f64 {
-    use distributions::float::IntoFloat;
+            -> F where F: ZigguratFloat, Standard: Distribution<F>,
+                       P: FnMut(F) -> F, Z: FnMut(&mut R, F) -> F {
     loop {
-        // As an optimisation we re-implement the conversion to a f64.
-        // From the remaining 12 most significant bits we use 8 to construct `i`.
-        // This saves us generating a whole extra random number, while the added
-        // precision of using 64 bits for f64 does not buy us much.
-        let bits = rng.next_u64();
-        let i = bits as usize & 0xff;
-
-        let u = if symmetric {
-            // Convert to a value in the range [2,4) and substract to get [-1,1)
-            // We can't convert to an open range directly, that would require
-            // substracting `3.0 - EPSILON`, which is not representable.
-            // It is possible with an extra step, but an open range does not
-            // seem neccesary for the ziggurat algorithm anyway.
-            (bits >> 12).into_float_with_exponent(1) - 3.0
-        } else {
-            // Convert to a value in the range [1,2) and substract to get (0,1)
-            (bits >> 12).into_float_with_exponent(0)
-            - (1.0 - ::core::f64::EPSILON / 2.0)
-        };
+        let (i, u) = F::zig_sample(rng, symmetric);
         let x = u * x_tab[i];
 
         let test_x = if symmetric { x.abs() } else {x};
@@ -497,7 +524,7 @@ pub fn ziggurat<R: Rng + ?Sized, P, Z>(
             return zero_case(rng, u);
         }
         // algebraically equivalent to f1 + DRanU()*(f0 - f1) < 1
-        if f_tab[i + 1] + (f_tab[i] - f_tab[i + 1]) * rng.gen::<f64>() < pdf(x) {
+        if f_tab[i + 1] + (f_tab[i] - f_tab[i + 1]) * rng.gen::<F>() < pdf(x) {
             return x;
         }
     }
@@ -0,0 +1,222 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The von Mises distribution.
+
+use Rng;
+use distributions::{Distribution, Float, Open01, Standard, StandardNormal};
+use std::{error, fmt};
+
+/// The von Mises distribution `VonMises(mu, kappa)`, a continuous
+/// distribution of angles on the circle.
+///
+/// This distribution has density function:
+/// `f(x) = exp(kappa * cos(x - mu)) / (2 * pi * I0(kappa))`,
+/// where `I0` is the modified Bessel function of order 0. It is the circular
+/// analogue of the normal distribution: `mu` is the mean direction and
+/// `kappa` is a concentration parameter, similar to `1 / variance`. For
+/// `kappa = 0` the distribution is uniform on the circle.
+///
+/// Samples are angles in radians in the range `[mu - pi, mu + pi]`.
+///
+/// Samples are generated with the rejection algorithm of Best and Fisher
+/// (1979), which needs fewer than 1.5 iterations on average for any `kappa`.
+/// For `kappa > 1e6`, where that algorithm suffers from rounding errors, the
+/// distribution is approximated by a normal distribution with variance
+/// `1 / kappa`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, VonMises};
+///
+/// let vm = VonMises::new(0.5, 4.0).unwrap();
+/// let v = vm.sample(&mut rand::thread_rng());
+/// println!("{} is from a VonMises(0.5, 4) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct VonMises<F> {
+    mu: F,
+    kappa: F,
+    /// The parameter `r` of the wrapped Cauchy envelope, as defined by Best
+    /// and Fisher. Unused if `kappa == 0` or `kappa > NORMAL_THRESHOLD`.
+    r: F,
+}
+
+/// Error type returned from `VonMises::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VonMisesError {
+    /// `kappa < 0` or `nan`.
+    KappaNegative,
+}
+
+impl VonMisesError {
+    fn msg(&self) -> &str {
+        match *self {
+            VonMisesError::KappaNegative => "kappa is negative in von Mises distribution",
+        }
+    }
+}
+
+impl fmt::Display for VonMisesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for VonMisesError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+/// Above this `kappa`, the normal approximation is used.
+const NORMAL_THRESHOLD: f64 = 1e6;
+
+impl<F: Float> VonMises<F> {
+    /// Construct a new `VonMises` with the given mean direction `mu` and
+    /// concentration `kappa`.
+    ///
+    /// Returns an error if `kappa < 0`.
+    pub fn new(mu: F, kappa: F) -> Result<VonMises<F>, VonMisesError> {
+        if !(kappa >= F::zero()) {
+            return Err(VonMisesError::KappaNegative);
+        }
+        let r = if kappa > F::from_f64(NORMAL_THRESHOLD) {
+            F::zero()
+        } else {
+            // `rho = (tau - sqrt(2 tau)) / (2 kappa)` as given by Best and
+            // Fisher, rewritten to avoid cancellation for small `kappa`.
+            let two = F::from_f64(2.0);
+            let tau = F::one() + (F::one() + F::from_f64(4.0) * kappa * kappa).sqrt();
+            let rho = two * kappa / (tau + (two * tau).sqrt());
+            (F::one() + rho * rho) / (two * rho)
+        };
+        // For tiny `kappa`, `r` overflows and the distribution is uniform
+        // within the precision of `F`.
+        let kappa = if r.is_finite() { kappa } else { F::zero() };
+        Ok(VonMises { mu, kappa, r })
+    }
+}
+
+impl<F: Float> Distribution<F> for VonMises<F>
+    where Standard: Distribution<F>, Open01: Distribution<F>,
+          StandardNormal: Distribution<F>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let half = F::from_f64(0.5);
+        if self.kappa == F::zero() {
+            let u: F = rng.gen();
+            return self.mu + F::pi() * (u + u - F::one());
+        }
+        if self.kappa > F::from_f64(NORMAL_THRESHOLD) {
+            // A deviation larger than `pi` would need a normal sample beyond
+            // 3000 standard deviations, so no wrapping is needed.
+            let x: F = rng.sample(StandardNormal);
+            return self.mu + x / self.kappa.sqrt();
+        }
+
+        let r = self.r;
+        let f = loop {
+            let u1: F = rng.gen();
+            let u2: F = rng.sample(Open01);
+            let z = (F::pi() * u1).cos();
+            let f = (F::one() + r * z) / (r + z);
+            let c = self.kappa * (r - f);
+            if c * (F::from_f64(2.0) - c) > u2 || (c / u2).ln() + F::one() >= c {
+                break f;
+            }
+        };
+        // Rounding may move `f` slightly outside of `[-1, 1]`.
+        let f = if f > F::one() {
+            F::one()
+        } else if f < -F::one() {
+            -F::one()
+        } else {
+            f
+        };
+        let theta = f.acos();
+        let u3: F = rng.gen();
+        if u3 < half { self.mu - theta } else { self.mu + theta }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{VonMises, VonMisesError};
+
+    fn test_von_mises_moments(mu: f64, kappa: f64, expected_cos: f64, seed: u64) {
+        let d = VonMises::new(mu, kappa).unwrap();
+        let mut rng = ::test::rng(seed);
+        let n = 10000;
+        let (mut sum_cos, mut sum_sin) = (0.0, 0.0);
+        for _ in 0..n {
+            let x = d.sample(&mut rng);
+            assert!(x >= mu - ::std::f64::consts::PI && x <= mu + ::std::f64::consts::PI);
+            sum_cos += (x - mu).cos();
+            sum_sin += (x - mu).sin();
+        }
+        // The standard error of each mean is at most `1 / sqrt(n)`.
+        let mean_cos = sum_cos / n as f64;
+        let mean_sin = sum_sin / n as f64;
+        assert!((mean_cos - expected_cos).abs() < 0.04,
+                "kappa {}: mean cos {}", kappa, mean_cos);
+        assert!(mean_sin.abs() < 0.04, "kappa {}: mean sin {}", kappa, mean_sin);
+    }
+
+    #[test]
+    fn test_von_mises() {
+        // The mean of `cos(x - mu)` is `I1(kappa) / I0(kappa)`.
+        test_von_mises_moments(0.0, 0.0, 0.0, 810);
+        test_von_mises_moments(1.0, 1e-7, 5e-8, 811);
+        test_von_mises_moments(-2.0, 1.0, 0.44639, 812);
+        test_von_mises_moments(0.5, 10.0, 0.94860, 813);
+        test_von_mises_moments(3.0, 1000.0, 0.99950, 814);
+        test_von_mises_moments(0.0, 1e8, 1.0, 815);
+    }
+
+    #[test]
+    fn test_von_mises_f32() {
+        let d = VonMises::new(0.0f32, 2.0).unwrap();
+        let mut rng = ::test::rng(816);
+        for _ in 0..1000 {
+            let x = d.sample(&mut rng);
+            assert!(x.abs() <= ::std::f32::consts::PI);
+        }
+    }
+
+    #[test]
+    fn test_von_mises_f32_small_kappa() {
+        // `1 + 4 kappa^2` rounds to `1` in `f32` for these.
+        for &kappa in &[3e-4f32, 1e-4, 5e-5, 2e-5, 1e-30, 1e-40] {
+            let d = VonMises::new(0.0f32, kappa).unwrap();
+            let mut rng = ::test::rng(818);
+            let mut sum_cos = 0.0;
+            for _ in 0..1000 {
+                let x = d.sample(&mut rng);
+                assert!(x.abs() <= ::std::f32::consts::PI);
+                sum_cos += x.cos();
+            }
+            assert!((sum_cos / 1000.0).abs() < 0.13, "kappa {}: {}", kappa, sum_cos);
+        }
+    }
+
+    #[test]
+    fn test_von_mises_infinite_kappa() {
+        let d = VonMises::new(1.0, ::std::f64::INFINITY).unwrap();
+        assert_eq!(d.sample(&mut ::test::rng(817)), 1.0);
+    }
+
+    #[test]
+    fn test_von_mises_invalid() {
+        assert_eq!(VonMises::new(0.0, -1.0).unwrap_err(), VonMisesError::KappaNegative);
+        assert_eq!(VonMises::new(0.0, ::std::f64::NAN).unwrap_err(),
+                   VonMisesError::KappaNegative);
+    }
+}
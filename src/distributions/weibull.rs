@@ -9,7 +9,8 @@
 //! The Weibull distribution.
 
 use Rng;
-use distributions::{Distribution, OpenClosed01};
+use distributions::{Distribution, Float, OpenClosed01};
+use std::{error, fmt};
 
 /// Samples floating-point numbers according to the Weibull distribution
 ///
@@ -18,30 +19,63 @@ use distributions::{Distribution, OpenClosed01};
 /// use rand::prelude::*;
 /// use rand::distributions::Weibull;
 ///
-/// let val: f64 = SmallRng::from_entropy().sample(Weibull::new(1., 10.));
+/// let val: f64 = SmallRng::from_entropy().sample(Weibull::new(1., 10.).unwrap());
 /// println!("{}", val);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Weibull {
-    inv_shape: f64,
-    scale: f64,
+pub struct Weibull<F> {
+    inv_shape: F,
+    scale: F,
 }
 
-impl Weibull {
+/// Error type returned from `Weibull::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeibullError {
+    /// `scale <= 0` or `nan`.
+    ScaleNotPositive,
+    /// `shape <= 0` or `nan`.
+    ShapeNotPositive,
+}
+
+impl WeibullError {
+    fn msg(&self) -> &str {
+        match *self {
+            WeibullError::ScaleNotPositive => "scale is not positive in Weibull distribution",
+            WeibullError::ShapeNotPositive => "shape is not positive in Weibull distribution",
+        }
+    }
+}
+
+impl fmt::Display for WeibullError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for WeibullError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl<F: Float> Weibull<F> {
     /// Construct a new `Weibull` distribution with given `scale` and `shape`.
     ///
-    /// # Panics
-    ///
-    /// `scale` and `shape` have to be non-zero and positive.
-    pub fn new(scale: f64, shape: f64) -> Weibull {
-        assert!((scale > 0.) & (shape > 0.));
-        Weibull { inv_shape: 1./shape, scale }
+    /// Returns an error unless `scale` and `shape` are both positive.
+    pub fn new(scale: F, shape: F) -> Result<Weibull<F>, WeibullError> {
+        if !(scale > F::zero()) {
+            return Err(WeibullError::ScaleNotPositive);
+        }
+        if !(shape > F::zero()) {
+            return Err(WeibullError::ShapeNotPositive);
+        }
+        Ok(Weibull { inv_shape: F::one() / shape, scale })
     }
 }
 
-impl Distribution<f64> for Weibull {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let x: f64 = rng.sample(OpenClosed01);
+impl<F: Float> Distribution<F> for Weibull<F> where OpenClosed01: Distribution<F> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> F {
+        let x: F = rng.sample(OpenClosed01);
         self.scale * (-x.ln()).powf(self.inv_shape)
     }
 }
@@ -49,19 +83,19 @@ impl Distribution<f64> for Weibull {
 #[cfg(test)]
 mod tests {
     use distributions::Distribution;
-    use super::Weibull;
+    use super::{Weibull, WeibullError};
 
     #[test]
-    #[should_panic]
     fn invalid() {
-        Weibull::new(0., 0.);
+        assert_eq!(Weibull::new(0., 0.).unwrap_err(), WeibullError::ScaleNotPositive);
+        assert_eq!(Weibull::new(1., -1.).unwrap_err(), WeibullError::ShapeNotPositive);
     }
 
     #[test]
     fn sample() {
         let scale = 1.0;
         let shape = 2.0;
-        let d = Weibull::new(scale, shape);
+        let d = Weibull::new(scale, shape).unwrap();
         let mut rng = ::test::rng(1);
         for _ in 0..1000 {
             let r = d.sample(&mut rng);
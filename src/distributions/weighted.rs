@@ -44,6 +44,10 @@ use core::fmt;
 /// will request a single value from the underlying [`RngCore`], though the
 /// exact number depends on the implementaiton of `Uniform<X>::sample`.
 ///
+/// Individual weights can be changed with [`update_weights`], which takes
+/// `O(N)` time but does not allocate. For a weighted index which changes
+/// frequently, or which grows and shrinks, see [`WeightedTreeIndex`].
+///
 /// # Example
 ///
 /// ```
@@ -69,9 +73,12 @@ use core::fmt;
 ///
 /// [`Uniform<X>`]: crate::distributions::uniform::Uniform
 /// [`RngCore`]: rand_core::RngCore
+/// [`update_weights`]: WeightedIndex::update_weights
+/// [`WeightedTreeIndex`]: crate::distributions::WeightedTreeIndex
 #[derive(Debug, Clone)]
 pub struct WeightedIndex<X: SampleUniform + PartialOrd> {
     cumulative_weights: Vec<X>,
+    total_weight: X,
     weight_distribution: X::Sampler,
 }
 
@@ -113,9 +120,111 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
         if total_weight == zero {
             return Err(WeightedError::AllWeightsZero);
         }
-        let distr = X::Sampler::new(zero, total_weight);
+        let distr = X::Sampler::new(zero, total_weight.clone());
 
-        Ok(WeightedIndex { cumulative_weights: weights, weight_distribution: distr })
+        Ok(WeightedIndex { cumulative_weights: weights, total_weight, weight_distribution: distr })
+    }
+
+    /// Updates a subset of the weights, without changing the number of
+    /// weights.
+    ///
+    /// `new_weights` is a list of `(index, weight)` pairs, which must be
+    /// sorted by strictly increasing index. This takes `O(N)` time, but
+    /// unlike `new` it does not allocate.
+    ///
+    /// Returns an error if an index is out of bounds or the indices are not
+    /// sorted, if any new weight is `< 0`, or if the new total weight is 0.
+    /// In case of error, `self` is not modified.
+    pub fn update_weights(&mut self, new_weights: &[(usize, &X)])
+        -> Result<(), WeightedError>
+        where X: for<'a> ::core::ops::AddAssign<&'a X> +
+                 for<'a> ::core::ops::SubAssign<&'a X> +
+                 Clone +
+                 Default {
+        let zero = <X as Default>::default();
+        let len = self.cumulative_weights.len() + 1;
+
+        // Check everything first, so that `self` is left untouched on error.
+        let mut prev_index = None;
+        let mut any_positive = false;
+        for &(i, w) in new_weights {
+            if i >= len || prev_index.map_or(false, |prev| prev >= i) {
+                return Err(WeightedError::InvalidIndex);
+            }
+            if *w < zero {
+                return Err(WeightedError::NegativeWeight);
+            }
+            any_positive |= *w > zero;
+            prev_index = Some(i);
+        }
+        if new_weights.is_empty() {
+            return Ok(());
+        }
+        if !any_positive {
+            let mut updated = new_weights.iter().map(|&(i, _)| i).peekable();
+            let all_zero = (0..len).all(|i| {
+                if updated.peek() == Some(&i) {
+                    updated.next();
+                    true
+                } else {
+                    self.weight(i) == zero
+                }
+            });
+            if all_zero {
+                return Err(WeightedError::AllWeightsZero);
+            }
+        }
+
+        // Rebuild the cumulative weights from the first changed index. The
+        // unchanged weights are recovered as differences of the old ones.
+        let first = new_weights[0].0;
+        let mut updates = new_weights.iter().peekable();
+        let mut cumulative = if first > 0 {
+            self.cumulative_weights[first - 1].clone()
+        } else {
+            zero.clone()
+        };
+        let mut prev_old = cumulative.clone();
+        for i in first..len {
+            let old = if i < len - 1 {
+                self.cumulative_weights[i].clone()
+            } else {
+                self.total_weight.clone()
+            };
+            match updates.peek() {
+                Some(&&(j, w)) if j == i => {
+                    cumulative += w;
+                    updates.next();
+                }
+                _ => {
+                    let mut w = old.clone();
+                    w -= &prev_old;
+                    cumulative += &w;
+                }
+            }
+            prev_old = old;
+            if i < len - 1 {
+                self.cumulative_weights[i] = cumulative.clone();
+            }
+        }
+
+        self.total_weight = cumulative;
+        self.weight_distribution = X::Sampler::new(zero, self.total_weight.clone());
+        Ok(())
+    }
+
+    // The weight of the item at index `i`.
+    fn weight(&self, i: usize) -> X
+        where X: for<'a> ::core::ops::SubAssign<&'a X> + Clone {
+        let mut w = if i < self.cumulative_weights.len() {
+            self.cumulative_weights[i].clone()
+        } else {
+            self.total_weight.clone()
+        };
+        if i > 0 {
+            w -= &self.cumulative_weights[i - 1];
+        }
+        w
     }
 }
 
@@ -188,9 +297,62 @@ mod test {
         assert_eq!(WeightedIndex::new(&[-10, 20, 1, 30]).unwrap_err(), WeightedError::NegativeWeight);
         assert_eq!(WeightedIndex::new(&[-10]).unwrap_err(), WeightedError::NegativeWeight);
     }
+
+    #[test]
+    fn test_update_weights() {
+        let data = [
+            (&[10u32, 2, 3, 4][..],
+             &[(1, &100), (2, &4)][..],  // positive change
+             &[10, 100, 4, 4][..]),
+            (&[1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7][..],
+             &[(2, &1), (5, &1), (13, &100)][..],  // negative change and last element
+             &[1u32, 2, 1, 0, 5, 1, 7, 1, 2, 3, 4, 5, 6, 100][..]),
+            (&[5u32, 0, 3][..],
+             &[(0, &0)][..],  // first element becomes zero
+             &[0u32, 0, 3][..]),
+        ];
+
+        for &(weights, update, expected_weights) in data.iter() {
+            let total_weight = weights.iter().sum::<u32>();
+            let mut distr = WeightedIndex::new(weights.to_vec()).unwrap();
+            assert_eq!(distr.total_weight, total_weight);
+
+            distr.update_weights(update).unwrap();
+            let expected_total_weight = expected_weights.iter().sum::<u32>();
+            let expected_distr = WeightedIndex::new(expected_weights.to_vec()).unwrap();
+            assert_eq!(distr.total_weight, expected_total_weight);
+            assert_eq!(distr.total_weight, expected_distr.total_weight);
+            assert_eq!(distr.cumulative_weights, expected_distr.cumulative_weights);
+        }
+
+        // Sampling follows the new weights.
+        let mut r = ::test::rng(701);
+        let mut distr = WeightedIndex::new(&[1, 0, 0]).unwrap();
+        distr.update_weights(&[(0, &0), (2, &3)]).unwrap();
+        for _ in 0..5 {
+            assert_eq!(distr.sample(&mut r), 2);
+        }
+
+        // Errors leave the distribution unchanged.
+        let mut distr = WeightedIndex::new(&[1, 2, 3]).unwrap();
+        assert_eq!(distr.update_weights(&[(3, &1)]), Err(WeightedError::InvalidIndex));
+        assert_eq!(distr.update_weights(&[(1, &1), (0, &1)]), Err(WeightedError::InvalidIndex));
+        assert_eq!(distr.update_weights(&[(1, &1), (1, &1)]), Err(WeightedError::InvalidIndex));
+        assert_eq!(distr.update_weights(&[(0, &2), (1, &-1)]), Err(WeightedError::NegativeWeight));
+        assert_eq!(distr.update_weights(&[(0, &0), (1, &0), (2, &0)]),
+                   Err(WeightedError::AllWeightsZero));
+        assert_eq!(distr.cumulative_weights, vec![1, 3]);
+        assert_eq!(distr.total_weight, 6);
+
+        // Setting only some weights to zero is allowed.
+        distr.update_weights(&[(0, &0), (2, &0)]).unwrap();
+        assert_eq!(distr.cumulative_weights, vec![0, 2]);
+        assert_eq!(distr.total_weight, 2);
+    }
 }
 
-/// Error type returned from `WeightedIndex::new`.
+/// Error type returned from the constructors and update methods of
+/// `WeightedIndex`, `WeightedAliasIndex` and `WeightedTreeIndex`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WeightedError {
     /// The provided iterator contained no items.
@@ -201,6 +363,23 @@ pub enum WeightedError {
 
     /// All items in the provided iterator had a weight of zero.
     AllWeightsZero,
+
+    /// A weight is not finite, or too large for the total weight to be
+    /// represented.
+    InvalidWeight,
+
+    /// Too many weights were provided (length greater than `u32::MAX`).
+    TooMany,
+
+    /// An index is out of bounds, or the indices are not in increasing order.
+    InvalidIndex,
+
+    /// Fewer items with a non-zero weight are available than the number of
+    /// items requested.
+    InsufficientNonZero,
+
+    /// The number of weights differs from the number of items.
+    LengthMismatch,
 }
 
 impl WeightedError {
@@ -209,6 +388,11 @@ impl WeightedError {
             WeightedError::NoItem => "No items found",
             WeightedError::NegativeWeight => "Item has negative weight",
             WeightedError::AllWeightsZero => "All items had weight zero",
+            WeightedError::InvalidWeight => "A weight is invalid",
+            WeightedError::TooMany => "Too many weights (>= 2^32)",
+            WeightedError::InvalidIndex => "Invalid or unsorted index",
+            WeightedError::InsufficientNonZero => "Not enough items with non-zero weight",
+            WeightedError::LengthMismatch => "Number of weights differs from number of items",
         }
     }
 }
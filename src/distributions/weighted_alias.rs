@@ -0,0 +1,368 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weighted index sampling using the alias method.
+
+use Rng;
+use distributions::{Distribution, Uniform, WeightedError};
+use distributions::uniform::{SampleUniform, UniformSampler};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+// Note that this whole module is only imported if feature="alloc" is enabled.
+#[cfg(not(feature="std"))] use alloc::vec::Vec;
+
+/// A distribution using weighted sampling to pick a discretely selected item,
+/// implemented with the alias method.
+///
+/// Sampling a `WeightedAliasIndex<W>` distribution returns the index of a
+/// randomly selected element from the vector used to create the
+/// `WeightedAliasIndex<W>`. The chance of a given element being picked is
+/// proportional to the value of the element. The weights can have any type `W`
+/// for which an implementation of [`AliasableWeight`] exists.
+///
+/// # Performance
+///
+/// Given that `n` is the number of items in the vector used to create a
+/// `WeightedAliasIndex<W>`, it will require `O(n)` amount of memory. More
+/// specifically it takes up some constant amount of memory plus the vector
+/// used to create it and a `Vec<u32>` with capacity `n`.
+///
+/// Time complexity for the creation of a `WeightedAliasIndex<W>` is `O(n)`.
+/// Sampling is `O(1)`, it makes a call to `Uniform<u32>::sample` and a call
+/// to `Uniform<W>::sample`. In contrast, [`WeightedIndex`] needs `O(log n)`
+/// time per sample, but has a cheaper setup and supports arbitrary iterators
+/// as input.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::WeightedAliasIndex;
+///
+/// let choices = vec!['a', 'b', 'c'];
+/// let weights = vec![2, 1, 1];
+/// let dist = WeightedAliasIndex::new(weights).unwrap();
+/// let mut rng = thread_rng();
+/// for _ in 0..100 {
+///     // 50% chance to print 'a', 25% chance to print 'b', 25% chance to print 'c'
+///     println!("{}", choices[dist.sample(&mut rng)]);
+/// }
+///
+/// let items = [('a', 0), ('b', 3), ('c', 7)];
+/// let dist2 = WeightedAliasIndex::new(items.iter().map(|item| item.1).collect()).unwrap();
+/// for _ in 0..100 {
+///     // 0% chance to print 'a', 30% chance to print 'b', 70% chance to print 'c'
+///     println!("{}", items[dist2.sample(&mut rng)].0);
+/// }
+/// ```
+///
+/// [`WeightedIndex`]: crate::distributions::WeightedIndex
+#[derive(Debug, Clone)]
+pub struct WeightedAliasIndex<W: AliasableWeight> {
+    aliases: Vec<u32>,
+    no_alias_odds: Vec<W>,
+    uniform_index: Uniform<u32>,
+    uniform_within_weight_sum: W::Sampler,
+}
+
+impl<W: AliasableWeight> WeightedAliasIndex<W> {
+    /// Creates a new `WeightedAliasIndex`.
+    ///
+    /// Returns an error if:
+    /// - The vector is empty.
+    /// - The vector is longer than `u32::MAX`.
+    /// - For any weight `w`: `w < 0` or `w > max` where `max = W::MAX /
+    ///   weights.len()`.
+    /// - The sum of weights is zero.
+    pub fn new(weights: Vec<W>) -> Result<Self, WeightedError> {
+        let n = weights.len();
+        if n == 0 {
+            return Err(WeightedError::NoItem);
+        } else if n > ::core::u32::MAX as usize {
+            return Err(WeightedError::TooMany);
+        }
+        let n = n as u32;
+
+        let max_weight_size = W::try_from_u32_lossy(n)
+            .map(|n| W::MAX / n)
+            .unwrap_or(W::ZERO);
+        for &w in &weights {
+            if w < W::ZERO {
+                return Err(WeightedError::NegativeWeight);
+            }
+            // This also rejects NaN for floating-point weights.
+            if !(w <= max_weight_size) {
+                return Err(WeightedError::InvalidWeight);
+            }
+        }
+
+        // The sum of weights will represent 100% of no alias odds.
+        let mut weight_sum = W::ZERO;
+        for &w in &weights {
+            weight_sum += w;
+        }
+        if weight_sum == W::ZERO {
+            return Err(WeightedError::AllWeightsZero);
+        }
+
+        // `weight_sum` can't be zero at this point, so `n` can be converted.
+        let n_converted = W::try_from_u32_lossy(n).unwrap();
+
+        // Scale the weights so that the average weight equals `weight_sum`,
+        // the odds of not taking the alias of a "full" column. This can not
+        // overflow, since every weight is at most `W::MAX / n`.
+        let mut no_alias_odds = weights;
+        for odds in no_alias_odds.iter_mut() {
+            *odds *= n_converted;
+        }
+
+        // Split the indices into those which need more probability mass than
+        // a single column holds ("small") and those with a surplus ("big").
+        let mut smalls = Vec::new();
+        let mut bigs = Vec::new();
+        for (index, &odds) in no_alias_odds.iter().enumerate() {
+            if odds < weight_sum {
+                smalls.push(index as u32);
+            } else {
+                bigs.push(index as u32);
+            }
+        }
+
+        // Fill up each small column with mass from a big one, which becomes
+        // its alias. Every step finalizes one column.
+        let mut aliases = vec![0; n as usize];
+        while let (Some(&s), Some(&b)) = (smalls.last(), bigs.last()) {
+            smalls.pop();
+            bigs.pop();
+
+            aliases[s as usize] = b;
+            no_alias_odds[b as usize] = no_alias_odds[b as usize]
+                - weight_sum
+                + no_alias_odds[s as usize];
+
+            if no_alias_odds[b as usize] < weight_sum {
+                smalls.push(b);
+            } else {
+                bigs.push(b);
+            }
+        }
+
+        // The remaining columns are full. Any deviation from that is caused
+        // by rounding errors and is corrected here.
+        for &index in smalls.iter().chain(bigs.iter()) {
+            no_alias_odds[index as usize] = weight_sum;
+        }
+
+        let uniform_index = Uniform::new(0, n);
+        let uniform_within_weight_sum = W::Sampler::new(W::ZERO, weight_sum);
+
+        Ok(WeightedAliasIndex {
+            aliases,
+            no_alias_odds,
+            uniform_index,
+            uniform_within_weight_sum,
+        })
+    }
+}
+
+impl<W: AliasableWeight> Distribution<usize> for WeightedAliasIndex<W> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let candidate = rng.sample(self.uniform_index);
+        if self.uniform_within_weight_sum.sample(rng)
+            < self.no_alias_odds[candidate as usize]
+        {
+            candidate as usize
+        } else {
+            self.aliases[candidate as usize] as usize
+        }
+    }
+}
+
+/// Trait that must be implemented for weights, that are used with
+/// [`WeightedAliasIndex`]. Currently no guarantees on the correctness of
+/// [`WeightedAliasIndex`] are given for custom implementations of this trait.
+///
+/// [`WeightedAliasIndex`]: crate::distributions::WeightedAliasIndex
+pub trait AliasableWeight:
+    Sized
+    + Copy
+    + SampleUniform
+    + PartialOrd
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + MulAssign
+    + Div<Output = Self>
+    + DivAssign
+{
+    /// Maximum number representable by `Self`.
+    const MAX: Self;
+
+    /// Element of `Self` equivalent to 0.
+    const ZERO: Self;
+
+    /// Produce an instance of `Self` from a `u32` value, or return `None` if
+    /// out of range. Loss of precision (where `Self` is a floating point type)
+    /// is acceptable.
+    fn try_from_u32_lossy(n: u32) -> Option<Self>;
+}
+
+macro_rules! impl_weight_for_float {
+    ($T: ident) => {
+        impl AliasableWeight for $T {
+            const MAX: Self = ::core::$T::MAX;
+            const ZERO: Self = 0.0;
+
+            fn try_from_u32_lossy(n: u32) -> Option<Self> {
+                Some(n as $T)
+            }
+        }
+    };
+}
+
+macro_rules! impl_weight_for_int {
+    ($T: ident) => {
+        impl AliasableWeight for $T {
+            const MAX: Self = ::core::$T::MAX;
+            const ZERO: Self = 0;
+
+            fn try_from_u32_lossy(n: u32) -> Option<Self> {
+                let n_converted = n as Self;
+                if n_converted >= Self::ZERO && n_converted as u32 == n {
+                    Some(n_converted)
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+impl_weight_for_float!(f64);
+impl_weight_for_float!(f32);
+impl_weight_for_int!(usize);
+#[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+impl_weight_for_int!(u128);
+impl_weight_for_int!(u64);
+impl_weight_for_int!(u32);
+impl_weight_for_int!(u16);
+impl_weight_for_int!(u8);
+impl_weight_for_int!(isize);
+#[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+impl_weight_for_int!(i128);
+impl_weight_for_int!(i64);
+impl_weight_for_int!(i32);
+impl_weight_for_int!(i16);
+impl_weight_for_int!(i8);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_weighted_alias_index_f32() {
+        test_weighted_alias_index(f32::into);
+
+        // Floating point special cases
+        assert_eq!(
+            WeightedAliasIndex::new(vec![::core::f32::INFINITY]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::new(vec![-0_f32]).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert_eq!(
+            WeightedAliasIndex::new(vec![-1_f32]).unwrap_err(),
+            WeightedError::NegativeWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::new(vec![-::core::f32::INFINITY]).unwrap_err(),
+            WeightedError::NegativeWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::new(vec![::core::f32::NAN]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+    }
+
+    #[test]
+    fn test_weighted_alias_index_u32() {
+        test_weighted_alias_index(|x: u32| x as f64);
+
+        // Unsigned integer special cases
+        assert_eq!(
+            WeightedAliasIndex::new(vec![::core::u32::MAX, 1]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+    }
+
+    #[test]
+    fn test_weighted_alias_index_i8() {
+        test_weighted_alias_index(|x: i8| x as f64);
+
+        // Signed integer special cases
+        assert_eq!(
+            WeightedAliasIndex::new(vec![-1_i8]).unwrap_err(),
+            WeightedError::NegativeWeight
+        );
+        assert_eq!(
+            WeightedAliasIndex::new(vec![::core::i8::MIN]).unwrap_err(),
+            WeightedError::NegativeWeight
+        );
+    }
+
+    fn test_weighted_alias_index<W, F>(w_to_f64: F)
+        where W: AliasableWeight + ::core::fmt::Debug, F: Fn(W) -> f64
+    {
+        const NUM_WEIGHTS: u32 = 10;
+        const ZERO_WEIGHT_INDEX: u32 = 3;
+        const NUM_SAMPLES: u32 = 15000;
+        let mut rng = ::test::rng(0x9c9fa0b0580a7031);
+
+        let weights = {
+            let mut weights = Vec::with_capacity(NUM_WEIGHTS as usize);
+            let random_weight_distribution = Uniform::new_inclusive(
+                W::ZERO,
+                W::MAX / W::try_from_u32_lossy(NUM_WEIGHTS).unwrap(),
+            );
+            for _ in 0..NUM_WEIGHTS {
+                weights.push(rng.sample(&random_weight_distribution));
+            }
+            weights[ZERO_WEIGHT_INDEX as usize] = W::ZERO;
+            weights
+        };
+        let weight_sum = weights.iter().map(|w| w_to_f64(*w)).sum::<f64>();
+        let expected_counts = weights
+            .iter()
+            .map(|&w| w_to_f64(w) / weight_sum * NUM_SAMPLES as f64)
+            .collect::<Vec<f64>>();
+        let weight_distribution = WeightedAliasIndex::new(weights).unwrap();
+
+        let mut counts = vec![0; NUM_WEIGHTS as usize];
+        for _ in 0..NUM_SAMPLES {
+            counts[rng.sample(&weight_distribution)] += 1;
+        }
+
+        assert_eq!(counts[ZERO_WEIGHT_INDEX as usize], 0);
+        for (count, expected_count) in counts.into_iter().zip(expected_counts) {
+            let difference = (count as f64 - expected_count).abs();
+            let max_allowed_difference = NUM_SAMPLES as f64 / NUM_WEIGHTS as f64 * 0.1;
+            assert!(difference <= max_allowed_difference);
+        }
+
+        assert_eq!(WeightedAliasIndex::<W>::new(vec![]).err(),
+                   Some(WeightedError::NoItem));
+        assert_eq!(WeightedAliasIndex::new(vec![W::ZERO]).err(),
+                   Some(WeightedError::AllWeightsZero));
+        assert_eq!(WeightedAliasIndex::new(vec![W::MAX, W::MAX]).err(),
+                   Some(WeightedError::InvalidWeight));
+    }
+}
@@ -0,0 +1,479 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weighted index sampling backed by a sum tree.
+
+use Rng;
+use distributions::{Distribution, WeightedError};
+use distributions::uniform::{UniformSampler, SampleUniform, SampleBorrow};
+use core::ops::{Add, Sub};
+
+// Note that this whole module is only imported if feature="alloc" is enabled.
+#[cfg(not(feature="std"))] use alloc::vec::Vec;
+
+/// A distribution using weighted sampling to pick a discretely selected item,
+/// whose weights can be changed efficiently.
+///
+/// Sampling a `WeightedTreeIndex` distribution returns the index of a randomly
+/// selected item. The chance of a given item being picked is proportional to
+/// its weight. Unlike [`WeightedIndex`], items can be added, removed and
+/// reweighted after construction, which makes this type suitable for
+/// long-lived structures such as prioritized experience replay buffers.
+///
+/// The weights can use any type `W` implementing [`TreeWeight`], which is
+/// implemented for the primitive integer and floating-point types.
+///
+/// # Performance
+///
+/// The weights are stored in an implicit binary tree (like a binary heap),
+/// where each node also stores the total weight of its subtree. A
+/// `WeightedTreeIndex<W>` with `N` items therefore holds two `Vec<W>` of
+/// length `N`.
+///
+/// Construction takes `O(N)` time. [`push`], [`pop`], [`swap_remove`] and
+/// [`update`] take `O(log N)` time, as does sampling, which makes a single
+/// call to `Uniform<W>::sample_single`.
+///
+/// Subtotals are recomputed from the children of a node rather than adjusted
+/// by the difference of the old and new weight, so floating-point rounding
+/// errors do not accumulate when weights are changed repeatedly.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::WeightedTreeIndex;
+///
+/// let mut dist = WeightedTreeIndex::new(&[1.0, 0.0, 3.0]).unwrap();
+/// dist.push(4.0).unwrap();
+/// dist.update(0, 2.0).unwrap();
+/// assert_eq!(dist.total_weight(), 9.0);
+///
+/// let mut rng = thread_rng();
+/// // 0% chance of 1, 22% chance of 0, 33% chance of 2 and 44% chance of 3
+/// let i = dist.sample(&mut rng);
+/// assert!(i != 1);
+/// ```
+///
+/// [`WeightedIndex`]: crate::distributions::WeightedIndex
+/// [`TreeWeight`]: crate::distributions::TreeWeight
+/// [`push`]: WeightedTreeIndex::push
+/// [`pop`]: WeightedTreeIndex::pop
+/// [`swap_remove`]: WeightedTreeIndex::swap_remove
+/// [`update`]: WeightedTreeIndex::update
+#[derive(Debug, Clone)]
+pub struct WeightedTreeIndex<W> {
+    weights: Vec<W>,
+    // `subtotals[i]` is the sum of the weights in the subtree rooted at `i`,
+    // whose children are `2 * i + 1` and `2 * i + 2`.
+    subtotals: Vec<W>,
+}
+
+impl<W: TreeWeight> WeightedTreeIndex<W> {
+    /// Creates a new `WeightedTreeIndex` using the values in `weights`.
+    ///
+    /// Unlike [`WeightedIndex::new`], the iterator may be empty and all
+    /// weights may be zero, since items can be added or reweighted later.
+    /// Sampling from such an index fails however; see [`try_sample`].
+    ///
+    /// Returns an error if any weight is `< 0` or is not finite, or if the
+    /// total weight overflows or is not finite.
+    ///
+    /// [`WeightedIndex::new`]: crate::distributions::WeightedIndex::new
+    /// [`try_sample`]: WeightedTreeIndex::try_sample
+    pub fn new<I>(weights: I) -> Result<WeightedTreeIndex<W>, WeightedError>
+        where I: IntoIterator,
+              I::Item: SampleBorrow<W> {
+        let mut tree_weights = Vec::new();
+        for w in weights {
+            let w = *w.borrow();
+            check_weight(w)?;
+            tree_weights.push(w);
+        }
+
+        let mut tree = WeightedTreeIndex {
+            subtotals: tree_weights.clone(),
+            weights: tree_weights,
+        };
+        // Children always have a higher index than their parent.
+        for i in (0..tree.len()).rev() {
+            tree.recompute(i)?;
+        }
+        Ok(tree)
+    }
+
+    /// Returns `true` if the index contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns the weight of the item at `index`, or `None` if `index` is out
+    /// of bounds.
+    pub fn get(&self, index: usize) -> Option<W> {
+        self.weights.get(index).cloned()
+    }
+
+    /// Returns the sum of all weights.
+    pub fn total_weight(&self) -> W {
+        self.subtotals.first().cloned().unwrap_or_default()
+    }
+
+    /// Appends an item with the given weight. Its index is the previous
+    /// [`len`](WeightedTreeIndex::len).
+    ///
+    /// Returns an error if `weight < 0` or is not finite, or if the total
+    /// weight would overflow or not be finite. In case of error, `self` is not modified.
+    pub fn push(&mut self, weight: W) -> Result<(), WeightedError> {
+        check_weight(weight)?;
+        self.weights.push(weight);
+        self.subtotals.push(weight);
+        let index = self.len() - 1;
+        if let Err(e) = self.recompute_ancestors(index) {
+            self.pop();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Removes the last item and returns its weight, or `None` if the index
+    /// is empty.
+    pub fn pop(&mut self) -> Option<W> {
+        let weight = self.weights.pop()?;
+        self.subtotals.pop();
+        let index = self.len();
+        // Subtotals only decrease, so they cannot overflow.
+        self.recompute_ancestors(index).unwrap();
+        Some(weight)
+    }
+
+    /// Removes the item at `index` and returns its weight. The last item is
+    /// moved to `index`, like `Vec::swap_remove`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> W {
+        let last = self.len() - 1;
+        let weight = self.weights.swap_remove(index);
+        self.subtotals.pop();
+        // Subtotals only decrease, so they cannot overflow.
+        self.recompute_ancestors(last).unwrap();
+        if index < last {
+            self.recompute(index).unwrap();
+            self.recompute_ancestors(index).unwrap();
+        }
+        weight
+    }
+
+    /// Changes the weight of the item at `index`.
+    ///
+    /// Returns an error if `index` is out of bounds, if `weight < 0` or is
+    /// not finite, or if the total weight would overflow or not be finite. In
+    /// case of error, `self` is not modified.
+    pub fn update(&mut self, index: usize, weight: W) -> Result<(), WeightedError> {
+        if index >= self.len() {
+            return Err(WeightedError::InvalidIndex);
+        }
+        check_weight(weight)?;
+        let old_weight = self.weights[index];
+        if let Err(e) = self.set_weight(index, weight) {
+            // The subtotals with the old weight did not overflow.
+            self.set_weight(index, old_weight).unwrap();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn set_weight(&mut self, index: usize, weight: W) -> Result<(), WeightedError> {
+        self.weights[index] = weight;
+        self.recompute(index)?;
+        self.recompute_ancestors(index)
+    }
+
+    /// Samples a random index, or returns an error if the index is empty or
+    /// all weights are zero.
+    pub fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R)
+        -> Result<usize, WeightedError> {
+        let zero = W::default();
+        if self.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+        let total_weight = self.total_weight();
+        if !(total_weight > zero) {
+            return Err(WeightedError::AllWeightsZero);
+        }
+
+        let mut target = W::Sampler::sample_single(zero, total_weight, rng);
+        // Invariant: the subtree rooted at `index` has a positive subtotal.
+        let mut index = 0;
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let left_subtotal = self.subtotal(left);
+            if target < left_subtotal {
+                index = left;
+                continue;
+            }
+            target = target - left_subtotal;
+
+            let weight = self.weights[index];
+            if target < weight {
+                return Ok(index);
+            }
+            target = target - weight;
+
+            // Only the right subtree is left. Due to rounding `target` may
+            // exceed its subtotal, in which case we still pick an item with
+            // positive weight from the highest non-empty part of this subtree.
+            if self.subtotal(right) > zero {
+                index = right;
+            } else if weight > zero {
+                return Ok(index);
+            } else {
+                index = left;
+            }
+        }
+    }
+
+    // The subtotal of node `index`, or zero if it does not exist.
+    fn subtotal(&self, index: usize) -> W {
+        self.subtotals.get(index).cloned().unwrap_or_default()
+    }
+
+    // Recomputes the subtotal of node `index` from its weight and children,
+    // or returns an error if it overflows.
+    fn recompute(&mut self, index: usize) -> Result<(), WeightedError> {
+        self.subtotals[index] = self.weights[index]
+            .checked_add(self.subtotal(2 * index + 1))
+            .and_then(|sum| sum.checked_add(self.subtotal(2 * index + 2)))
+            .ok_or(WeightedError::InvalidWeight)?;
+        Ok(())
+    }
+
+    // Recomputes the subtotals of all strict ancestors of node `index`, or
+    // returns an error if one overflows.
+    fn recompute_ancestors(&mut self, mut index: usize) -> Result<(), WeightedError> {
+        while index > 0 {
+            index = (index - 1) / 2;
+            self.recompute(index)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: TreeWeight> Distribution<usize> for WeightedTreeIndex<W> {
+    /// Samples a random index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is empty or all weights are zero; use
+    /// [`try_sample`](WeightedTreeIndex::try_sample) to handle this case.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.try_sample(rng).unwrap()
+    }
+}
+
+fn check_weight<W: TreeWeight>(weight: W) -> Result<(), WeightedError> {
+    let zero = W::default();
+    if weight < zero {
+        Err(WeightedError::NegativeWeight)
+    } else if weight.checked_add(zero).is_none() {
+        // Infinite or NaN
+        Err(WeightedError::InvalidWeight)
+    } else {
+        Ok(())
+    }
+}
+
+/// Trait that must be implemented for weights, that are used with
+/// [`WeightedTreeIndex`].
+///
+/// [`WeightedTreeIndex`]: crate::distributions::WeightedTreeIndex
+pub trait TreeWeight:
+    Sized
+    + Copy
+    + Default
+    + SampleUniform
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+{
+    /// Adds `other` to `self`, or returns `None` if the sum overflows. For
+    /// floating-point types, this is the case if the sum is not finite.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_tree_weight_for_float {
+    ($T: ident) => {
+        impl TreeWeight for $T {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                let sum = self + other;
+                if sum.is_finite() { Some(sum) } else { None }
+            }
+        }
+    };
+}
+
+macro_rules! impl_tree_weight_for_int {
+    ($T: ident) => {
+        impl TreeWeight for $T {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$T>::checked_add(self, other)
+            }
+        }
+    };
+}
+
+impl_tree_weight_for_float!(f64);
+impl_tree_weight_for_float!(f32);
+impl_tree_weight_for_int!(usize);
+#[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+impl_tree_weight_for_int!(u128);
+impl_tree_weight_for_int!(u64);
+impl_tree_weight_for_int!(u32);
+impl_tree_weight_for_int!(u16);
+impl_tree_weight_for_int!(u8);
+impl_tree_weight_for_int!(isize);
+#[cfg(all(rustc_1_26, not(target_os = "emscripten")))]
+impl_tree_weight_for_int!(i128);
+impl_tree_weight_for_int!(i64);
+impl_tree_weight_for_int!(i32);
+impl_tree_weight_for_int!(i16);
+impl_tree_weight_for_int!(i8);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_weighted_tree_index() {
+        let mut r = ::test::rng(702);
+        const N_REPS: u32 = 5000;
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7];
+        let total_weight = weights.iter().sum::<u32>();
+
+        let verify = |distr: &WeightedTreeIndex<u32>, r: &mut ::test::TestRng<::rngs::StdRng>| {
+            let mut chosen = [0i32; 14];
+            for _ in 0..N_REPS {
+                chosen[distr.sample(r)] += 1;
+            }
+            for (i, count) in chosen.iter().enumerate() {
+                let exp = (distr.get(i).unwrap() * N_REPS) as f32
+                    / distr.total_weight() as f32;
+                let mut err = (*count as f32 - exp).abs();
+                if err != 0.0 {
+                    err /= exp;
+                }
+                assert!(err <= 0.25);
+            }
+        };
+
+        let mut distr = WeightedTreeIndex::new(&weights[..]).unwrap();
+        assert_eq!(distr.len(), 14);
+        assert_eq!(distr.total_weight(), total_weight);
+        verify(&distr, &mut r);
+
+        // Built by pushing, in a different order
+        let mut pushed = WeightedTreeIndex::new(&[0u32; 0]).unwrap();
+        for &w in weights.iter() {
+            pushed.push(w).unwrap();
+        }
+        assert_eq!(pushed.weights, distr.weights);
+        assert_eq!(pushed.subtotals, distr.subtotals);
+
+        distr.update(3, 10).unwrap();
+        distr.update(13, 0).unwrap();
+        assert_eq!(distr.get(3), Some(10));
+        assert_eq!(distr.total_weight(), total_weight + 10 - 7);
+        verify(&distr, &mut r);
+
+        assert_eq!(distr.swap_remove(1), 2);
+        assert_eq!(distr.get(1), Some(0));
+        assert_eq!(distr.len(), 13);
+        assert_eq!(distr.pop(), Some(6));
+        assert_eq!(distr.total_weight(), total_weight + 10 - 7 - 2 - 6);
+        assert_eq!(distr.len(), 12);
+
+        // Compare against a freshly built tree
+        let rebuilt = WeightedTreeIndex::new(distr.weights.clone()).unwrap();
+        assert_eq!(rebuilt.subtotals, distr.subtotals);
+    }
+
+    #[test]
+    fn test_weighted_tree_index_edge_cases() {
+        let mut r = ::test::rng(703);
+
+        let mut distr = WeightedTreeIndex::<f64>::new(&[0.0f64; 0]).unwrap();
+        assert!(distr.is_empty());
+        assert_eq!(distr.try_sample(&mut r), Err(WeightedError::NoItem));
+        assert_eq!(distr.pop(), None);
+
+        distr.push(0.0).unwrap();
+        distr.push(0.0).unwrap();
+        assert_eq!(distr.try_sample(&mut r), Err(WeightedError::AllWeightsZero));
+
+        distr.push(0.5).unwrap();
+        for _ in 0..10 {
+            assert_eq!(distr.sample(&mut r), 2);
+        }
+        distr.update(1, 2.0).unwrap();
+        distr.update(2, 0.0).unwrap();
+        for _ in 0..10 {
+            assert_eq!(distr.sample(&mut r), 1);
+        }
+
+        assert_eq!(distr.push(-1.0), Err(WeightedError::NegativeWeight));
+        assert_eq!(distr.push(::core::f64::NAN), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.push(::core::f64::INFINITY), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update(0, ::core::f64::INFINITY), Err(WeightedError::InvalidWeight));
+        // The total weight overflows
+        distr.push(::core::f64::MAX).unwrap();
+        assert_eq!(distr.push(::core::f64::MAX), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update(0, ::core::f64::MAX), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.pop(), Some(::core::f64::MAX));
+        assert_eq!(WeightedTreeIndex::new(&[1.0, ::core::f64::INFINITY]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(WeightedTreeIndex::new(&[::core::f64::MAX, ::core::f64::MAX]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(distr.update(3, 1.0), Err(WeightedError::InvalidIndex));
+        assert_eq!(distr.update(0, -1.0), Err(WeightedError::NegativeWeight));
+        assert_eq!(WeightedTreeIndex::new(&[1, -1]).unwrap_err(),
+                   WeightedError::NegativeWeight);
+        assert_eq!(distr.len(), 3);
+        assert_eq!(distr.total_weight(), 2.0);
+    }
+
+    #[test]
+    fn test_weighted_tree_index_int_overflow() {
+        let max = ::core::u32::MAX;
+        let mut distr = WeightedTreeIndex::new(&[0u32, 1]).unwrap();
+        distr.push(max - 1).unwrap();
+        assert_eq!(distr.total_weight(), max);
+        assert_eq!(distr.push(1), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.push(max), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update(0, 1), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.len(), 3);
+        assert_eq!(distr.get(0), Some(0));
+        assert_eq!(distr.total_weight(), max);
+        // The tree is unchanged
+        let rebuilt = WeightedTreeIndex::new(distr.weights.clone()).unwrap();
+        assert_eq!(rebuilt.subtotals, distr.subtotals);
+
+        assert_eq!(WeightedTreeIndex::new(&[max, max]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        let mut distr = WeightedTreeIndex::new(&[max]).unwrap();
+        assert_eq!(distr.push(max), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.len(), 1);
+        assert_eq!(distr.total_weight(), max);
+    }
+}
@@ -0,0 +1,364 @@
+// Copyright 2019 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Zipf and Zeta distributions.
+
+use Rng;
+use distributions::{Distribution, OpenClosed01, Standard};
+use std::{error, fmt};
+
+/// The Zipf distribution `Zipf(n, s)` over `{1, 2, ..., n}`.
+///
+/// The probability of `k` is proportional to `k^(-s)`, where `s >= 0` is the
+/// exponent. This is the distribution of ranks which follow Zipf's law, such
+/// as word frequencies or the popularity of cache keys. For `s = 0` it is the
+/// uniform distribution over `{1, ..., n}`; for `n = ∞` see [`Zeta`].
+///
+/// Samples are generated with the rejection-inversion method of Hörmann and
+/// Derflinger (1996), which takes `O(1)` expected time and memory regardless
+/// of `n`, unlike [`WeightedIndex`].
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Zipf};
+///
+/// // Popularity ranks of 10 million keys
+/// let zipf = Zipf::new(10_000_000, 1.1).unwrap();
+/// let key = zipf.sample(&mut rand::thread_rng());
+/// println!("requested key #{}", key);
+/// ```
+///
+/// [`Zeta`]: crate::distributions::Zeta
+/// [`WeightedIndex`]: crate::distributions::WeightedIndex
+#[derive(Clone, Copy, Debug)]
+pub struct Zipf {
+    n: u64,
+    exponent: f64,
+    /// `H(1.5) - 1`.
+    h_integral_x1: f64,
+    /// `H(n + 0.5)`.
+    h_integral_n: f64,
+    /// Threshold for the squeeze test, `2 - H^-1(H(2.5) - h(2))`.
+    s: f64,
+}
+
+/// Error type returned from `Zipf::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZipfError {
+    /// `n == 0`.
+    NTooSmall,
+    /// `exponent < 0`, infinite or `nan`.
+    InvalidExponent,
+}
+
+impl ZipfError {
+    fn msg(&self) -> &str {
+        match *self {
+            ZipfError::NTooSmall => "n is zero in Zipf distribution",
+            ZipfError::InvalidExponent =>
+                "exponent is negative or not finite in Zipf distribution",
+        }
+    }
+}
+
+impl fmt::Display for ZipfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for ZipfError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl Zipf {
+    /// Construct a new `Zipf` over `{1, ..., n}` with the given `exponent`.
+    ///
+    /// Returns an error if `n == 0`, or if `exponent` is negative or not
+    /// finite.
+    pub fn new(n: u64, exponent: f64) -> Result<Zipf, ZipfError> {
+        if n == 0 {
+            return Err(ZipfError::NTooSmall);
+        }
+        if !(exponent >= 0.0 && exponent.is_finite()) {
+            return Err(ZipfError::InvalidExponent);
+        }
+        let h_integral_x1 = h_integral(1.5, exponent) - 1.0;
+        let h_integral_n = h_integral(n as f64 + 0.5, exponent);
+        let s = 2.0 - h_integral_inv(h_integral(2.5, exponent) - h(2.0, exponent), exponent);
+        Ok(Zipf { n, exponent, h_integral_x1, h_integral_n, s })
+    }
+}
+
+/// The unnormalized probability `h(x) = x^(-exponent)`.
+fn h(x: f64, exponent: f64) -> f64 {
+    (-exponent * x.ln()).exp()
+}
+
+/// `H(x) = (x^(1 - exponent) - 1) / (1 - exponent)`, an integral of `h` which
+/// is `ln(x)` for `exponent = 1`.
+fn h_integral(x: f64, exponent: f64) -> f64 {
+    let ln_x = x.ln();
+    helper2((1.0 - exponent) * ln_x) * ln_x
+}
+
+/// The inverse function of `H`.
+fn h_integral_inv(x: f64, exponent: f64) -> f64 {
+    let mut t = x * (1.0 - exponent);
+    if t < -1.0 {
+        // Limit `t` to the domain of `ln_1p`, avoiding NaN from rounding
+        // errors.
+        t = -1.0;
+    }
+    (helper1(t) * x).exp()
+}
+
+/// `ln(1 + x) / x`, and its limit `1` at `x = 0`.
+fn helper1(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.ln_1p() / x
+    } else {
+        1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x))
+    }
+}
+
+/// `(exp(x) - 1) / x`, and its limit `1` at `x = 0`.
+fn helper2(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.exp_m1() / x
+    } else {
+        1.0 + x * 0.5 * (1.0 + x / 3.0 * (1.0 + 0.25 * x))
+    }
+}
+
+impl Distribution<u64> for Zipf {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let n = self.n as f64;
+        loop {
+            let v: f64 = rng.gen();
+            let u = self.h_integral_n + v * (self.h_integral_x1 - self.h_integral_n);
+            let x = h_integral_inv(u, self.exponent);
+            // Round to the nearest integer in `[1, n]`.
+            let mut k = (x + 0.5).floor();
+            if k < 1.0 {
+                k = 1.0;
+            } else if k > n {
+                k = n;
+            }
+            // Accept either by the squeeze or by comparing `u` with the area
+            // of the histogram bar of `k`.
+            if k - x <= self.s
+                || u >= h_integral(k + 0.5, self.exponent) - h(k, self.exponent)
+            {
+                return if k >= n { self.n } else { k as u64 };
+            }
+        }
+    }
+}
+
+/// The Zeta distribution `Zeta(a)` over the positive integers.
+///
+/// The probability of `k` is `k^(-a) / ζ(a)`, where `ζ` is the Riemann zeta
+/// function and the exponent `a` is greater than `1`. This is the limit of
+/// the [`Zipf`] distribution for `n → ∞`.
+///
+/// Samples larger than `u64::MAX`, which are possible for `a` close to `1`,
+/// saturate to `u64::MAX`.
+///
+/// Samples are generated with the rejection algorithm of Devroye (1986),
+/// which takes `O(1)` expected time.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Zeta};
+///
+/// let zeta = Zeta::new(2.5).unwrap();
+/// let v = zeta.sample(&mut rand::thread_rng());
+/// println!("{} is from a Zeta(2.5) distribution", v);
+/// ```
+///
+/// [`Zipf`]: crate::distributions::Zipf
+#[derive(Clone, Copy, Debug)]
+pub struct Zeta {
+    a_minus_1: f64,
+    /// `2^(a - 1)`.
+    b: f64,
+}
+
+/// Error type returned from `Zeta::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZetaError {
+    /// `exponent <= 1` or `nan`.
+    ExponentTooSmall,
+}
+
+impl ZetaError {
+    fn msg(&self) -> &str {
+        match *self {
+            ZetaError::ExponentTooSmall => "exponent is not greater than 1 in Zeta distribution",
+        }
+    }
+}
+
+impl fmt::Display for ZetaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+impl error::Error for ZetaError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl Zeta {
+    /// Construct a new `Zeta` with the given `exponent`.
+    ///
+    /// Returns an error if `exponent <= 1`.
+    pub fn new(exponent: f64) -> Result<Zeta, ZetaError> {
+        if !(exponent > 1.0) {
+            return Err(ZetaError::ExponentTooSmall);
+        }
+        let a_minus_1 = exponent - 1.0;
+        Ok(Zeta { a_minus_1, b: 2f64.powf(a_minus_1) })
+    }
+}
+
+impl Distribution<u64> for Zeta {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        loop {
+            let u: f64 = rng.sample(OpenClosed01);
+            let x = u.powf(-1.0 / self.a_minus_1).floor();
+            if x >= ::std::u64::MAX as f64 {
+                // This also avoids an infinite loop for `a` close to `1`,
+                // where `x` would always be infinite and rejected below.
+                return ::std::u64::MAX;
+            }
+            let t = (1.0 + 1.0 / x).powf(self.a_minus_1);
+            let v: f64 = rng.sample(Standard);
+            if v * x * (t - 1.0) * self.b <= t * (self.b - 1.0) {
+                return x as u64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::Distribution;
+    use super::{Zipf, ZipfError, Zeta, ZetaError};
+
+    fn test_zipf_frequencies<R: Rng>(n: u64, exponent: f64, rng: &mut R) {
+        let d = Zipf::new(n, exponent).unwrap();
+        let norm: f64 = (1..(n + 1)).map(|k| (k as f64).powf(-exponent)).sum();
+
+        const N_SAMPLES: usize = 20000;
+        let mut counts = vec![0usize; n as usize];
+        for _ in 0..N_SAMPLES {
+            let k = d.sample(rng);
+            assert!(k >= 1 && k <= n);
+            counts[(k - 1) as usize] += 1;
+        }
+        for (i, &c) in counts.iter().enumerate() {
+            let p = ((i + 1) as f64).powf(-exponent) / norm;
+            let expected = p * N_SAMPLES as f64;
+            let std_dev = (expected * (1.0 - p)).sqrt();
+            assert!((c as f64 - expected).abs() < 4.0 * std_dev + 1.0,
+                    "n {} exponent {}: count of {} is {}, expected {}",
+                    n, exponent, i + 1, c, expected);
+        }
+    }
+
+    #[test]
+    fn test_zipf() {
+        let mut rng = ::test::rng(830);
+        test_zipf_frequencies(1, 1.0, &mut rng);
+        test_zipf_frequencies(10, 0.0, &mut rng);
+        test_zipf_frequencies(10, 1.0, &mut rng);
+        test_zipf_frequencies(20, 1.5, &mut rng);
+        test_zipf_frequencies(50, 0.5, &mut rng);
+        test_zipf_frequencies(5, 10.0, &mut rng);
+    }
+
+    #[test]
+    fn test_zipf_large_n() {
+        let mut rng = ::test::rng(831);
+        let d = Zipf::new(::std::u64::MAX, 0.5).unwrap();
+        let mut ones = 0;
+        for _ in 0..1000 {
+            let k = d.sample(&mut rng);
+            assert!(k >= 1);
+            if k == 1 {
+                ones += 1;
+            }
+        }
+        // The probability of `1` is tiny.
+        assert!(ones < 10);
+    }
+
+    #[test]
+    fn test_zipf_invalid() {
+        assert_eq!(Zipf::new(0, 1.0).unwrap_err(), ZipfError::NTooSmall);
+        assert_eq!(Zipf::new(10, -1.0).unwrap_err(), ZipfError::InvalidExponent);
+        assert_eq!(Zipf::new(10, ::std::f64::NAN).unwrap_err(), ZipfError::InvalidExponent);
+        assert_eq!(Zipf::new(10, ::std::f64::INFINITY).unwrap_err(),
+                   ZipfError::InvalidExponent);
+    }
+
+    #[test]
+    fn test_zeta() {
+        // For `a = 4`, the mean is `ζ(3) / ζ(4)` and the variance is
+        // `ζ(2) / ζ(4) - mean^2`.
+        const ZETA_2: f64 = 1.6449340668482264;
+        const ZETA_3: f64 = 1.2020569031595942;
+        const ZETA_4: f64 = 1.0823232337111381;
+        let mut rng = ::test::rng(832);
+        let d = Zeta::new(4.0).unwrap();
+        let n = 10000;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let k = d.sample(&mut rng);
+            assert!(k >= 1);
+            sum += k as f64;
+        }
+        let mean = sum / n as f64;
+        let expected_mean = ZETA_3 / ZETA_4;
+        let expected_variance = ZETA_2 / ZETA_4 - expected_mean * expected_mean;
+        assert!((mean - expected_mean).abs() < 4.0 * (expected_variance / n as f64).sqrt(),
+                "mean {}", mean);
+
+        // For `a = 2`, the probability of `1` is `1 / ζ(2)`.
+        let d = Zeta::new(2.0).unwrap();
+        let ones = (0..n).filter(|_| d.sample(&mut rng) == 1).count();
+        let p = 1.0 / ZETA_2;
+        let std_dev = (n as f64 * p * (1.0 - p)).sqrt();
+        assert!((ones as f64 - n as f64 * p).abs() < 4.0 * std_dev, "ones {}", ones);
+    }
+
+    #[test]
+    fn test_zeta_small_exponent() {
+        let mut rng = ::test::rng(833);
+        let d = Zeta::new(1.0 + 1e-10).unwrap();
+        for _ in 0..100 {
+            assert!(d.sample(&mut rng) >= 1);
+        }
+    }
+
+    #[test]
+    fn test_zeta_invalid() {
+        assert_eq!(Zeta::new(1.0).unwrap_err(), ZetaError::ExponentTooSmall);
+        assert_eq!(Zeta::new(0.5).unwrap_err(), ZetaError::ExponentTooSmall);
+        assert_eq!(Zeta::new(::std::f64::NAN).unwrap_err(), ZetaError::ExponentTooSmall);
+    }
+}
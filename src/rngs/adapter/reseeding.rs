@@ -341,17 +341,16 @@ mod test {
     fn test_reseeding() {
         let mut zero = StepRng::new(0, 0);
         let rng = ChaChaCore::from_rng(&mut zero).unwrap();
-        let mut reseeding = ReseedingRng::new(rng, 32*4, zero);
+        let mut reseeding = ReseedingRng::new(rng, 128*4, zero);
 
-        // Currently we only support for arrays up to length 32.
         // TODO: cannot generate seq via Rng::gen because it uses different alg
-        let mut buf = [0u32; 32]; // Needs to be a multiple of the RNGs result
-                                  // size to test exactly.
-        reseeding.fill(&mut buf);
+        let mut buf = [0u32; 128]; // Needs to be a multiple of the RNGs result
+                                   // size to test exactly.
+        reseeding.fill(&mut buf[..]);
         let seq = buf;
         for _ in 0..10 {
-            reseeding.fill(&mut buf);
-            assert_eq!(buf, seq);
+            reseeding.fill(&mut buf[..]);
+            assert_eq!(&buf[..], &seq[..]);
         }
     }
 
@@ -17,6 +17,7 @@
 #[cfg(all(feature="alloc", not(feature="std")))] use alloc::collections::BTreeSet;
 
 #[cfg(feature="alloc")] use distributions::{Distribution, Uniform};
+#[cfg(feature="std")] use distributions::{Open01, WeightedError};
 use Rng;
 
 /// A vector of indices.
@@ -220,6 +221,134 @@ pub fn sample<R>(rng: &mut R, length: usize, amount: usize) -> IndexVec
     }
 }
 
+/// Randomly sample exactly `amount` distinct indices from `0..length`, where
+/// the probability of choosing index `i` is proportional to `weight(i)`.
+///
+/// This uses the A-Res algorithm of Efraimidis and Spirakis: each index with a
+/// positive weight `w` is given the key `u^(1/w)` for a uniformly random `u`,
+/// and the `amount` indices with the largest keys are selected. The indices
+/// are returned in decreasing order of their key, which is the order in which
+/// they would be drawn by repeated weighted sampling without replacement.
+///
+/// This implementation uses `O(length)` time and `O(amount)` memory, and calls
+/// `weight` exactly once per index. Indices with weight zero are never
+/// selected.
+///
+/// Returns an error if any weight is negative or not finite, or if fewer
+/// than `amount` indices have a positive weight.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index;
+///
+/// let weights = [1.0, 0.0, 2.0, 5.0];
+/// let mut rng = rand::thread_rng();
+/// let indices = index::sample_weighted(&mut rng, weights.len(), |i| weights[i], 2)
+///     .unwrap();
+/// assert_eq!(indices.len(), 2);
+/// assert!(indices.iter().all(|i| i != 1));
+/// ```
+#[cfg(feature="std")]
+pub fn sample_weighted<R, F, X>(rng: &mut R, length: usize, weight: F, amount: usize)
+    -> Result<IndexVec, WeightedError>
+    where R: Rng + ?Sized,
+          F: Fn(usize) -> X,
+          X: Into<f64>,
+{
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+
+    let (indices, num_positive) = weighted_reservoir(rng,
+        (0..length).map(|i| (i, weight(i).into())), amount)?;
+    if indices.len() < amount {
+        return Err(if num_positive == 0 {
+            WeightedError::AllWeightsZero
+        } else {
+            WeightedError::InsufficientNonZero
+        });
+    }
+    Ok(IndexVec::from(indices))
+}
+
+/// Select up to `amount` items with the A-Res algorithm in a single pass over
+/// `items`, which yields each item together with its weight.
+///
+/// Returns the selected items in decreasing order of their key, and the
+/// number of items with a positive weight. Only `O(amount)` items are held in
+/// memory at any time.
+#[cfg(feature="std")]
+pub(crate) fn weighted_reservoir<R, I, T>(rng: &mut R, items: I, amount: usize)
+    -> Result<(Vec<T>, usize), WeightedError>
+    where R: Rng + ?Sized,
+          I: Iterator<Item = (T, f64)>,
+{
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+
+    // Ordered by *decreasing* key, so that the top of the `BinaryHeap` is the
+    // element with the smallest key.
+    struct Element<T> {
+        item: T,
+        key: f64,
+    }
+    impl<T> PartialEq for Element<T> {
+        fn eq(&self, other: &Element<T>) -> bool {
+            self.key == other.key
+        }
+    }
+    impl<T> Eq for Element<T> {}
+    impl<T> PartialOrd for Element<T> {
+        fn partial_cmp(&self, other: &Element<T>) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl<T> Ord for Element<T> {
+        fn cmp(&self, other: &Element<T>) -> Ordering {
+            // Keys are never NaN
+            other.key.partial_cmp(&self.key).unwrap()
+        }
+    }
+
+    let mut heap = BinaryHeap::with_capacity(amount);
+    let mut num_positive = 0;
+    for (item, w) in items {
+        if w < 0.0 {
+            return Err(WeightedError::NegativeWeight);
+        }
+        if !(w < ::core::f64::INFINITY) {
+            return Err(WeightedError::InvalidWeight);
+        }
+        if w == 0.0 {
+            continue;
+        }
+        num_positive += 1;
+        if amount == 0 {
+            continue;
+        }
+
+        // We use `ln(u) / w`, which orders the same as `u^(1/w)` but does not
+        // underflow to zero for small weights.
+        let u: f64 = rng.sample(Open01);
+        let key = u.ln() / w;
+        if heap.len() < amount {
+            heap.push(Element { item, key });
+        } else if let Some(mut smallest) = heap.peek_mut() {
+            if key > smallest.key {
+                *smallest = Element { item, key };
+            }
+        }
+    }
+
+    let items = heap.into_sorted_vec().into_iter()
+        .map(|element| element.item)
+        .collect();
+    Ok((items, num_positive))
+}
+
 /// Randomly sample exactly `amount` indices from `0..length`, using Floyd's
 /// combination algorithm.
 ///
@@ -375,4 +504,58 @@ mod test {
         assert!(v1.iter().all(|e| e < length));
         assert_eq!(v1, v2);
     }
+
+    #[cfg(feature="std")]
+    #[test]
+    fn test_sample_weighted() {
+        let mut r = ::test::rng(423);
+        let weights = [1u32, 0, 3, 4, 0, 2, 6];
+
+        // Weights of zero are never chosen
+        for _ in 0..10 {
+            let v = sample_weighted(&mut r, weights.len(), |i| weights[i], 5)
+                .unwrap().into_vec();
+            let mut sorted = v.clone();
+            sorted.sort();
+            assert_eq!(sorted, vec![0, 2, 3, 5, 6]);
+        }
+
+        // The first index follows the weights, and the full order is that of
+        // sequential sampling without replacement.
+        const N_REPS: usize = 6000;
+        let mut first = [0usize; 7];
+        let mut second_after_6 = [0usize; 7];
+        let mut n_first_6 = 0;
+        for _ in 0..N_REPS {
+            let v = sample_weighted(&mut r, weights.len(), |i| weights[i] as f64, 2)
+                .unwrap();
+            first[v.index(0)] += 1;
+            if v.index(0) == 6 {
+                n_first_6 += 1;
+                second_after_6[v.index(1)] += 1;
+            }
+        }
+        for (i, &count) in first.iter().enumerate() {
+            let expected = (N_REPS * weights[i] as usize) as f64 / 16.0;
+            assert!((count as f64 - expected).abs() <= 0.15 * expected + 5.0);
+        }
+        for (i, &count) in second_after_6.iter().enumerate() {
+            let w = if i == 6 { 0 } else { weights[i] };
+            let expected = (n_first_6 * w as usize) as f64 / 10.0;
+            assert!((count as f64 - expected).abs() <= 0.2 * expected + 5.0);
+        }
+
+        assert_eq!(sample_weighted(&mut r, 3, |_| 1.0f32, 0).unwrap().len(), 0);
+        assert_eq!(sample_weighted(&mut r, 3, |i| [1, 1, 1][i], 3).unwrap().len(), 3);
+        assert_eq!(sample_weighted(&mut r, 3, |i| [1.0, -1.0, 1.0][i], 1).unwrap_err(),
+                   WeightedError::NegativeWeight);
+        assert_eq!(sample_weighted(&mut r, 2, |i| [1.0, ::core::f64::NAN][i], 1).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(sample_weighted(&mut r, 2, |_| ::core::f64::INFINITY, 1).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(sample_weighted(&mut r, 2, |_| 0.0, 1).unwrap_err(),
+                   WeightedError::AllWeightsZero);
+        assert_eq!(sample_weighted(&mut r, 3, |i| [1, 0, 1][i], 3).unwrap_err(),
+                   WeightedError::InsufficientNonZero);
+    }
 }
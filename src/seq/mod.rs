@@ -130,6 +130,41 @@ pub trait SliceRandom {
                  Clone +
                  Default;
 
+    /// Similar to [`choose_multiple`], but where the likelihood of each
+    /// element's inclusion in the output may be specified. The elements are
+    /// returned in the order they were chosen, i.e. the order of repeated
+    /// weighted sampling without replacement.
+    ///
+    /// The specified function `weight` maps each item `x` to a relative
+    /// likelihood `weight(x)`. Items with a weight of zero are never chosen.
+    ///
+    /// Returns an error if a weight is negative or not finite, or if fewer
+    /// than `amount` items have a positive weight. If `amount` is greater than
+    /// the length of the slice, it is reduced to the length.
+    ///
+    /// Complexity is the same as [`index::sample_weighted`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::prelude::*;
+    ///
+    /// let choices = [('a', 2), ('b', 1), ('c', 1)];
+    /// let mut rng = thread_rng();
+    /// // First draw 'a' has 50% chance, 'b' 25%, 'c' 25%; then one of the
+    /// // remaining two, with probability proportional to their weights.
+    /// println!("{:?}", choices.choose_multiple_weighted(&mut rng, 2, |item| item.1)
+    ///     .unwrap().collect::<Vec<_>>());
+    /// ```
+    /// [`choose_multiple`]: SliceRandom::choose_multiple
+    /// [`index::sample_weighted`]: index::sample_weighted
+    #[cfg(feature = "std")]
+    fn choose_multiple_weighted<R, F, X>(&self, rng: &mut R, amount: usize, weight: F)
+        -> Result<SliceChooseIter<Self, Self::Item>, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&Self::Item) -> X,
+              X: Into<f64>;
+
     /// Shuffle a mutable slice in place.
     /// 
     /// Depending on the implementation, complexity is expected to be `O(1)`.
@@ -305,6 +340,81 @@ pub trait IteratorRandom: Iterator + Sized {
         }
         reservoir
     }
+
+    /// Choose one element at random from the iterator, where the likelihood
+    /// of each outcome may be specified. The specified function `weight` maps
+    /// items `x` to a relative likelihood `weight(x)`.
+    ///
+    /// This makes a single pass over the iterator and holds only one element
+    /// at a time, so it can be used on streams of unknown length. Items with
+    /// a weight of zero are never chosen.
+    ///
+    /// Returns an error if the iterator is empty, if all weights are zero, or
+    /// if a weight is negative or not finite.
+    ///
+    /// Complexity is `O(n)`, where `n` is the length of the iterator. One
+    /// random number is consumed per element with a positive weight.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::prelude::*;
+    ///
+    /// let records = vec![("debug", 1), ("info", 2), ("error", 10)];
+    /// let mut rng = thread_rng();
+    /// let chosen = records.into_iter().choose_weighted(&mut rng, |r| r.1).unwrap();
+    /// println!("{}", chosen.0);
+    /// ```
+    #[cfg(feature = "std")]
+    fn choose_weighted<R, F, X>(self, rng: &mut R, mut weight: F)
+        -> Result<Self::Item, WeightedError>
+        where R: Rng + ?Sized,
+              F: FnMut(&Self::Item) -> X,
+              X: Into<f64>
+    {
+        let mut any_item = false;
+        let (mut chosen, _) = index::weighted_reservoir(rng, self.map(|item| {
+            any_item = true;
+            let w = weight(&item).into();
+            (item, w)
+        }), 1)?;
+        match chosen.pop() {
+            Some(item) => Ok(item),
+            None if any_item => Err(WeightedError::AllWeightsZero),
+            None => Err(WeightedError::NoItem),
+        }
+    }
+
+    /// Collects `amount` distinct elements at random from the iterator into a
+    /// vector, where the likelihood of each element's inclusion may be
+    /// specified. The specified function `weight` maps items `x` to a relative
+    /// likelihood `weight(x)`.
+    ///
+    /// The elements are returned in the order they were chosen, i.e. the
+    /// order of repeated weighted sampling without replacement. This makes a
+    /// single pass over the iterator using the A-Res algorithm, holding at
+    /// most `amount` elements at a time.
+    ///
+    /// Items with a weight of zero are never chosen. The length of the
+    /// returned vector equals `amount` unless the iterator contains fewer
+    /// elements with positive weight, in which case all of those are
+    /// returned. Returns an error if a weight is negative or not finite.
+    ///
+    /// Complexity is `O(n log(amount))` where `n` is the length of the
+    /// iterator.
+    #[cfg(feature = "std")]
+    fn choose_multiple_weighted<R, F, X>(self, rng: &mut R, amount: usize, mut weight: F)
+        -> Result<Vec<Self::Item>, WeightedError>
+        where R: Rng + ?Sized,
+              F: FnMut(&Self::Item) -> X,
+              X: Into<f64>
+    {
+        let (chosen, _) = index::weighted_reservoir(rng, self.map(|item| {
+            let w = weight(&item).into();
+            (item, w)
+        }), amount)?;
+        Ok(chosen)
+    }
 }
 
 
@@ -375,6 +485,22 @@ impl<T> SliceRandom for [T] {
         Ok(&mut self[distr.sample(rng)])
     }
 
+    #[cfg(feature = "std")]
+    fn choose_multiple_weighted<R, F, X>(&self, rng: &mut R, amount: usize, weight: F)
+        -> Result<SliceChooseIter<Self, Self::Item>, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&Self::Item) -> X,
+              X: Into<f64>
+    {
+        let amount = ::core::cmp::min(amount, self.len());
+        Ok(SliceChooseIter {
+            slice: self,
+            _phantom: Default::default(),
+            indices: index::sample_weighted(rng, self.len(),
+                |idx| weight(&self[idx]), amount)?.into_iter(),
+        })
+    }
+
     fn shuffle<R>(&mut self, rng: &mut R) where R: Rng + ?Sized
     {
         for i in (1..self.len()).rev() {
@@ -826,4 +952,80 @@ mod test {
         assert_eq!([0, -1].choose_weighted_mut(&mut r, |x| *x), Err(WeightedError::NegativeWeight));
         assert_eq!([-1, 0].choose_weighted_mut(&mut r, |x| *x), Err(WeightedError::NegativeWeight));
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_multiple_weighted() {
+        let mut r = ::test::rng(407);
+        let choices = [('a', 2i32), ('b', 0), ('c', 1), ('d', 4)];
+
+        const N_REPS: usize = 3000;
+        let mut first = [0usize; 4];
+        for _ in 0..N_REPS {
+            let chosen: Vec<char> = choices
+                .choose_multiple_weighted(&mut r, 2, |item| item.1)
+                .unwrap().map(|item| item.0).collect();
+            assert_eq!(chosen.len(), 2);
+            assert!(chosen[0] != chosen[1] && !chosen.contains(&'b'));
+            first[(chosen[0] as usize) - ('a' as usize)] += 1;
+        }
+        for (i, &count) in first.iter().enumerate() {
+            let expected = N_REPS as f64 * choices[i].1 as f64 / 7.0;
+            assert!((count as f64 - expected).abs() <= 0.15 * expected + 5.0);
+        }
+
+        // `amount` is limited to the length
+        let chosen: Vec<_> = choices[2..]
+            .choose_multiple_weighted(&mut r, 5, |item| item.1)
+            .unwrap().collect();
+        assert_eq!(chosen.len(), 2);
+
+        assert_eq!(choices.choose_multiple_weighted(&mut r, 4, |item| item.1).err()
+                       .unwrap(), WeightedError::InsufficientNonZero);
+        assert_eq!(choices.choose_multiple_weighted(&mut r, 1, |item| -item.1).err()
+                       .unwrap(), WeightedError::NegativeWeight);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_iterator_weighted() {
+        let mut r = ::test::rng(408);
+        let weights = [2u32, 0, 1, 4];
+
+        const N_REPS: usize = 3000;
+        let mut chosen = [0usize; 4];
+        for _ in 0..N_REPS {
+            // A filtered iterator has no exact size hint
+            let i = (0..4).filter(|_| true)
+                .choose_weighted(&mut r, |&i| weights[i]).unwrap();
+            chosen[i] += 1;
+        }
+        for (i, &count) in chosen.iter().enumerate() {
+            let expected = N_REPS as f64 * weights[i] as f64 / 7.0;
+            assert!((count as f64 - expected).abs() <= 0.15 * expected + 5.0);
+        }
+
+        let mut first = [0usize; 4];
+        for _ in 0..N_REPS {
+            let v = (0..4).choose_multiple_weighted(&mut r, 2, |&i| weights[i]).unwrap();
+            assert_eq!(v.len(), 2);
+            assert!(v[0] != v[1] && !v.contains(&1));
+            first[v[0]] += 1;
+        }
+        for (i, &count) in first.iter().enumerate() {
+            let expected = N_REPS as f64 * weights[i] as f64 / 7.0;
+            assert!((count as f64 - expected).abs() <= 0.15 * expected + 5.0);
+        }
+
+        // Only items with positive weight are returned
+        let mut v = (0..4).choose_multiple_weighted(&mut r, 10, |&i| weights[i]).unwrap();
+        v.sort();
+        assert_eq!(v, vec![0, 2, 3]);
+
+        assert_eq!((0..0).choose_weighted(&mut r, |_| 1.0), Err(WeightedError::NoItem));
+        assert_eq!((0..3).choose_weighted(&mut r, |_| 0.0), Err(WeightedError::AllWeightsZero));
+        assert_eq!((0..3).choose_weighted(&mut r, |&i| 1 - i), Err(WeightedError::NegativeWeight));
+        assert_eq!((0..3).choose_multiple_weighted(&mut r, 2, |_| ::core::f64::NAN),
+                   Err(WeightedError::InvalidWeight));
+    }
 }
@@ -15,6 +15,8 @@ main() {
     cross test --target $TARGET --manifest-path rand_xoshiro/Cargo.toml
     cross test --target $TARGET --manifest-path rand_chacha/Cargo.toml
     cross test --target $TARGET --manifest-path rand_hc/Cargo.toml
+    cross test --target $TARGET --manifest-path rand_aes/Cargo.toml
+    cross test --target $TARGET --manifest-path rand_drbg/Cargo.toml
     cross test --target $TARGET --manifest-path rand_os/Cargo.toml
     cross test --target $TARGET --manifest-path rand_jitter/Cargo.toml
 }
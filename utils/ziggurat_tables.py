@@ -84,24 +84,31 @@ EXP = tables(EXP_R, EXP_V,
 
 # Output the tables/constants/types
 
+def render_const(name, type, value):
+    return 'pub const %s: %s = %s;\n' % (name, type, value)
+
 def render_static(name, type, value):
     # no space or
     return 'pub static %s: %s =%s;\n' % (name, type, value)
 
-# static `name`: [`type`, .. `len(values)`] =
+# static `name`: [`type`; `len(values)`] =
 #     [values[0], ..., values[3],
 #      values[4], ..., values[7],
 #      ... ];
-def render_table(name, values):
+def render_table(name, type, fmt, values):
     rows = []
     # 4 values on each row
     for i in range(0, len(values), 4):
         row = values[i:i+4]
-        rows.append(', '.join('%.18f' % f for f in row))
+        rows.append(', '.join(fmt % f for f in row))
 
     rendered = '\n    [%s]' % ',\n     '.join(rows)
-    return render_static(name, '[f64, .. %d]' % len(values), rendered)
+    return render_static(name, '[%s; %d]' % (type, len(values)), rendered)
 
+# The f32 tables are the same values rounded to single precision; 9
+# significant digits are enough to round-trip any `f32`.
+FORMATS = [('f64', '', '%.18f'),
+           ('f32', '_F32', '%.9e')]
 
 with open('ziggurat_tables.rs', 'w') as f:
     f.write('''// Copyright 2018 Developers of the Rand project.
@@ -116,10 +123,12 @@ with open('ziggurat_tables.rs', 'w') as f:
 // Tables for distributions which are sampled using the ziggurat
 // algorithm. Autogenerated by `ziggurat_tables.py`.
 
-pub type ZigTable = &\'static [f64, .. %d];
+pub type ZigTable<F> = &\'static [F; %d];
 '''  % (TABLE_LEN + 1))
-    for name, tables, r in [('NORM', NORM, NORM_R),
-                            ('EXP', EXP, EXP_R)]:
-        f.write(render_static('ZIG_%s_R' % name, 'f64', ' %.18f' % r))
-        for (tabname, table) in zip(TABLE_NAMES, tables):
-            f.write(render_table('ZIG_%s_%s' % (name, tabname), table))
+    for type, suffix, fmt in FORMATS:
+        for name, tables, r in [('NORM', NORM, NORM_R),
+                                ('EXP', EXP, EXP_R)]:
+            f.write(render_const('ZIG_%s_R%s' % (name, suffix), type, fmt % r))
+            for (tabname, table) in zip(TABLE_NAMES, tables):
+                f.write(render_table('ZIG_%s_%s%s' % (name, tabname, suffix),
+                                     type, fmt, table))